@@ -1,10 +1,15 @@
 use cc;
 
 fn main() {
+    // cfg!(target_os) would describe the machine running this script, not the one being built for.
+    let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+
+    // Every backend is compiled everywhere; each one's source is wrapped in a check for its own
+    // platform, so the others compile to nothing.
     cc::Build::new()
-        .flag("-Wno-unused-parameter") // Suppress unused parameter warnings
-        .flag("-Wno-tautological-pointer-compare") // Suppress always false comparison warning
-        .flag("-Wno-unused-function") // Suppress unused function warnings
+        .flag_if_supported("-Wno-unused-parameter") // Suppress unused parameter warnings
+        .flag_if_supported("-Wno-tautological-pointer-compare") // Suppress always false comparison warning
+        .flag_if_supported("-Wno-unused-function") // Suppress unused function warnings
         .file("manymouse/linux_evdev.c")
         .file("manymouse/macosx_hidmanager.c")
         .file("manymouse/macosx_hidutilities.c")
@@ -13,8 +18,17 @@ fn main() {
         .file("manymouse/x11_xinput2.c")
         .compile("libmanymouse.a");
 
-    println!("cargo:rustc-link-lib=framework=CoreFoundation");
-    println!("cargo:rustc-link-lib=framework=IOKit");
+    match target_os.as_str() {
+        "macos" => {
+            println!("cargo:rustc-link-lib=framework=CoreFoundation");
+            println!("cargo:rustc-link-lib=framework=IOKit");
+        }
+        // The XInput2 backend loads the X11 libraries itself with dlopen.
+        "linux" => println!("cargo:rustc-link-lib=dl"),
+        // The WM_INPUT backend looks up user32 and setupapi at runtime, and kernel32 is always
+        // linked, so there's nothing extra to link.
+        _ => {}
+    }
 
     let bindings = bindgen::Builder::default()
         .header("manymouse/manymouse.h")
@@ -8,6 +8,7 @@
  */
 
 #include <stdlib.h>
+#include <string.h>
 #include "manymouse.h"
 
 static const char *manymouse_copyright =
@@ -42,9 +43,16 @@ static const ManyMouseDriver **mice_drivers[] =
 static const ManyMouseDriver *driver = NULL;
 
 int ManyMouse_Init(void)
+{
+    return ManyMouse_InitWithDriver(NULL);
+} /* ManyMouse_Init */
+
+
+int ManyMouse_InitWithDriver(const char *driver_name)
 {
     const int upper = (sizeof (mice_drivers) / sizeof (mice_drivers[0]));
     int i;
+    int mice;
     int retval = -1;
 
     /* impossible test to keep manymouse_copyright linked into the binary. */
@@ -57,19 +65,23 @@ int ManyMouse_Init(void)
     for (i = 0; (i < upper) && (driver == NULL); i++)
     {
         const ManyMouseDriver *this_driver = *(mice_drivers[i]);
-        if (this_driver != NULL) /* if not built for this platform, skip it. */
-        {
-            const int mice = this_driver->init();
-            if (mice > retval)
-                retval = mice; /* may move from "error" to "no mice found". */
-
-            if (mice >= 0)
-                driver = this_driver;
-        } /* if */
+        if (this_driver == NULL) /* if not built for this platform, skip it. */
+            continue;
+
+        /* if a particular driver was asked for, skip all the others. */
+        if ((driver_name != NULL) && (strcmp(this_driver->driver_name, driver_name) != 0))
+            continue;
+
+        mice = this_driver->init();
+        if (mice > retval)
+            retval = mice; /* may move from "error" to "no mice found". */
+
+        if (mice >= 0)
+            driver = this_driver;
     } /* for */
 
     return retval;
-} /* ManyMouse_Init */
+} /* ManyMouse_InitWithDriver */
 
 
 void ManyMouse_Quit(void)
@@ -0,0 +1,91 @@
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy_xpbd_2d::prelude::*;
+
+use super::{player::Cursor, spawn_level::RopeSegment, AppState, Difficulty, TEXT_COLOR};
+
+// Velocity lines are this long per meter/second of cursor speed, up to a maximum length.
+const VELOCITY_LINE_SCALE: f32 = 0.15;
+const MAX_VELOCITY_LINE_LENGTH: f32 = 1.5;
+const APEX_MARKER_RADIUS: f32 = 0.1;
+const GUIDE_ALPHA: f32 = 0.3;
+
+// A faint guide to help new players aim flings: a line along each cursor's velocity, and a
+// marker where the rope would peak if it were let fly now.
+pub struct AimingGuidePlugin;
+
+impl Plugin for AimingGuidePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AimingGuide>()
+            .add_systems(Update, toggle_guide.run_if(input_just_pressed(KeyCode::G)))
+            .add_systems(
+                Update,
+                draw_guide
+                    .run_if(in_state(AppState::Playing).or_else(in_state(AppState::Warmup)))
+                    .run_if(guide_enabled),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct AimingGuide {
+    pub enabled: bool,
+}
+
+impl Default for AimingGuide {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+fn guide_enabled(guide: Res<AimingGuide>, difficulty: Res<Difficulty>) -> bool {
+    guide.enabled && *difficulty != Difficulty::Hard
+}
+
+fn toggle_guide(mut guide: ResMut<AimingGuide>) {
+    guide.enabled = !guide.enabled;
+}
+
+fn draw_guide(
+    mut gizmos: Gizmos,
+    cursors: Query<(&Transform, &LinearVelocity), With<Cursor>>,
+    rope: Query<(&Transform, &LinearVelocity), With<RopeSegment>>,
+    gravity: Res<Gravity>,
+) {
+    let color = TEXT_COLOR.with_a(GUIDE_ALPHA);
+
+    for (transform, velocity) in cursors.iter() {
+        let start = transform.translation.truncate();
+        let offset = (velocity.0 * VELOCITY_LINE_SCALE).clamp_length_max(MAX_VELOCITY_LINE_LENGTH);
+        gizmos.line_2d(start, start + offset, color);
+    }
+
+    // Treat the rope as a single projectile at its center of mass.
+    let num_segments = rope.iter().count();
+    if num_segments == 0 {
+        return;
+    }
+    let (position_sum, velocity_sum) = rope.iter().fold(
+        (Vec2::ZERO, Vec2::ZERO),
+        |(position_sum, velocity_sum), (transform, velocity)| {
+            (
+                position_sum + transform.translation.truncate(),
+                velocity_sum + velocity.0,
+            )
+        },
+    );
+    let position = position_sum / num_segments as f32;
+    let velocity = velocity_sum / num_segments as f32;
+
+    // Only upward flings have an apex ahead of them.
+    let g = -gravity.0.y;
+    if velocity.y <= 0.0 || g <= 0.0 {
+        return;
+    }
+    let time_to_apex = velocity.y / g;
+    let apex = position
+        + Vec2::new(
+            velocity.x * time_to_apex,
+            velocity.y * velocity.y / (2.0 * g),
+        );
+    gizmos.circle_2d(apex, APEX_MARKER_RADIUS, color);
+}
@@ -0,0 +1,81 @@
+use bevy::{core_pipeline::clear_color::ClearColorConfig, prelude::*};
+
+use super::{
+    gameplay::LevelState,
+    stats::{track_sorts, ShapeSorted},
+    AppState, BACKGROUND_COLOR,
+};
+
+// How far the background hue rotates, in degrees, by the time intensity reaches 1.
+const HUE_DRIFT_DEGREES: f32 = 40.0;
+// A wrong sort darkens the background by this fraction of its lightness, fading out over
+// DARKEN_SECONDS.
+const DARKEN_AMOUNT: f32 = 0.4;
+const DARKEN_SECONDS: f32 = 0.5;
+// Each correct sort that extends a combo brightens the background, fading out over PULSE_SECONDS.
+const PULSE_LIGHTNESS: f32 = 0.06;
+const PULSE_SECONDS: f32 = 0.6;
+
+pub struct BackgroundPlugin;
+
+impl Plugin for BackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BackgroundEffects>().add_systems(
+            Update,
+            (react_to_sorts, animate_background)
+                .chain()
+                .after(track_sorts),
+        );
+    }
+}
+
+// Strength of each transient effect, from 1 when triggered decaying to 0.
+#[derive(Resource, Default)]
+struct BackgroundEffects {
+    darken: f32,
+    pulse: f32,
+}
+
+fn react_to_sorts(
+    mut sorted_events: EventReader<ShapeSorted>,
+    mut effects: ResMut<BackgroundEffects>,
+) {
+    for event in sorted_events.iter() {
+        if !event.correct {
+            effects.darken = 1.0;
+        } else if event.combo >= 2 {
+            effects.pulse = 1.0;
+        }
+    }
+}
+
+fn animate_background(
+    mut effects: ResMut<BackgroundEffects>,
+    level_state: Option<Res<LevelState>>,
+    app_state: Res<State<AppState>>,
+    mut cameras: Query<&mut Camera2d>,
+    time: Res<Time>,
+) {
+    effects.darken = f32::max(effects.darken - time.delta_seconds() / DARKEN_SECONDS, 0.0);
+    effects.pulse = f32::max(effects.pulse - time.delta_seconds() / PULSE_SECONDS, 0.0);
+
+    let intensity = match (app_state.get(), level_state) {
+        (AppState::Init, _) | (_, None) => 0.0,
+        (_, Some(level_state)) => level_state.intensity,
+    };
+
+    let [hue, saturation, lightness, alpha] = BACKGROUND_COLOR.as_hsla_f32();
+    let hue = (hue + intensity * HUE_DRIFT_DEGREES).rem_euclid(360.0);
+    let lightness = lightness * (1.0 - DARKEN_AMOUNT * effects.darken)
+        + PULSE_LIGHTNESS * smooth_pulse(effects.pulse);
+    let color = Color::hsla(hue, saturation, lightness.clamp(0.0, 1.0), alpha);
+
+    for mut camera in cameras.iter_mut() {
+        camera.clear_color = ClearColorConfig::Custom(color);
+    }
+}
+
+// Eases the pulse in and out rather than snapping to full brightness.
+fn smooth_pulse(strength: f32) -> f32 {
+    (strength * std::f32::consts::PI).sin()
+}
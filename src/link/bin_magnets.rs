@@ -0,0 +1,256 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_xpbd_2d::prelude::*;
+use rand::Rng;
+
+use super::{
+    gameplay::{BinAssignment, InBin, Settled, Shape},
+    levels::CurrentLevel,
+    locale::Localizer,
+    spawn_level::{Layer, RopeSegment, HEIGHT},
+    stalls::StallState,
+    text_style::TextStyler,
+    AppState, TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+// A pickup turns up every so often, somewhere in the middle of the playfield, and disappears if
+// it isn't collected in time.
+const PICKUP_MIN_SECONDS: f32 = 20.0;
+const PICKUP_MAX_SECONDS: f32 = 35.0;
+const PICKUP_LIFETIME_SECONDS: f32 = 8.0;
+const PICKUP_RADIUS: f32 = 0.2;
+const PICKUP_REGION: Rect = Rect {
+    min: Vec2::new(-3.0, 0.5),
+    max: Vec2::new(3.0, 2.5),
+};
+
+// Once collected, each bin pulls on shapes of its kind within this many meters of it, harder the
+// closer they are, up to this many meters per second squared.
+const MAGNET_SECONDS: f32 = 10.0;
+const MAGNET_RADIUS: f32 = 3.0;
+const MAGNET_ACCELERATION: f32 = 4.0;
+
+// An occasional power-up: touch it with the rope and for a while each bin gently draws in the
+// shapes that belong in it.
+pub struct BinMagnetPlugin;
+
+impl Plugin for BinMagnetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BinMagnets>()
+            .add_systems(OnEnter(AppState::Playing), start_pickups)
+            .add_systems(Startup, spawn_magnet_display)
+            .add_systems(
+                Update,
+                (
+                    schedule_pickups,
+                    expire_pickups,
+                    collect_pickups,
+                    tick_magnets,
+                )
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(in_state(StallState::Running)),
+            )
+            .add_systems(
+                FixedUpdate,
+                attract_shapes
+                    .before(PhysicsSet::Prepare)
+                    .run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(Update, display_magnets)
+            .add_systems(
+                OnExit(AppState::Playing),
+                (cleanup_system::<MagnetPickup>, stop_magnets),
+            );
+    }
+}
+
+/// How much longer the bins' magnets are on for, in seconds.
+#[derive(Resource, Default)]
+struct BinMagnets {
+    remaining_seconds: f32,
+}
+
+#[derive(Resource)]
+struct PickupScheduler {
+    timer: Timer,
+}
+
+#[derive(Component)]
+struct MagnetPickup {
+    lifetime: Timer,
+}
+
+#[derive(Component)]
+struct MagnetDisplay;
+
+fn pickup_delay() -> Duration {
+    let mut rng = rand::thread_rng();
+    Duration::from_secs_f32(rng.gen_range(PICKUP_MIN_SECONDS..PICKUP_MAX_SECONDS))
+}
+
+fn start_pickups(mut commands: Commands) {
+    commands.insert_resource(PickupScheduler {
+        timer: Timer::new(pickup_delay(), TimerMode::Once),
+    });
+}
+
+fn stop_magnets(mut magnets: ResMut<BinMagnets>) {
+    magnets.remaining_seconds = 0.0;
+}
+
+fn schedule_pickups(
+    mut commands: Commands,
+    mut scheduler: ResMut<PickupScheduler>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    if !scheduler.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    scheduler.timer.set_duration(pickup_delay());
+    scheduler.timer.reset();
+
+    let mut rng = rand::thread_rng();
+    let x = rng.gen_range(PICKUP_REGION.min.x..PICKUP_REGION.max.x);
+    let y = rng.gen_range(PICKUP_REGION.min.y..PICKUP_REGION.max.y);
+    commands.spawn((
+        MaterialMesh2dBundle {
+            transform: Transform::from_xyz(x, y, 0.5),
+            mesh: meshes
+                .add(
+                    shape::Circle {
+                        radius: PICKUP_RADIUS,
+                        ..default()
+                    }
+                    .into(),
+                )
+                .into(),
+            material: materials.add(ColorMaterial::from(TEXT_COLOR)),
+            ..default()
+        },
+        RigidBody::Static,
+        Collider::ball(PICKUP_RADIUS),
+        // Touched rather than pushed, and by the rope alone.
+        Sensor,
+        CollisionLayers::new([Layer::Hazards], [Layer::Rope]),
+        MagnetPickup {
+            lifetime: Timer::from_seconds(PICKUP_LIFETIME_SECONDS, TimerMode::Once),
+        },
+        Name::new("MagnetPickup"),
+    ));
+}
+
+// Pulses as it waits, faster as it's about to disappear.
+fn expire_pickups(
+    mut commands: Commands,
+    mut pickups: Query<(Entity, &mut MagnetPickup, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut pickup, mut transform) in pickups.iter_mut() {
+        if pickup.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let elapsed = pickup.lifetime.elapsed_secs();
+        let rate = 4.0 + 8.0 * pickup.lifetime.percent();
+        transform.scale = Vec3::splat(1.0 + 0.15 * (elapsed * rate).sin());
+    }
+}
+
+fn collect_pickups(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionStarted>,
+    pickups: Query<(), With<MagnetPickup>>,
+    segments: Query<(), With<RopeSegment>>,
+    mut magnets: ResMut<BinMagnets>,
+) {
+    for CollisionStarted(a, b) in collisions.iter() {
+        let (pickup, segment) = match pickups.contains(*a) {
+            true => (*a, *b),
+            false => (*b, *a),
+        };
+        if !pickups.contains(pickup) || !segments.contains(segment) {
+            continue;
+        }
+        println!("Bin magnets on for {}s", MAGNET_SECONDS);
+        commands.entity(pickup).despawn_recursive();
+        magnets.remaining_seconds = MAGNET_SECONDS;
+    }
+}
+
+fn tick_magnets(mut magnets: ResMut<BinMagnets>, time: Res<Time>) {
+    if magnets.remaining_seconds > 0.0 {
+        magnets.remaining_seconds = (magnets.remaining_seconds - time.delta_seconds()).max(0.0);
+    }
+}
+
+// Only shapes still on their way to a bin are pulled. Once a shape is inside a bin's sensor it's
+// left to settle on its own, and a sleeping shape isn't woken up.
+type Unbinned = (Without<InBin>, Without<Settled>, Without<Sleeping>);
+
+fn attract_shapes(
+    magnets: Res<BinMagnets>,
+    current_level: CurrentLevel,
+    assignment: Res<BinAssignment>,
+    mut shapes: Query<(&Position, &Shape, &Mass, &mut ExternalForce), Unbinned>,
+) {
+    if magnets.remaining_seconds <= 0.0 {
+        return;
+    }
+    for (position, shape, mass, mut force) in shapes.iter_mut() {
+        let Some(bin) = assignment.bin(*shape) else {
+            continue;
+        };
+        let offset = bin.region(current_level.get()).center() - position.0;
+        let distance = offset.length();
+        if distance >= MAGNET_RADIUS || distance == 0.0 {
+            continue;
+        }
+        let falloff = 1.0 - distance / MAGNET_RADIUS;
+        force.apply_force(offset / distance * MAGNET_ACCELERATION * falloff * mass.0);
+    }
+}
+
+fn spawn_magnet_display(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, HEIGHT / 2.0 - 2.6, 1.0)
+                .with_scale(Vec3::splat(0.003)),
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: TEXT_COLOR,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        MagnetDisplay,
+        Name::new("MagnetDisplay"),
+    ));
+}
+
+fn display_magnets(
+    magnets: Res<BinMagnets>,
+    localizer: Localizer,
+    mut displays: Query<&mut Text, With<MagnetDisplay>>,
+) {
+    if !magnets.is_changed() {
+        return;
+    }
+    let value = match magnets.remaining_seconds > 0.0 {
+        true => localizer.format(
+            "bin_magnets.remaining",
+            &[&format!("{:.0}", magnets.remaining_seconds.ceil())],
+        ),
+        false => String::new(),
+    };
+    for mut text in displays.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
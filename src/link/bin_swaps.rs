@@ -0,0 +1,255 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    gameplay::{Bin, BinAssignment, LevelState, Shape},
+    levels::CurrentLevel,
+    locale::Localizer,
+    palette::Palette,
+    spawn_level::BinWall,
+    stalls::StallState,
+    text_style::TextStyler,
+    AppState, TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+// Swaps only happen once the level is this intense.
+const SWAP_MIN_INTENSITY: f32 = 0.6;
+const SWAP_MIN_DELAY_SECONDS: f32 = 20.0;
+const SWAP_MAX_DELAY_SECONDS: f32 = 35.0;
+const SWAP_WARNING_SECONDS: f32 = 3.0;
+// For this long after a swap, shapes landing where they used to belong still count as correct.
+const SWAP_GRACE_SECONDS: f32 = 3.0;
+// Swapping bins' walls and signs flash between their old and new colors this many times a second.
+const FLASH_RATE: f32 = 3.0;
+
+// Late in a game, two bins now and then trade the shapes they accept. The bins' walls flash
+// between their old and new colors as a warning, signs above them say what they take now, and
+// for a moment afterwards the old assignment is still honored.
+pub struct BinSwapPlugin;
+
+impl Plugin for BinSwapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Playing), start_bin_swaps)
+            .add_systems(
+                Update,
+                (run_bin_swaps, announce_bin_swap, display_bin_signs)
+                    .chain()
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(in_state(StallState::Running)),
+            )
+            .add_systems(Update, color_bin_walls)
+            .add_systems(
+                OnExit(AppState::Playing),
+                (
+                    cleanup_system::<BinSwapBanner>,
+                    cleanup_system::<BinSign>,
+                    end_bin_swaps,
+                ),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct BinSwaps {
+    timer: Timer,
+    // The bins about to trade shapes.
+    warning: Option<(Bin, Bin, Timer)>,
+    grace: Option<Timer>,
+}
+
+#[derive(Component)]
+struct BinSwapBanner;
+
+// Hangs above a bin, naming the shape it accepts while the bins are out of their usual order.
+#[derive(Component)]
+struct BinSign(Bin);
+
+fn swap_delay() -> Duration {
+    let mut rng = rand::thread_rng();
+    Duration::from_secs_f32(rng.gen_range(SWAP_MIN_DELAY_SECONDS..SWAP_MAX_DELAY_SECONDS))
+}
+
+fn mix(from: Color, to: Color, t: f32) -> Color {
+    Color::from(Vec4::from(from).lerp(Vec4::from(to), t))
+}
+
+fn flash(time: &Time) -> f32 {
+    0.5 + 0.5 * (time.elapsed_seconds() * FLASH_RATE * std::f32::consts::TAU).sin()
+}
+
+fn plural(shape: Shape, localizer: &Localizer) -> String {
+    localizer.get(match shape {
+        Shape::Square => "shape.squares",
+        Shape::Circle => "shape.circles",
+        Shape::Triangle => "shape.triangles",
+    })
+}
+
+fn start_bin_swaps(mut commands: Commands, current_level: CurrentLevel, text_styler: TextStyler) {
+    commands.insert_resource(BinSwaps {
+        timer: Timer::new(swap_delay(), TimerMode::Once),
+        warning: None,
+        grace: None,
+    });
+    let text_style = TextStyle {
+        font: text_styler.font(),
+        font_size: 100.0,
+        color: TEXT_COLOR,
+    };
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 0.0, 3.0).with_scale(Vec3::splat(0.005)),
+            text: Text::from_section("", text_style.clone()).with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        BinSwapBanner,
+        Name::new("BinSwapBanner"),
+    ));
+    for bin in Bin::ALL {
+        let region = bin.region(current_level.get());
+        commands.spawn((
+            Text2dBundle {
+                transform: Transform::from_xyz(region.center().x, region.max.y + 0.4, 3.0)
+                    .with_scale(Vec3::splat(0.003)),
+                text: Text::from_section("", text_style.clone())
+                    .with_alignment(TextAlignment::Center),
+                ..default()
+            },
+            BinSign(bin),
+            Name::new(format!("BinSign ({:?})", bin)),
+        ));
+    }
+}
+
+fn end_bin_swaps(mut commands: Commands, mut assignment: ResMut<BinAssignment>) {
+    commands.remove_resource::<BinSwaps>();
+    assignment.set_if_neq(BinAssignment::default());
+}
+
+// Waits out the delay, then the warning, then the grace period, and starts over.
+fn run_bin_swaps(
+    mut swaps: ResMut<BinSwaps>,
+    mut assignment: ResMut<BinAssignment>,
+    level_state: Res<LevelState>,
+    time: Res<Time>,
+) {
+    if let Some(grace) = swaps.grace.as_mut() {
+        if grace.tick(time.delta()).just_finished() {
+            assignment.end_grace();
+            swaps.grace = None;
+        }
+        return;
+    }
+    if let Some((a, b, warning)) = swaps.warning.as_mut() {
+        if warning.tick(time.delta()).just_finished() {
+            println!("Swapping the {:?} and {:?} bins", a, b);
+            assignment.swap(*a, *b);
+            swaps.warning = None;
+            swaps.grace = Some(Timer::from_seconds(SWAP_GRACE_SECONDS, TimerMode::Once));
+        }
+        return;
+    }
+    if level_state.intensity < SWAP_MIN_INTENSITY {
+        return;
+    }
+    if swaps.timer.tick(time.delta()).just_finished() {
+        let mut bins = Bin::ALL.to_vec();
+        bins.shuffle(&mut rand::thread_rng());
+        swaps.warning = Some((
+            bins[0],
+            bins[1],
+            Timer::from_seconds(SWAP_WARNING_SECONDS, TimerMode::Once),
+        ));
+        swaps.timer.set_duration(swap_delay());
+        swaps.timer.reset();
+    }
+}
+
+fn announce_bin_swap(
+    swaps: Res<BinSwaps>,
+    localizer: Localizer,
+    mut banners: Query<&mut Text, With<BinSwapBanner>>,
+) {
+    let value = match (&swaps.warning, &swaps.grace) {
+        (Some(_), _) => localizer.get("bin_swaps.warning"),
+        (None, Some(grace)) => localizer.format(
+            "bin_swaps.grace",
+            &[&grace.remaining_secs().ceil().to_string()],
+        ),
+        (None, None) => String::new(),
+    };
+    for mut text in banners.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
+
+// The shape a bin is about to accept, if it's one of the pair being swapped.
+fn incoming_shape(swaps: Option<&BinSwaps>, assignment: &BinAssignment, bin: Bin) -> Option<Shape> {
+    let (a, b, _) = swaps?.warning.as_ref()?;
+    if bin == *a {
+        Some(assignment.shape(*b))
+    } else if bin == *b {
+        Some(assignment.shape(*a))
+    } else {
+        None
+    }
+}
+
+fn display_bin_signs(
+    swaps: Res<BinSwaps>,
+    assignment: Res<BinAssignment>,
+    palette: Res<Palette>,
+    localizer: Localizer,
+    mut signs: Query<(&BinSign, &mut Text)>,
+    time: Res<Time>,
+) {
+    for (sign, mut text) in signs.iter_mut() {
+        let current = assignment.shape(sign.0);
+        let (value, color) = match incoming_shape(Some(&swaps), &assignment, sign.0) {
+            Some(incoming) => (
+                plural(incoming, &localizer),
+                mix(
+                    palette.shape(current),
+                    palette.shape(incoming),
+                    flash(&time),
+                ),
+            ),
+            None if assignment.is_swapped() => {
+                (plural(current, &localizer), palette.shape(current))
+            }
+            None => (String::new(), palette.shape(current)),
+        };
+        text.sections[0].value = value;
+        text.sections[0].style.color = color;
+    }
+}
+
+// Runs all the time, so the walls go back to their usual colors when the game ends, and walls
+// rebuilt for a new level pick up the current assignment and palette.
+fn color_bin_walls(
+    swaps: Option<Res<BinSwaps>>,
+    assignment: Res<BinAssignment>,
+    palette: Res<Palette>,
+    walls: Query<(&BinWall, &Handle<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    for (wall, handle) in walls.iter() {
+        let current = palette.shape(assignment.shape(wall.0));
+        let color = match incoming_shape(swaps.as_deref(), &assignment, wall.0) {
+            Some(incoming) => mix(current, palette.shape(incoming), flash(&time)),
+            None => current,
+        };
+        if materials
+            .get(handle)
+            .is_some_and(|material| material.color != color)
+        {
+            if let Some(material) = materials.get_mut(handle) {
+                material.color = color;
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, utils::HashMap};
+
+use super::{
+    locale::{Localizer, TextKey},
+    text_style::TextStyler,
+    title_hints::TitleHint,
+    AppState, DespawnOnExitInit, TEXT_COLOR,
+};
+use crate::{
+    mischief::{
+        accumulate_motion, MischiefDeviceSettings, MischiefDevices, MouseMotionAccumulator,
+    },
+    util::cleanup_system,
+};
+
+// Players slide their mouse this far along a ruler to measure its resolution.
+const CALIBRATION_INCHES: f32 = 4.0;
+// Drags shorter than this many counts are assumed to be accidental and ignored.
+const MIN_CALIBRATION_COUNTS: f32 = 100.0;
+
+// Press C on the title screen to measure each mouse's resolution (counts per inch), so a high DPI
+// mouse doesn't get a faster cursor than a low DPI one. Values can also be entered by hand in the
+// inspector, under `MischiefDeviceSettings`, and either way are saved with each mouse's settings.
+pub struct CalibrationPlugin;
+
+impl Plugin for CalibrationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CalibrationDrags>()
+            .add_systems(OnEnter(AppState::Init), spawn_calibration_hint)
+            .add_systems(
+                Update,
+                toggle_calibration
+                    .run_if(input_just_pressed(KeyCode::C))
+                    .run_if(in_state(AppState::Init).or_else(in_state(AppState::Calibrating))),
+            )
+            .add_systems(OnEnter(AppState::Calibrating), spawn_calibration_screen)
+            .add_systems(
+                Update,
+                (measure_drags.after(accumulate_motion), display_calibration)
+                    .chain()
+                    .run_if(in_state(AppState::Calibrating)),
+            )
+            .add_systems(
+                OnExit(AppState::Calibrating),
+                cleanup_system::<CalibrationScreen>,
+            );
+    }
+}
+
+// Raw motion of each device since Space was pressed.
+#[derive(Resource, Default)]
+struct CalibrationDrags(HashMap<u32, Vec2>);
+
+#[derive(Component)]
+struct CalibrationScreen;
+
+#[derive(Component)]
+struct CalibrationResults;
+
+fn toggle_calibration(state: Res<State<AppState>>, mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(match state.get() {
+        AppState::Calibrating => AppState::Init,
+        _ => AppState::Calibrating,
+    });
+}
+
+fn spawn_calibration_hint(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: TitleHint::Calibration.transform(),
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: TEXT_COLOR.with_a(0.6),
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        DespawnOnExitInit,
+        TextKey("title.calibration_hint"),
+        Name::new("CalibrationHint"),
+    ));
+}
+
+fn spawn_calibration_screen(
+    mut commands: Commands,
+    mut drags: ResMut<CalibrationDrags>,
+    localizer: Localizer,
+    text_styler: TextStyler,
+) {
+    drags.0.clear();
+
+    let text_style = TextStyle {
+        font: text_styler.font(),
+        font_size: 100.0,
+        color: TEXT_COLOR,
+    };
+    commands
+        .spawn((
+            SpatialBundle::default(),
+            CalibrationScreen,
+            Name::new("CalibrationScreen"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, 2.5, 1.0).with_scale(Vec3::splat(0.003)),
+                    text: Text {
+                        sections: vec![TextSection::new(
+                            localizer.format(
+                                "calibration.instructions",
+                                &[
+                                    &CALIBRATION_INCHES.to_string(),
+                                    &format!("{:.0}", CALIBRATION_INCHES * 2.54),
+                                ],
+                            ),
+                            text_style.clone(),
+                        )],
+                        alignment: TextAlignment::Center,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    ..default()
+                },
+                Name::new("CalibrationInstructions"),
+            ));
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, 1.5, 1.0).with_scale(Vec3::splat(0.003)),
+                    text_anchor: bevy::sprite::Anchor::TopCenter,
+                    text: Text {
+                        sections: vec![TextSection::new("", text_style)],
+                        alignment: TextAlignment::Center,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    ..default()
+                },
+                CalibrationResults,
+                Name::new("CalibrationResults"),
+            ));
+        });
+}
+
+fn measure_drags(
+    mut drags: ResMut<CalibrationDrags>,
+    mut settings: ResMut<MischiefDeviceSettings>,
+    motion: Res<MouseMotionAccumulator>,
+    keys: Res<Input<KeyCode>>,
+) {
+    if keys.pressed(KeyCode::Space) {
+        for (device, motion) in motion.iter() {
+            *drags.0.entry(device).or_default() += motion.as_vec2();
+        }
+    }
+
+    if !keys.just_released(KeyCode::Space) {
+        return;
+    }
+    // Measure the length of the drag, so it doesn't matter which way the ruler was lying.
+    for (&device, drag) in drags.0.iter() {
+        let counts = drag.length();
+        if counts < MIN_CALIBRATION_COUNTS {
+            continue;
+        }
+        let counts_per_inch = counts / CALIBRATION_INCHES;
+        println!(
+            "Calibrated device {} at {:.0} counts per inch",
+            device, counts_per_inch
+        );
+        settings.devices.entry(device).or_default().counts_per_inch = counts_per_inch;
+    }
+    drags.0.clear();
+}
+
+fn display_calibration(
+    devices: Res<MischiefDevices>,
+    drags: Res<CalibrationDrags>,
+    settings: Res<MischiefDeviceSettings>,
+    localizer: Localizer,
+    mut results: Query<&mut Text, With<CalibrationResults>>,
+) {
+    let lines = devices
+        .connected()
+        .map(|device| {
+            let measuring = drags.0.get(&device.id).map(|drag| drag.length());
+            match (measuring, settings.get(device.id).counts_per_inch) {
+                (Some(counts), _) => localizer.format(
+                    "calibration.measuring",
+                    &[&device.name, &format!("{:.0}", counts)],
+                ),
+                (None, cpi) if cpi > 0.0 => localizer.format(
+                    "calibration.calibrated",
+                    &[&device.name, &format!("{:.0}", cpi)],
+                ),
+                (None, _) => localizer.format("calibration.uncalibrated", &[&device.name]),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for mut text in results.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use bevy_xpbd_2d::prelude::*;
+
+use super::{gameplay::Shape, player::Cursor, spawn_level::RopeSegment, stats::RunStats, AppState};
+
+// Positions and velocities are rounded to this many steps per meter before hashing, so float noise
+// below a millimeter doesn't change the checksum.
+const QUANTIZE_STEPS_PER_METER: f32 = 1000.0;
+
+// FNV-1a, which is tiny and stable across platforms and Rust versions, unlike std's hasher.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// Folds the simulation state into a rolling checksum after every physics step of a run. The
+// checksum is saved with the run, so anyone re-simulating the run from the same inputs can check
+// they reach the same state and score.
+pub struct ChecksumPlugin;
+
+impl Plugin for ChecksumPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            update_checksum
+                .after(PhysicsSet::Sync)
+                .run_if(in_state(AppState::Playing))
+                .run_if(resource_exists::<RunStats>()),
+        );
+    }
+}
+
+fn fnv(hash: u64, value: i64) -> u64 {
+    value.to_le_bytes().iter().fold(hash, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Hashes a list of values the same way the simulation checksum does, for other saved data that
+/// needs to notice being edited, see `LeaderboardPlugin`.
+pub fn checksum_of(values: impl IntoIterator<Item = i64>) -> u64 {
+    values.into_iter().fold(FNV_OFFSET, fnv)
+}
+
+fn quantize(value: f32) -> i64 {
+    (value * QUANTIZE_STEPS_PER_METER).round() as i64
+}
+
+fn body_hash(kind: i64, position: Vec2, velocity: Vec2) -> u64 {
+    [
+        kind,
+        quantize(position.x),
+        quantize(position.y),
+        quantize(velocity.x),
+        quantize(velocity.y),
+    ]
+    .into_iter()
+    .fold(FNV_OFFSET, fnv)
+}
+
+fn update_checksum(
+    mut run_stats: ResMut<RunStats>,
+    shapes: Query<(&Position, &LinearVelocity, &Shape)>,
+    cursors: Query<(&Position, &LinearVelocity), With<Cursor>>,
+    rope: Query<(&Position, &LinearVelocity), With<RopeSegment>>,
+) {
+    // Query order isn't stable between runs, so bodies are combined with an order-independent sum.
+    let mut step_hash: u64 = 0;
+    for (position, velocity, shape) in shapes.iter() {
+        let kind = match shape {
+            Shape::Square => 0,
+            Shape::Circle => 1,
+            // Cursors and rope segments are 2 and 3.
+            Shape::Triangle => 4,
+        };
+        step_hash = step_hash.wrapping_add(body_hash(kind, position.0, velocity.0));
+    }
+    for (position, velocity) in cursors.iter() {
+        step_hash = step_hash.wrapping_add(body_hash(2, position.0, velocity.0));
+    }
+    for (position, velocity) in rope.iter() {
+        step_hash = step_hash.wrapping_add(body_hash(3, position.0, velocity.0));
+    }
+
+    // Steps are chained in order, so the checksum also captures when things happened.
+    run_stats.checksum = fnv(run_stats.checksum, step_hash as i64);
+}
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+
+use super::{
+    frenzy::Frenzy,
+    gameplay::Score,
+    locale::{Localizer, TextKey},
+    popups::Popup,
+    spawn_level::HEIGHT,
+    stats::{track_sorts, ShapeSorted},
+    text_style::TextStyler,
+    AppState, TEXT_COLOR,
+};
+
+// Each correct sort after the first in a row earns one more bonus point than the last, up to this
+// many per sort, when the meter is full.
+const MAX_COMBO_BONUS: u32 = 5;
+// The meter sits under the clock, at the top of the screen.
+const METER_SIZE: Vec2 = Vec2::new(2.0, 0.1);
+const METER_Y: f32 = HEIGHT / 2.0 - 2.2;
+// Every combo this long, and each multiple of it, is called out over the shape that reached it.
+const COMBO_MILESTONE: u32 = 5;
+
+// Sorting shapes correctly one after another builds a combo, worth escalating bonus points. A
+// mistake breaks it. The combo is shown on a meter during play, and called out at milestones.
+pub struct ComboPlugin;
+
+impl Plugin for ComboPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Combo>()
+            .add_systems(Startup, spawn_combo_meter)
+            .add_systems(OnEnter(AppState::Playing), reset_combo)
+            .add_systems(
+                Update,
+                award_combo_bonus
+                    .after(track_sorts)
+                    .run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(Update, display_combo_meter);
+    }
+}
+
+#[derive(Resource, Default)]
+struct Combo {
+    // Consecutive correct sorts, including the latest.
+    length: u32,
+    // The bonus the latest sort earned.
+    bonus: u32,
+}
+
+#[derive(Component)]
+struct ComboMeter;
+
+#[derive(Component)]
+struct ComboMeterFill;
+
+#[derive(Component)]
+struct ComboLabel;
+
+fn combo_bonus(length: u32) -> u32 {
+    length.saturating_sub(1).min(MAX_COMBO_BONUS)
+}
+
+fn reset_combo(mut combo: ResMut<Combo>) {
+    *combo = Combo::default();
+}
+
+fn award_combo_bonus(
+    mut sorted_events: EventReader<ShapeSorted>,
+    mut combo: ResMut<Combo>,
+    mut score: ResMut<Score>,
+    mut popups: EventWriter<Popup>,
+    frenzy: Res<Frenzy>,
+    localizer: Localizer,
+    shapes: Query<&Transform>,
+) {
+    for event in sorted_events.iter() {
+        combo.length = match event.correct {
+            true => event.combo,
+            false => 0,
+        };
+        combo.bonus = combo_bonus(combo.length);
+        score.bonus += combo.bonus as i32 * frenzy.multiplier();
+        if combo.length > 0 && combo.length.is_multiple_of(COMBO_MILESTONE) {
+            // Above the shape's own points popup.
+            let position = shapes.get(event.shape).map_or(Vec2::ZERO, |transform| {
+                transform.translation.truncate() + Vec2::new(0.0, 0.5)
+            });
+            let label = localizer.format("combo.milestone", &[&combo.length.to_string()]);
+            popups.send(Popup::new(label, position).with_scale(0.005));
+        }
+    }
+}
+
+fn spawn_combo_meter(mut commands: Commands, text_styler: TextStyler) {
+    let text_style = TextStyle {
+        font: text_styler.font(),
+        font_size: 100.0,
+        color: TEXT_COLOR,
+    };
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: TEXT_COLOR.with_a(0.2),
+                    custom_size: Some(METER_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, METER_Y, 1.0),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            ComboMeter,
+            Name::new("ComboMeter"),
+        ))
+        .with_children(|meter| {
+            meter.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: TEXT_COLOR,
+                        custom_size: Some(METER_SIZE),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                    ..default()
+                },
+                ComboMeterFill,
+            ));
+            meter.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(-METER_SIZE.x / 2.0 - 0.1, 0.0, 0.1)
+                        .with_scale(Vec3::splat(0.002)),
+                    text: Text::from_section("", text_style.clone())
+                        .with_alignment(TextAlignment::Right),
+                    text_anchor: bevy::sprite::Anchor::CenterRight,
+                    ..default()
+                },
+                TextKey("combo.meter"),
+            ));
+            meter.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(METER_SIZE.x / 2.0 + 0.1, 0.0, 0.1)
+                        .with_scale(Vec3::splat(0.002)),
+                    text: Text::from_section("", text_style).with_alignment(TextAlignment::Left),
+                    text_anchor: bevy::sprite::Anchor::CenterLeft,
+                    ..default()
+                },
+                ComboLabel,
+            ));
+        });
+}
+
+fn display_combo_meter(
+    combo: Res<Combo>,
+    state: Res<State<AppState>>,
+    mut meters: Query<&mut Visibility, With<ComboMeter>>,
+    mut fills: Query<&mut Transform, With<ComboMeterFill>>,
+    mut labels: Query<&mut Text, With<ComboLabel>>,
+) {
+    for mut visibility in meters.iter_mut() {
+        *visibility = match state.get() {
+            AppState::Playing => Visibility::Inherited,
+            _ => Visibility::Hidden,
+        };
+    }
+
+    let fullness = combo.bonus as f32 / MAX_COMBO_BONUS as f32;
+    for mut transform in fills.iter_mut() {
+        transform.scale.x = fullness;
+        // Grows from the left edge.
+        transform.translation.x = -METER_SIZE.x / 2.0 * (1.0 - fullness);
+    }
+    let label = match combo.length {
+        0 | 1 => String::new(),
+        length => format!("x{}  +{}", length, combo.bonus),
+    };
+    for mut text in labels.iter_mut() {
+        text.sections[0].value = label.clone();
+    }
+}
@@ -0,0 +1,124 @@
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy_xpbd_2d::prelude::*;
+
+use super::player::{apply_cursor_force, CursorSlot, PIDController, TargetVelocity};
+
+// In position mode a cursor's target can run at most this far ahead of the cursor, in meters, so
+// pushing against a wall doesn't wind up motion that all comes out once the cursor is free.
+const MAX_TARGET_LEAD: f32 = 0.5;
+
+// Press M to switch how the mice drive the cursors. By default each cursor is a physical body
+// pushed towards the mouse's velocity, which gives it weight but some lag. In position mode each
+// cursor is a kinematic body placed exactly where the mouse has moved it, for players who'd rather
+// have 1:1 response.
+pub struct CursorControlPlugin;
+
+impl Plugin for CursorControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CursorControl>()
+            .init_resource::<CursorControl>()
+            .add_systems(
+                Update,
+                (
+                    toggle_cursor_control.run_if(input_just_pressed(KeyCode::M)),
+                    apply_cursor_control,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                FixedUpdate,
+                drive_positioned_cursors
+                    .after(apply_cursor_force)
+                    .before(PhysicsSet::Prepare),
+            );
+    }
+}
+
+#[derive(Resource, Reflect, Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[reflect(Resource)]
+pub enum CursorControl {
+    /// Cursors are dynamic bodies, pushed by their PID controllers.
+    #[default]
+    Force,
+    /// Cursors are kinematic bodies, moved straight to where the mouse puts them.
+    Position,
+}
+
+/// Where a cursor in position mode is headed: everywhere the mouse has moved it since the mode
+/// was switched on.
+#[derive(Component, Debug)]
+struct PositionTarget(Vec2);
+
+fn toggle_cursor_control(mut control: ResMut<CursorControl>) {
+    *control = match *control {
+        CursorControl::Force => CursorControl::Position,
+        CursorControl::Position => CursorControl::Force,
+    };
+    println!("Cursor control: {:?}", *control);
+}
+
+// Reapplied to every cursor when the mode changes, and to cursors as they spawn.
+fn apply_cursor_control(
+    mut commands: Commands,
+    control: Res<CursorControl>,
+    mut cursors: Query<
+        (
+            Entity,
+            // Not `Position`, which cursors only get once they've been through a physics step.
+            &Transform,
+            &mut RigidBody,
+            &mut PIDController,
+            &mut LinearVelocity,
+        ),
+        With<CursorSlot>,
+    >,
+    added: Query<(), Added<CursorSlot>>,
+) {
+    if !control.is_changed() && added.is_empty() {
+        return;
+    }
+    for (entity, transform, mut body, mut controller, mut velocity) in cursors.iter_mut() {
+        match *control {
+            CursorControl::Force => {
+                if *body == RigidBody::Dynamic {
+                    continue;
+                }
+                *body = RigidBody::Dynamic;
+                // The controller sat idle in position mode; stale error would jerk the cursor.
+                controller.integral_error = Vec2::ZERO;
+                controller.prev_error = Vec2::ZERO;
+                velocity.0 = Vec2::ZERO;
+                commands.entity(entity).remove::<PositionTarget>();
+            }
+            CursorControl::Position => {
+                if *body == RigidBody::Kinematic {
+                    continue;
+                }
+                *body = RigidBody::Kinematic;
+                commands
+                    .entity(entity)
+                    .insert(PositionTarget(transform.translation.truncate()));
+            }
+        }
+    }
+}
+
+// A kinematic body moves at exactly its velocity, so each step it's given the velocity that lands
+// it on its target. Kinematic bodies pass through the walls, so it's the tunneling sweep that
+// stops them there.
+fn drive_positioned_cursors(
+    mut cursors: Query<(
+        &TargetVelocity,
+        &Position,
+        &mut PositionTarget,
+        &mut LinearVelocity,
+    )>,
+    time: Res<FixedTime>,
+) {
+    let period = time.period.as_secs_f32();
+    for (target_velocity, position, mut target, mut velocity) in cursors.iter_mut() {
+        let lead = target.0 + target_velocity.0 * period - position.0;
+        target.0 = position.0 + lead.clamp_length_max(MAX_TARGET_LEAD);
+        velocity.0 = (target.0 - position.0) / period;
+    }
+}
@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+use bevy_xpbd_2d::prelude::*;
+
+use super::player::CursorSlot;
+
+// Launch with e.g. `--cursor-weight=heavy` to pick a preset.
+const WEIGHT_FLAG: &str = "--cursor-weight=";
+// The size cursor meshes are built at, so they can be scaled to match the collider.
+const MESH_SIZE: f32 = 0.3;
+
+// Cursors can be made light and nimble or heavy and sluggish, by picking a `CursorWeight` preset
+// or editing the `CursorPhysicsConfig` it fills in, both from the inspector.
+pub struct CursorPhysicsPlugin;
+
+impl Plugin for CursorPhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        let weight = CursorWeight::from_args();
+        app.register_type::<CursorWeight>()
+            .register_type::<CursorPhysicsConfig>()
+            .insert_resource(weight)
+            .insert_resource(weight.config())
+            .add_systems(Update, (apply_weight_preset, apply_cursor_shape).chain())
+            // xpbd works mass out from the collider whenever the collider changes, so the
+            // configured mass is put back after it does.
+            .add_systems(
+                FixedUpdate,
+                hold_cursor_mass
+                    .after(PhysicsSet::Prepare)
+                    .before(PhysicsSet::StepSimulation),
+            );
+    }
+}
+
+#[derive(Resource, Reflect, Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[reflect(Resource)]
+pub enum CursorWeight {
+    Light,
+    #[default]
+    Normal,
+    Heavy,
+}
+
+impl CursorWeight {
+    fn from_args() -> Self {
+        let Some(value) =
+            std::env::args().find_map(|arg| arg.strip_prefix(WEIGHT_FLAG).map(str::to_owned))
+        else {
+            return Self::default();
+        };
+        match value.as_str() {
+            "light" => Self::Light,
+            "normal" => Self::Normal,
+            "heavy" => Self::Heavy,
+            _ => {
+                println!(
+                    "Unknown cursor weight '{}', expected light, normal or heavy",
+                    value
+                );
+                Self::default()
+            }
+        }
+    }
+
+    fn config(self) -> CursorPhysicsConfig {
+        match self {
+            Self::Light => CursorPhysicsConfig {
+                size: 0.25,
+                mass: 0.04,
+                linear_damping: 0.0,
+            },
+            Self::Normal => CursorPhysicsConfig::default(),
+            Self::Heavy => CursorPhysicsConfig {
+                size: 0.35,
+                mass: 0.3,
+                linear_damping: 2.0,
+            },
+        }
+    }
+}
+
+/// Physical properties shared by every cursor. Set from the `CursorWeight` preset, and editable on
+/// top of it.
+#[derive(Resource, Reflect, Debug, Clone)]
+#[reflect(Resource)]
+pub struct CursorPhysicsConfig {
+    /// Side length of the cursor's square collider, in meters. The cursor is drawn to match.
+    pub size: f32,
+    /// In kilograms. Cursor forces scale with mass, so this mostly changes how hard a cursor pulls
+    /// the rope and knocks shapes around.
+    pub mass: f32,
+    pub linear_damping: f32,
+}
+
+impl Default for CursorPhysicsConfig {
+    // What cursors were before they were configurable: a 0.3m square of density 1.
+    fn default() -> Self {
+        Self {
+            size: MESH_SIZE,
+            mass: MESH_SIZE * MESH_SIZE,
+            linear_damping: 0.0,
+        }
+    }
+}
+
+fn apply_weight_preset(weight: Res<CursorWeight>, mut config: ResMut<CursorPhysicsConfig>) {
+    // The preset is already in place at launch, and mustn't clobber edits made since.
+    if weight.is_changed() && !weight.is_added() {
+        *config = weight.config();
+    }
+}
+
+fn apply_cursor_shape(
+    mut commands: Commands,
+    config: Res<CursorPhysicsConfig>,
+    mut cursors: Query<(Entity, &mut Collider, &mut Transform), With<CursorSlot>>,
+    added: Query<(), Added<CursorSlot>>,
+) {
+    if !config.is_changed() && added.is_empty() {
+        return;
+    }
+    for (entity, mut collider, mut transform) in cursors.iter_mut() {
+        *collider = Collider::cuboid(config.size, config.size);
+        transform.scale = Vec3::new(config.size / MESH_SIZE, config.size / MESH_SIZE, 1.0);
+        commands
+            .entity(entity)
+            .insert(LinearDamping(config.linear_damping));
+    }
+}
+
+fn hold_cursor_mass(
+    config: Res<CursorPhysicsConfig>,
+    mut cursors: Query<(&mut Mass, &mut InverseMass), With<CursorSlot>>,
+) {
+    // A massless dynamic body would break the solver.
+    if config.mass <= 0.0 {
+        return;
+    }
+    for (mut mass, mut inverse_mass) in cursors.iter_mut() {
+        // Only written when it differs, so change detection stays quiet.
+        if mass.0 != config.mass {
+            mass.0 = config.mass;
+            inverse_mass.0 = 1.0 / config.mass;
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::path::Path;
+
+use bevy::{prelude::*, sprite::Mesh2dHandle, utils::HashMap};
+
+use super::{
+    palette::Palette,
+    player::{CursorSlot, MAX_PLAYERS},
+};
+
+// Skins are images in assets/cursors, e.g. assets/cursors/arrow.png is the skin "arrow". White
+// parts of the image take the player's color.
+const SKIN_DIRECTORY: &str = "cursors";
+// Launch with e.g. `--cursor-skin=arrow` to skin every cursor, or `--cursor-skin=left:arrow` for
+// just one. The flag can be given once per cursor.
+const SKIN_FLAG: &str = "--cursor-skin=";
+// Each cursor has its own shape by default, drawn from these skins in order: the left cursor is a
+// square, the right one a circle and so on.
+const DEFAULT_SKINS: [&str; MAX_PLAYERS as usize] = ["square", "circle", "triangle", "hexagon"];
+// The fallback skin: each cursor keeps the shape generated for it when it spawns, see
+// `cursor_mesh`.
+const SHAPES_SKIN: &str = "shapes";
+// Skinned cursors are drawn a little larger than their collider, since art rarely fills its image.
+const SKIN_SIZE: f32 = 0.4;
+
+// Draws the cursors with artwork, chosen per cursor from the skins found in assets/cursors. Without
+// any, for instance if the art is missing, cursors keep the shapes generated when they spawn.
+pub struct CursorSkinPlugin;
+
+impl Plugin for CursorSkinPlugin {
+    fn build(&self, app: &mut App) {
+        let skins = CursorSkins::scan(app.world.resource::<AssetServer>());
+        let choice = CursorSkinChoice::from_args(&skins);
+        app.insert_resource(skins)
+            .insert_resource(choice)
+            .add_systems(Update, apply_cursor_skins);
+    }
+}
+
+/// Every skin available, by name.
+#[derive(Resource, Debug, Default)]
+pub struct CursorSkins(Vec<String>);
+
+impl CursorSkins {
+    // Lists the directory through the asset server, so it's found relative to the asset root like
+    // the images themselves rather than to wherever the game was launched from.
+    fn scan(asset_server: &AssetServer) -> Self {
+        let mut skins = vec![SHAPES_SKIN.to_owned()];
+        if let Ok(entries) = asset_server
+            .asset_io()
+            .read_directory(Path::new(SKIN_DIRECTORY))
+        {
+            let mut found = entries
+                .filter_map(|path| match path.extension()?.to_str()? {
+                    "png" => Some(path.file_stem()?.to_str()?.to_owned()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            found.sort();
+            skins.extend(found);
+        }
+        println!("Cursor skins: {}", skins.join(", "));
+        Self(skins)
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|skin| skin == name)
+    }
+}
+
+/// The skin each cursor wears. Cursors without an entry keep the generated shapes.
+#[derive(Resource, Debug, Default)]
+pub struct CursorSkinChoice(HashMap<CursorSlot, String>);
+
+impl CursorSkinChoice {
+    fn from_args(skins: &CursorSkins) -> Self {
+        let mut choice = Self::default();
+        for (slot, name) in (0..MAX_PLAYERS).map(CursorSlot).zip(DEFAULT_SKINS) {
+            if skins.contains(name) {
+                choice.0.insert(slot, name.to_owned());
+            }
+        }
+        for value in
+            std::env::args().filter_map(|arg| arg.strip_prefix(SKIN_FLAG).map(str::to_owned))
+        {
+            let (slots, name) = match value.split_once(':') {
+                Some((label, name)) => match CursorSlot::from_label(label) {
+                    Some(slot) => (vec![slot], name.to_owned()),
+                    None => {
+                        println!("Unknown cursor '{}' in {}{}", label, SKIN_FLAG, value);
+                        continue;
+                    }
+                },
+                None => ((0..MAX_PLAYERS).map(CursorSlot).collect(), value.clone()),
+            };
+            if !skins.contains(&name) {
+                println!("No cursor skin named '{}', keeping the default", name);
+                continue;
+            }
+            for slot in slots {
+                choice.0.insert(slot, name.clone());
+            }
+        }
+        choice
+    }
+}
+
+// Skins are applied to cursors as they spawn, and again to all of them if the choice or the palette
+// changes.
+fn apply_cursor_skins(
+    mut commands: Commands,
+    choice: Res<CursorSkinChoice>,
+    palette: Res<Palette>,
+    cursors: Query<(Entity, &CursorSlot)>,
+    added: Query<(), Added<CursorSlot>>,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !choice.is_changed() && !palette.is_changed() && added.is_empty() {
+        return;
+    }
+    for (entity, slot) in cursors.iter() {
+        let Some(name) = choice.0.get(slot).filter(|name| *name != SHAPES_SKIN) else {
+            continue;
+        };
+        let texture = asset_server.load(format!("{}/{}.png", SKIN_DIRECTORY, name));
+        let mesh: Mesh2dHandle = meshes
+            .add(
+                shape::Quad {
+                    size: Vec2::splat(SKIN_SIZE),
+                    ..default()
+                }
+                .into(),
+            )
+            .into();
+        // A new material rather than editing the old one, which the cursor shares with its rope.
+        let material = materials.add(ColorMaterial {
+            color: palette.slot(*slot),
+            texture: Some(texture),
+        });
+        commands.entity(entity).insert((mesh, material));
+    }
+}
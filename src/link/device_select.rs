@@ -0,0 +1,166 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{
+    locale::Localizer,
+    palette::Palette,
+    player::{claim_cursor, AttachState, Cursor, CursorSlot},
+    text_style::TextStyler,
+    AppState, DespawnOnExitInit, TEXT_COLOR,
+};
+use crate::mischief::{
+    accumulate_motion, MischiefAssignments, MischiefDevices, MouseMotionAccumulator,
+};
+
+// Pushing a mouse this many counts left or right (net of decay) claims that side's cursor, or a
+// cursor in the middle of the rope once that side is taken.
+const CLAIM_DISTANCE: f32 = 600.0;
+// Accumulated motion decays by this fraction each second, so slow drift never claims a cursor.
+const MOTION_DECAY_PER_SECOND: f32 = 0.9;
+// Devices which moved within this long are highlighted in the list.
+const ACTIVE_SECONDS: f32 = 0.25;
+
+// Lists detected mice by name on the title screen. Moving a mouse highlights it, and pushing it
+// firmly left or right claims the matching cursor.
+pub struct DeviceSelectPlugin;
+
+impl Plugin for DeviceSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DeviceMotion>()
+            .add_systems(OnEnter(AppState::Init), spawn_device_list)
+            .add_systems(
+                Update,
+                (
+                    track_device_motion.after(accumulate_motion),
+                    claim_by_motion.run_if(in_state(AttachState::Waiting)),
+                    display_device_list,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Init)),
+            );
+    }
+}
+
+#[derive(Default)]
+struct Motion {
+    horizontal: f32,
+    seconds_since_moved: f32,
+}
+
+#[derive(Resource, Default)]
+struct DeviceMotion(HashMap<u32, Motion>);
+
+#[derive(Component)]
+struct DeviceList;
+
+fn spawn_device_list(mut commands: Commands) {
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, -1.8, 1.0).with_scale(Vec3::splat(0.003)),
+            text: Text {
+                sections: vec![],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        DeviceList,
+        DespawnOnExitInit,
+        Name::new("DeviceList"),
+    ));
+}
+
+fn track_device_motion(
+    mut motion: ResMut<DeviceMotion>,
+    accumulator: Res<MouseMotionAccumulator>,
+    time: Res<Time>,
+) {
+    let decay = (1.0 - MOTION_DECAY_PER_SECOND).powf(time.delta_seconds());
+    for device_motion in motion.0.values_mut() {
+        device_motion.horizontal *= decay;
+        device_motion.seconds_since_moved += time.delta_seconds();
+    }
+
+    for (device, frame_motion) in accumulator.iter() {
+        let device_motion = motion.0.entry(device).or_default();
+        device_motion.horizontal += frame_motion.x as f32;
+        if frame_motion != IVec2::ZERO {
+            device_motion.seconds_since_moved = 0.0;
+        }
+    }
+}
+
+fn claim_by_motion(
+    mut motion: ResMut<DeviceMotion>,
+    mut cursors: Query<(&CursorSlot, &mut Cursor)>,
+) {
+    for (&device, device_motion) in motion.0.iter_mut() {
+        let preferred = if device_motion.horizontal <= -CLAIM_DISTANCE {
+            CursorSlot::LEFT
+        } else if device_motion.horizontal >= CLAIM_DISTANCE {
+            CursorSlot::RIGHT
+        } else {
+            continue;
+        };
+        if claim_cursor(&mut cursors, Some(preferred), device).is_some() {
+            device_motion.horizontal = 0.0;
+        }
+    }
+}
+
+fn display_device_list(
+    devices: Res<MischiefDevices>,
+    assignments: Res<MischiefAssignments>,
+    motion: Res<DeviceMotion>,
+    palette: Res<Palette>,
+    localizer: Localizer,
+    text_styler: TextStyler,
+    mut lists: Query<&mut Text, With<DeviceList>>,
+) {
+    let font = text_styler.font();
+
+    let mut sections = vec![TextSection::new(
+        localizer.get("device_select.prompt"),
+        TextStyle {
+            font: font.clone(),
+            font_size: 100.0,
+            color: TEXT_COLOR,
+        },
+    )];
+    for device in devices.connected() {
+        let active = motion
+            .0
+            .get(&device.id)
+            .is_some_and(|motion| motion.seconds_since_moved < ACTIVE_SECONDS);
+        let slot = assignments.player(device.id).map(CursorSlot);
+        let (label, color) = match slot {
+            Some(CursorSlot::LEFT) => (format!("< {}", device.name), palette.left),
+            Some(CursorSlot::RIGHT) => (format!("{} >", device.name), palette.right),
+            Some(slot) => (
+                format!("{} ({})", device.name, localizer.get(slot.label_key())),
+                palette.slot(slot),
+            ),
+            None if !device.capabilities.can_point() => (
+                localizer.format("device_select.unsupported", &[&device.name]),
+                TEXT_COLOR,
+            ),
+            None => (device.name.clone(), TEXT_COLOR),
+        };
+        // Idle devices are dimmed, so players can tell which entry is theirs by moving it.
+        let color = match active {
+            true => color,
+            false => color.with_a(0.5),
+        };
+        sections.push(TextSection::new(
+            format!("\n{}", label),
+            TextStyle {
+                font: font.clone(),
+                font_size: 100.0,
+                color,
+            },
+        ));
+    }
+
+    for mut text in lists.iter_mut() {
+        text.sections = sections.clone();
+    }
+}
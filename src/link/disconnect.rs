@@ -0,0 +1,148 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+
+use super::{
+    locale::{Localizer, TextKey},
+    player::{AttachState, Cursor, CursorSlot},
+    stalls::StallState,
+    text_style::TextStyler,
+    AppState, BACKGROUND_COLOR, TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+// When a mouse is unplugged or detached mid-run, its cursor is released and the run pauses behind an
+// overlay until the cursor is claimed again. Play then resumes after the usual stall countdown.
+pub struct DisconnectPlugin;
+
+impl Plugin for DisconnectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            pause_for_disconnect
+                .run_if(in_state(AppState::Warmup).or_else(in_state(AppState::Playing)))
+                .run_if(in_state(AttachState::Waiting))
+                .run_if(not(in_state(StallState::Disconnected))),
+        )
+        .add_systems(
+            OnEnter(StallState::Disconnected),
+            (bevy_xpbd_2d::pause, spawn_reconnect_overlay),
+        )
+        .add_systems(
+            Update,
+            (resume_when_attached, display_reconnect_overlay)
+                .run_if(in_state(StallState::Disconnected)),
+        )
+        .add_systems(
+            OnExit(StallState::Disconnected),
+            (bevy_xpbd_2d::resume, cleanup_system::<ReconnectOverlay>),
+        );
+    }
+}
+
+#[derive(Component)]
+struct ReconnectOverlay;
+
+#[derive(Component)]
+struct ReconnectPrompt;
+
+fn pause_for_disconnect(mut next_state: ResMut<NextState<StallState>>) {
+    next_state.set(StallState::Disconnected);
+}
+
+fn resume_when_attached(
+    attach_state: Res<State<AttachState>>,
+    mut next_state: ResMut<NextState<StallState>>,
+) {
+    if attach_state.get() == &AttachState::Attached {
+        next_state.set(StallState::Resuming);
+    }
+}
+
+fn spawn_reconnect_overlay(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    text_styler: TextStyler,
+) {
+    let text_style = TextStyle {
+        font: text_styler.font(),
+        font_size: 100.0,
+        color: TEXT_COLOR,
+    };
+
+    commands
+        .spawn((
+            MaterialMesh2dBundle {
+                transform: Transform::from_xyz(0.0, 2.0, 3.0),
+                mesh: meshes
+                    .add(
+                        shape::Quad {
+                            size: Vec2::new(8.0, 1.6),
+                            ..default()
+                        }
+                        .into(),
+                    )
+                    .into(),
+                material: materials.add(ColorMaterial::from(BACKGROUND_COLOR)),
+                ..default()
+            },
+            ReconnectOverlay,
+            Name::new("ReconnectOverlay"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, 0.2, 0.1).with_scale(Vec3::splat(0.006)),
+                    text: Text {
+                        sections: vec![TextSection::new("", text_style.clone())],
+                        alignment: TextAlignment::Center,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    ..default()
+                },
+                ReconnectPrompt,
+                Name::new("ReconnectPrompt"),
+            ));
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, -0.4, 0.1).with_scale(Vec3::splat(0.003)),
+                    text: Text {
+                        sections: vec![TextSection::new("", text_style.clone())],
+                        alignment: TextAlignment::Center,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    ..default()
+                },
+                TextKey("disconnect.instructions"),
+                Name::new("ReconnectInstructions"),
+            ));
+        });
+}
+
+fn display_reconnect_overlay(
+    cursors: Query<(&CursorSlot, &Cursor)>,
+    mut prompts: Query<&mut Text, With<ReconnectPrompt>>,
+    localizer: Localizer,
+) {
+    let mut detached = cursors
+        .iter()
+        .filter(|(_, cursor)| cursor.0.is_none())
+        .map(|(slot, _)| *slot)
+        .collect::<Vec<_>>();
+    detached.sort_by_key(|slot| slot.0);
+    let prompt = match detached.as_slice() {
+        [] => String::new(),
+        [slot] => localizer.format("disconnect.one", &[&localizer.get(slot.label_key())]),
+        [_, _] if cursors.iter().count() == 2 => localizer.get("disconnect.both"),
+        slots => localizer.format(
+            "disconnect.several",
+            &[&slots
+                .iter()
+                .map(|slot| localizer.get(slot.label_key()))
+                .collect::<Vec<_>>()
+                .join(", ")],
+        ),
+    };
+    for mut text in prompts.iter_mut() {
+        text.sections[0].value = prompt.clone();
+    }
+}
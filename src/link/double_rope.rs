@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+use bevy_xpbd_2d::prelude::*;
+
+use super::{
+    gameplay::{BinAssignment, InBin, Settled, Shape, ShapeValue},
+    locale::Localizer,
+    palette::Palette,
+    player::PlayerCount,
+    spawn_level::{RopeLayout, Team},
+    text_style::TextStyler,
+    AppState, DespawnOnExitGameOver, TEXT_COLOR,
+};
+
+// Launch with `--double-rope` for four players on two ropes.
+const DOUBLE_ROPE_FLAG: &str = "--double-rope";
+
+// Four mice, two teams: each pair of players holds its own rope, and the two ropes share the
+// playfield. A sorted shape scores for the team whose rope touched it last, so teams can steal
+// each other's shapes, or knock them into the wrong bin.
+pub struct DoubleRopePlugin;
+
+impl Plugin for DoubleRopePlugin {
+    fn build(&self, app: &mut App) {
+        if !std::env::args().any(|arg| arg == DOUBLE_ROPE_FLAG) {
+            return;
+        }
+        app.insert_resource(RopeLayout::Double)
+            // Two cursors on each rope, whatever `--players` says.
+            .insert_resource(PlayerCount(4))
+            .init_resource::<TeamScore>()
+            .add_systems(
+                OnEnter(AppState::Playing),
+                (reset_team_score, spawn_team_score_display),
+            )
+            .add_systems(
+                Update,
+                (credit_touches, update_team_score, display_team_score)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(OnEnter(AppState::GameOver), spawn_winner);
+    }
+}
+
+/// The team whose rope last touched a shape, which gets the credit when it's sorted.
+#[derive(Component, Debug)]
+struct LastTouchedBy(Team);
+
+/// Each team's points, indexed by `Team`.
+#[derive(Resource, Debug, Default)]
+struct TeamScore([i32; 2]);
+
+#[derive(Component)]
+struct TeamScoreDisplay;
+
+fn team_color(palette: &Palette, team: usize) -> Color {
+    match team {
+        0 => palette.left,
+        _ => palette.right,
+    }
+}
+
+fn reset_team_score(mut score: ResMut<TeamScore>) {
+    *score = TeamScore::default();
+}
+
+fn spawn_team_score_display(
+    mut commands: Commands,
+    palette: Res<Palette>,
+    text_styler: TextStyler,
+) {
+    let section = |team: usize| {
+        TextSection::new(
+            "",
+            TextStyle {
+                font: text_styler.font(),
+                font_size: 100.0,
+                color: team_color(&palette, team),
+            },
+        )
+    };
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 4.2, 1.0).with_scale(Vec3::splat(0.004)),
+            text: Text {
+                sections: vec![section(0), section(1)],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        TeamScoreDisplay,
+        DespawnOnExitGameOver,
+        Name::new("TeamScoreDisplay"),
+    ));
+}
+
+// Every part of a rope is a child of the entity holding its team.
+fn credit_touches(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionStarted>,
+    shapes: Query<(), With<Shape>>,
+    parents: Query<&Parent>,
+    teams: Query<&Team>,
+) {
+    for CollisionStarted(a, b) in collisions.iter() {
+        for (shape, body) in [(*a, *b), (*b, *a)] {
+            if !shapes.contains(shape) {
+                continue;
+            }
+            let Some(team) = parents
+                .get(body)
+                .ok()
+                .and_then(|parent| teams.get(parent.get()).ok())
+            else {
+                continue;
+            };
+            commands.entity(shape).insert(LastTouchedBy(*team));
+        }
+    }
+}
+
+fn update_team_score(
+    mut score: ResMut<TeamScore>,
+    assignment: Res<BinAssignment>,
+    shapes: Query<(&Shape, &ShapeValue, &InBin, &LastTouchedBy), With<Settled>>,
+) {
+    score.0 = [0; 2];
+    for (shape, value, in_bin, touched) in shapes.iter() {
+        score.0[touched.0 .0 as usize] += assignment.points(in_bin.0, *shape, *value);
+    }
+}
+
+fn display_team_score(
+    score: Res<TeamScore>,
+    localizer: Localizer,
+    mut displays: Query<&mut Text, With<TeamScoreDisplay>>,
+) {
+    for mut text in displays.iter_mut() {
+        text.sections[0].value =
+            localizer.format("double_rope.team_score", &["1", &score.0[0].to_string()]) + "    ";
+        text.sections[1].value =
+            localizer.format("double_rope.team_score", &["2", &score.0[1].to_string()]);
+    }
+}
+
+fn spawn_winner(
+    mut commands: Commands,
+    score: Res<TeamScore>,
+    palette: Res<Palette>,
+    localizer: Localizer,
+    text_styler: TextStyler,
+) {
+    let [first, second] = score.0;
+    let (message, color) = match first.cmp(&second) {
+        std::cmp::Ordering::Greater => (
+            localizer.format("double_rope.winner", &["1"]),
+            team_color(&palette, 0),
+        ),
+        std::cmp::Ordering::Less => (
+            localizer.format("double_rope.winner", &["2"]),
+            team_color(&palette, 1),
+        ),
+        std::cmp::Ordering::Equal => (localizer.get("versus.tie"), TEXT_COLOR),
+    };
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 0.3, 1.0).with_scale(Vec3::splat(0.005)),
+            text: Text::from_section(
+                message,
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        DespawnOnExitGameOver,
+        Name::new("Winner"),
+    ));
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use bevy_xpbd_2d::prelude::*;
+
+use super::{
+    mutators::Mutators,
+    spawn_level::{RopeJoint, RopeJointBuilder},
+};
+
+// With the elastic rope mutator, the rope's rigid links are loosened into springs: given some
+// compliance, a joint's anchors are let apart and pulled back together rather than held together,
+// for a bungee-like feel. This happens in place, so it can be turned on and off between games
+// without respawning the rope.
+pub struct ElasticRopePlugin;
+
+impl Plugin for ElasticRopePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_rope_elasticity);
+    }
+}
+
+/// A rope joint the elastic rope mutator has loosened into a spring, to be stiffened again when
+/// it's turned off. Ropes built from other kinds of joint, see `RopeJointBuilder`, are left alone.
+#[derive(Component)]
+pub struct ElasticJoint;
+
+// Reapplied to every rope joint when the mutators change, and to joints as they're added.
+fn apply_rope_elasticity(
+    mut commands: Commands,
+    mutators: Res<Mutators>,
+    joint_builder: Res<RopeJointBuilder>,
+    mut joints: Query<(Entity, &mut RevoluteJoint, Option<&ElasticJoint>), With<RopeJoint>>,
+    added: Query<(), Added<RopeJoint>>,
+) {
+    if !mutators.is_changed() && added.is_empty()
+        || !matches!(*joint_builder, RopeJointBuilder::Revolute)
+    {
+        return;
+    }
+    for (entity, mut joint, elastic) in joints.iter_mut() {
+        match mutators.elastic_rope {
+            true => {
+                joint.compliance = 1.0 / mutators.rope_stiffness;
+                commands.entity(entity).insert(ElasticJoint);
+            }
+            false if elastic.is_some() => {
+                joint.compliance = 0.0;
+                commands.entity(entity).remove::<ElasticJoint>();
+            }
+            false => {}
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, utils::HashMap};
+use bevy_xpbd_2d::prelude::*;
+
+use super::{
+    palette::Palette,
+    player::{apply_cursor_force, CursorSlot},
+};
+
+// Arrows are this long per newton of force on the cursor, up to a maximum length.
+const ARROW_SCALE: f32 = 0.05;
+const MAX_ARROW_LENGTH: f32 = 1.5;
+const ARROW_HEAD_LENGTH: f32 = 0.08;
+// Forces smaller than this aren't worth an arrow.
+const MIN_FORCE: f32 = 0.1;
+
+// Press F to draw an arrow on each cursor showing the force its controller is pushing it with.
+// The arrow grows whenever the cursor lags behind the hand, which helps when tuning gains or
+// choosing a cursor weight.
+pub struct ForceDisplayPlugin;
+
+impl Plugin for ForceDisplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ForceDisplay>()
+            .add_systems(
+                Update,
+                toggle_force_display.run_if(input_just_pressed(KeyCode::F)),
+            )
+            // Forces are cleared once they're applied, so they're recorded before the physics step.
+            .add_systems(
+                FixedUpdate,
+                record_cursor_forces
+                    .after(apply_cursor_force)
+                    .before(PhysicsSet::Prepare)
+                    .run_if(display_enabled),
+            )
+            .add_systems(Update, draw_cursor_forces.run_if(display_enabled));
+    }
+}
+
+#[derive(Resource, Default)]
+struct ForceDisplay {
+    enabled: bool,
+    // The force on each cursor in the latest physics step.
+    forces: HashMap<Entity, Vec2>,
+}
+
+fn display_enabled(display: Res<ForceDisplay>) -> bool {
+    display.enabled
+}
+
+fn toggle_force_display(mut display: ResMut<ForceDisplay>) {
+    display.enabled = !display.enabled;
+    display.forces.clear();
+}
+
+fn record_cursor_forces(
+    mut display: ResMut<ForceDisplay>,
+    cursors: Query<(Entity, &ExternalForce), With<CursorSlot>>,
+) {
+    display.forces.clear();
+    for (entity, force) in cursors.iter() {
+        display.forces.insert(entity, force.force());
+    }
+}
+
+fn draw_cursor_forces(
+    mut gizmos: Gizmos,
+    display: Res<ForceDisplay>,
+    palette: Res<Palette>,
+    cursors: Query<(Entity, &Transform, &CursorSlot)>,
+) {
+    for (entity, transform, slot) in cursors.iter() {
+        let Some(force) = display
+            .forces
+            .get(&entity)
+            .filter(|force| force.length() >= MIN_FORCE)
+        else {
+            continue;
+        };
+        let start = transform.translation.truncate();
+        let end = start + (*force * ARROW_SCALE).clamp_length_max(MAX_ARROW_LENGTH);
+        let color = palette.slot(*slot);
+        gizmos.line_2d(start, end, color);
+        let back = (start - end).normalize_or_zero() * ARROW_HEAD_LENGTH;
+        for angle in [0.5, -0.5] {
+            gizmos.line_2d(end, end + Vec2::from_angle(angle).rotate(back), color);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use super::{locale::Localizer, stalls::StallState, text_style::TextStyler, AppState, TEXT_COLOR};
+use crate::util::cleanup_system;
+
+// A frenzy comes along every so often, is announced for a moment beforehand, and lasts a little
+// while.
+const FRENZY_MIN_DELAY_SECONDS: f32 = 25.0;
+const FRENZY_MAX_DELAY_SECONDS: f32 = 40.0;
+const FRENZY_WARNING_SECONDS: f32 = 2.0;
+const FRENZY_SECONDS: f32 = 8.0;
+// Points scored during a frenzy are multiplied by this much.
+const FRENZY_MULTIPLIER: i32 = 2;
+
+// Every so often during a game, a frenzy doubles all the points scored for a few seconds. The
+// scoring consults `Frenzy` for the multiplier.
+pub struct FrenzyPlugin;
+
+impl Plugin for FrenzyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Frenzy>()
+            .add_systems(OnEnter(AppState::Playing), start_frenzies)
+            .add_systems(
+                Update,
+                (run_frenzies, announce_frenzy)
+                    .chain()
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(in_state(StallState::Running)),
+            )
+            .add_systems(
+                OnExit(AppState::Playing),
+                (cleanup_system::<FrenzyAnnouncement>, end_frenzy),
+            );
+    }
+}
+
+/// Whether a frenzy is on, and so how much points are worth right now.
+#[derive(Resource, Default)]
+pub struct Frenzy {
+    remaining_seconds: f32,
+}
+
+impl Frenzy {
+    pub fn multiplier(&self) -> i32 {
+        match self.remaining_seconds > 0.0 {
+            true => FRENZY_MULTIPLIER,
+            false => 1,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct FrenzyScheduler {
+    timer: Timer,
+    warning: Option<Timer>,
+}
+
+#[derive(Component)]
+struct FrenzyAnnouncement;
+
+fn frenzy_delay() -> Duration {
+    let mut rng = rand::thread_rng();
+    Duration::from_secs_f32(rng.gen_range(FRENZY_MIN_DELAY_SECONDS..FRENZY_MAX_DELAY_SECONDS))
+}
+
+fn start_frenzies(mut commands: Commands, text_styler: TextStyler) {
+    commands.insert_resource(FrenzyScheduler {
+        timer: Timer::new(frenzy_delay(), TimerMode::Once),
+        warning: None,
+    });
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 1.2, 3.0).with_scale(Vec3::splat(0.005)),
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: TEXT_COLOR,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        FrenzyAnnouncement,
+        Name::new("FrenzyAnnouncement"),
+    ));
+}
+
+fn end_frenzy(mut commands: Commands, mut frenzy: ResMut<Frenzy>) {
+    commands.remove_resource::<FrenzyScheduler>();
+    frenzy.remaining_seconds = 0.0;
+}
+
+// Waits out the delay, then the warning, then the frenzy itself, and starts over.
+fn run_frenzies(
+    mut scheduler: ResMut<FrenzyScheduler>,
+    mut frenzy: ResMut<Frenzy>,
+    time: Res<Time>,
+) {
+    if frenzy.remaining_seconds > 0.0 {
+        frenzy.remaining_seconds = (frenzy.remaining_seconds - time.delta_seconds()).max(0.0);
+        return;
+    }
+    if let Some(warning) = scheduler.warning.as_mut() {
+        if warning.tick(time.delta()).just_finished() {
+            println!("Frenzy! Points are doubled for {}s", FRENZY_SECONDS);
+            scheduler.warning = None;
+            frenzy.remaining_seconds = FRENZY_SECONDS;
+        }
+        return;
+    }
+    if scheduler.timer.tick(time.delta()).just_finished() {
+        scheduler.warning = Some(Timer::from_seconds(FRENZY_WARNING_SECONDS, TimerMode::Once));
+        scheduler.timer.set_duration(frenzy_delay());
+        scheduler.timer.reset();
+    }
+}
+
+fn announce_frenzy(
+    scheduler: Res<FrenzyScheduler>,
+    frenzy: Res<Frenzy>,
+    localizer: Localizer,
+    mut announcements: Query<(&mut Text, &mut Transform), With<FrenzyAnnouncement>>,
+    time: Res<Time>,
+) {
+    let value = if frenzy.remaining_seconds > 0.0 {
+        localizer.format(
+            "frenzy.active",
+            &[&frenzy.remaining_seconds.ceil().to_string()],
+        )
+    } else if scheduler.warning.is_some() {
+        localizer.get("frenzy.warning")
+    } else {
+        String::new()
+    };
+    // Throbs while it's on.
+    let scale = match frenzy.remaining_seconds > 0.0 {
+        true => 0.005 * (1.0 + 0.08 * (time.elapsed_seconds() * 8.0).sin()),
+        false => 0.005,
+    };
+    for (mut text, mut transform) in announcements.iter_mut() {
+        text.sections[0].value = value.clone();
+        transform.scale = Vec3::splat(scale);
+    }
+}
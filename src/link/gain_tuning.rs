@@ -0,0 +1,318 @@
+use std::time::Instant;
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, utils::HashMap};
+use bevy_xpbd_2d::prelude::*;
+
+use super::{
+    locale::{Localizer, TextKey},
+    player::{CursorGains, PIDController, TargetVelocity},
+    text_style::TextStyler,
+    title_hints::TitleHint,
+    AppState, DespawnOnExitInit, TEXT_COLOR,
+};
+use crate::{
+    mischief::{MischiefEvent, MischiefEventData},
+    util::cleanup_system,
+};
+
+// Players wiggle their mice for this long so their report rate and the frame rate can be measured.
+const MEASURE_SECONDS: f32 = 2.0;
+// Each axis of a report is a separate event, so events this close together are one report.
+const SAME_REPORT_SECONDS: f32 = 0.0001;
+// Each trial pushes every cursor at this speed, in meters per second, then lets it come to rest.
+const STEP_SPEED: f32 = 3.0;
+const STEP_SECONDS: f32 = 0.6;
+const REST_SECONDS: f32 = 0.4;
+const TRIALS: u32 = 8;
+// A trial whose peak speed exceeds the step by more than this fraction counts as overshooting.
+const MAX_OVERSHOOT: f32 = 0.1;
+// There's no point settling faster than the input updates, and settling much slower only adds lag.
+const RISE_INPUT_INTERVALS: f32 = 3.0;
+// Beyond a proportional gain of 1 the controller overcorrects on every physics step.
+const MAX_P: f32 = 1.0;
+const MIN_P: f32 = 0.1;
+const MAX_I: f32 = 4.0;
+
+// Press P on the title screen to derive PID gains for this machine. Every cursor is pushed back and
+// forth in a series of step tests, and the gains are nudged after each one until the cursors reach
+// speed quickly without overshooting. The result is saved to `cursor_gains.txt`.
+pub struct GainTuningPlugin;
+
+impl Plugin for GainTuningPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GainTuner>()
+            .add_systems(OnEnter(AppState::Init), spawn_gain_tuning_hint)
+            .add_systems(
+                Update,
+                toggle_gain_tuning
+                    .run_if(input_just_pressed(KeyCode::P))
+                    .run_if(in_state(AppState::Init).or_else(in_state(AppState::GainTuning))),
+            )
+            .add_systems(OnEnter(AppState::GainTuning), start_gain_tuning)
+            .add_systems(
+                Update,
+                (measure_timing, display_tuner)
+                    .chain()
+                    .run_if(in_state(AppState::GainTuning)),
+            )
+            // After everything in Update, so the step overrides whatever the mice, keyboard or
+            // gamepad are doing.
+            .add_systems(
+                PostUpdate,
+                run_trials.run_if(in_state(AppState::GainTuning)),
+            )
+            .add_systems(
+                OnExit(AppState::GainTuning),
+                cleanup_system::<GainTuningScreen>,
+            );
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum TunerPhase {
+    #[default]
+    Measuring,
+    Stepping {
+        trial: u32,
+    },
+    Done,
+}
+
+#[derive(Resource, Default)]
+struct GainTuner {
+    phase: TunerPhase,
+    elapsed: f32,
+    frame_seconds: Vec<f32>,
+    // The last report from each device, and the gaps between its reports.
+    last_report: HashMap<u32, Instant>,
+    report_seconds: Vec<f32>,
+    // How long the cursors should take to reach speed, once measuring is done.
+    target_rise_seconds: f32,
+    // When each cursor first reached 90% of the step this trial, and the highest speed any reached.
+    rise_seconds: HashMap<Entity, f32>,
+    peak_speed: f32,
+}
+
+#[derive(Component)]
+struct GainTuningScreen;
+
+#[derive(Component)]
+struct GainTuningStatus;
+
+fn toggle_gain_tuning(state: Res<State<AppState>>, mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(match state.get() {
+        AppState::GainTuning => AppState::Init,
+        _ => AppState::GainTuning,
+    });
+}
+
+fn spawn_gain_tuning_hint(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: TitleHint::GainTuning.transform(),
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: TEXT_COLOR.with_a(0.6),
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        DespawnOnExitInit,
+        TextKey("title.gain_tuning_hint"),
+        Name::new("GainTuningHint"),
+    ));
+}
+
+fn start_gain_tuning(
+    mut commands: Commands,
+    mut tuner: ResMut<GainTuner>,
+    text_styler: TextStyler,
+) {
+    *tuner = GainTuner::default();
+
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 2.5, 1.0).with_scale(Vec3::splat(0.003)),
+            text: Text {
+                sections: vec![TextSection::new(
+                    "",
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: TEXT_COLOR,
+                    },
+                )],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        GainTuningScreen,
+        GainTuningStatus,
+        Name::new("GainTuningStatus"),
+    ));
+}
+
+fn measure_timing(
+    mut tuner: ResMut<GainTuner>,
+    mut mouse_events: EventReader<MischiefEvent>,
+    time: Res<Time>,
+    fixed_time: Res<FixedTime>,
+) {
+    if tuner.phase != TunerPhase::Measuring {
+        return;
+    }
+
+    tuner.frame_seconds.push(time.delta_seconds());
+    for event in mouse_events.iter() {
+        if !matches!(event.event_data, MischiefEventData::RelMotion { .. }) {
+            continue;
+        }
+        let gap = tuner
+            .last_report
+            .get(&event.device)
+            .map(|last| event.timestamp.duration_since(*last).as_secs_f32());
+        match gap {
+            Some(gap) if gap < SAME_REPORT_SECONDS => continue,
+            Some(gap) => tuner.report_seconds.push(gap),
+            None => {}
+        }
+        tuner.last_report.insert(event.device, event.timestamp);
+    }
+
+    tuner.elapsed += time.delta_seconds();
+    if tuner.elapsed < MEASURE_SECONDS {
+        return;
+    }
+
+    let frame = median(&mut tuner.frame_seconds).unwrap_or(time.delta_seconds());
+    let report = median(&mut tuner.report_seconds);
+    // Motion is applied once per frame, so a mouse reporting faster than that doesn't help, but
+    // one reporting slower leaves frames without input.
+    let input_interval = report.map_or(frame, |report| report.max(frame));
+    tuner.target_rise_seconds =
+        (RISE_INPUT_INTERVALS * input_interval).max(2.0 * fixed_time.period.as_secs_f32());
+    println!(
+        "Frame time {:.1}ms, mouse report interval {}, tuning for a {:.0}ms rise time",
+        frame * 1000.0,
+        report.map_or("unknown".to_owned(), |report| format!(
+            "{:.1}ms",
+            report * 1000.0
+        )),
+        tuner.target_rise_seconds * 1000.0
+    );
+    tuner.phase = TunerPhase::Stepping { trial: 0 };
+    tuner.elapsed = 0.0;
+}
+
+fn median(values: &mut [f32]) -> Option<f32> {
+    values.sort_by(f32::total_cmp);
+    values.get(values.len() / 2).copied()
+}
+
+fn run_trials(
+    mut tuner: ResMut<GainTuner>,
+    mut gains: ResMut<CursorGains>,
+    mut cursors: Query<(Entity, &mut TargetVelocity, &LinearVelocity), With<PIDController>>,
+    time: Res<Time>,
+) {
+    let TunerPhase::Stepping { trial } = tuner.phase else {
+        return;
+    };
+
+    // Alternate directions so the rope stays roughly where it started.
+    let direction = match trial % 2 {
+        0 => Vec2::X,
+        _ => Vec2::NEG_X,
+    };
+    let stepping = tuner.elapsed < STEP_SECONDS;
+    for (entity, mut target_velocity, velocity) in cursors.iter_mut() {
+        target_velocity.0 = match stepping {
+            true => direction * STEP_SPEED,
+            false => Vec2::ZERO,
+        };
+        if !stepping {
+            continue;
+        }
+        let speed = velocity.0.dot(direction);
+        tuner.peak_speed = tuner.peak_speed.max(speed);
+        if speed >= 0.9 * STEP_SPEED {
+            let elapsed = tuner.elapsed;
+            tuner.rise_seconds.entry(entity).or_insert(elapsed);
+        }
+    }
+
+    tuner.elapsed += time.delta_seconds();
+    if tuner.elapsed < STEP_SECONDS + REST_SECONDS {
+        return;
+    }
+
+    // The slowest cursor sets the pace, and one that never got up to speed counts as the full step.
+    let rise = match tuner.rise_seconds.len() == cursors.iter().count() {
+        true => tuner.rise_seconds.values().copied().fold(0.0, f32::max),
+        false => STEP_SECONDS,
+    };
+    let overshoot = tuner.peak_speed / STEP_SPEED - 1.0;
+    if overshoot > MAX_OVERSHOOT {
+        gains.p *= 0.8;
+        gains.i *= 0.6;
+    } else if rise > 1.2 * tuner.target_rise_seconds {
+        gains.p *= 1.25;
+        gains.i *= 1.2;
+    } else if rise < 0.8 * tuner.target_rise_seconds {
+        // Faster than the input can keep up with just makes the cursor jerky.
+        gains.p *= 0.9;
+    }
+    gains.p = gains.p.clamp(MIN_P, MAX_P);
+    gains.i = gains.i.clamp(0.0, MAX_I);
+    println!(
+        "Gain tuning trial {}: rise {:.0}ms, overshoot {:.0}%, now p={:.2} i={:.2}",
+        trial + 1,
+        rise * 1000.0,
+        overshoot.max(0.0) * 100.0,
+        gains.p,
+        gains.i
+    );
+
+    tuner.elapsed = 0.0;
+    tuner.rise_seconds.clear();
+    tuner.peak_speed = 0.0;
+    tuner.phase = match trial + 1 < TRIALS {
+        true => TunerPhase::Stepping { trial: trial + 1 },
+        false => {
+            gains.save();
+            TunerPhase::Done
+        }
+    };
+}
+
+fn display_tuner(
+    tuner: Res<GainTuner>,
+    gains: Res<CursorGains>,
+    localizer: Localizer,
+    mut statuses: Query<&mut Text, With<GainTuningStatus>>,
+) {
+    let status = match tuner.phase {
+        TunerPhase::Measuring => localizer.get("gain_tuning.measuring"),
+        TunerPhase::Stepping { trial } => localizer.format(
+            "gain_tuning.stepping",
+            &[
+                &(trial + 1).to_string(),
+                &TRIALS.to_string(),
+                &format!("{:.2}", gains.p),
+                &format!("{:.2}", gains.i),
+            ],
+        ),
+        TunerPhase::Done => localizer.format(
+            "gain_tuning.done",
+            &[&format!("{:.2}", gains.p), &format!("{:.2}", gains.i)],
+        ),
+    };
+    for mut text in statuses.iter_mut() {
+        text.sections[0].value = status.clone();
+    }
+}
@@ -0,0 +1,451 @@
+use bevy::prelude::*;
+
+use super::{
+    gameplay::LevelState,
+    locale::Localizer,
+    spawn_level::{HEIGHT, WIDTH},
+    stalls::StallState,
+    stats::ShapeSorted,
+    text_style::TextStyler,
+    AppState, TEXT_COLOR,
+};
+use crate::{
+    mischief::{MischiefEvent, MischiefEventData},
+    util::cleanup_system,
+};
+
+// Clicks are ignored for this long after the mode select screen comes up, so the click that ended
+// the last screen doesn't also skip this one.
+const MODE_SELECT_GRACE_SECONDS: f32 = 0.5;
+
+// An endless run ramps up to full intensity over this many shapes.
+const ENDLESS_RAMP_SHAPES: f32 = 150.0;
+// A timed run lasts this long, in seconds, and every streak of this many correct sorts in a row
+// adds a few seconds to the clock.
+const TIMED_RUN_SECONDS: f32 = 120.0;
+const STREAK_LENGTH: u32 = 5;
+const STREAK_BONUS_SECONDS: f32 = 5.0;
+// How long the bonus is shown next to the clock.
+const BONUS_DISPLAY_SECONDS: f32 = 1.5;
+// With the lives rule, each shape sorted into the wrong bin costs a life, and the game is over once
+// they're gone. Endless runs always play with it, since nothing else ends them.
+const STARTING_LIVES: u32 = 3;
+
+// Before each game, the players pick how it's played: the classic game, which ends once a fixed
+// number of shapes has been sorted, a timed one, where shapes keep coming until the clock runs
+// out, an endless one, where they keep coming until the lives run out, or versus, where the
+// players compete for points. Any can be played with a limited number of lives, and with a rule for
+// shapes that fall out through the drain.
+pub struct GameModePlugin;
+
+impl Plugin for GameModePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<GameMode>()
+            .register_type::<Lives>()
+            .register_type::<DrainRule>()
+            .init_resource::<GameMode>()
+            .init_resource::<Lives>()
+            .init_resource::<DrainRule>()
+            .add_systems(OnEnter(AppState::ModeSelect), spawn_mode_select)
+            .add_systems(
+                Update,
+                (pick_mode, display_modes, start_game)
+                    .chain()
+                    .run_if(in_state(AppState::ModeSelect)),
+            )
+            .add_systems(
+                OnExit(AppState::ModeSelect),
+                cleanup_system::<ModeSelectScreen>,
+            )
+            .add_systems(
+                OnEnter(AppState::Playing),
+                (
+                    start_clock.run_if(resource_equals(GameMode::Timed)),
+                    start_lives,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    (award_streak_bonus, tick_clock)
+                        .chain()
+                        .run_if(in_state(StallState::Running)),
+                    display_clock,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(resource_exists::<RunClock>()),
+            )
+            .add_systems(
+                Update,
+                ramp_endless_intensity
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(in_state(StallState::Running))
+                    .run_if(resource_equals(GameMode::Endless)),
+            )
+            .add_systems(Update, display_lives.run_if(in_state(AppState::Playing)))
+            .add_systems(
+                OnExit(AppState::Playing),
+                (
+                    cleanup_system::<ClockDisplay>,
+                    cleanup_system::<LivesDisplay>,
+                    stop_clock,
+                ),
+            );
+    }
+}
+
+#[derive(Resource, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum GameMode {
+    /// A fixed number of shapes, and the game ends once they've all been sorted.
+    #[default]
+    Classic,
+    /// Shapes keep coming, and the game ends when the clock runs out, see `TIMED_RUN_SECONDS`.
+    Timed,
+    /// Played like the classic game, but each player scores only for their own bin, see
+    /// `VersusPlugin`.
+    Versus,
+    /// Shapes keep coming, ever faster, and the game ends when the last life is lost, see
+    /// `ENDLESS_RAMP_SHAPES`.
+    Endless,
+}
+
+impl GameMode {
+    const ALL: [GameMode; 4] = [
+        GameMode::Classic,
+        GameMode::Timed,
+        GameMode::Versus,
+        GameMode::Endless,
+    ];
+
+    fn label_key(self) -> &'static str {
+        match self {
+            GameMode::Classic => "game_mode.classic",
+            GameMode::Timed => "game_mode.timed",
+            GameMode::Versus => "game_mode.versus",
+            GameMode::Endless => "game_mode.endless",
+        }
+    }
+
+    // Each mode keeps the number key it was first given, so adding a mode never moves the others
+    // or the rules' toggles.
+    fn key(self) -> (KeyCode, u8) {
+        match self {
+            GameMode::Classic => (KeyCode::Key1, 1),
+            GameMode::Timed => (KeyCode::Key2, 2),
+            GameMode::Versus => (KeyCode::Key5, 5),
+            GameMode::Endless => (KeyCode::Key6, 6),
+        }
+    }
+}
+
+/// Whether the game ends once the level's shapes have all been sorted, rather than on the clock.
+pub fn shape_limited(mode: Res<GameMode>) -> bool {
+    matches!(*mode, GameMode::Classic | GameMode::Versus)
+}
+
+/// The optional lives rule, checked as shapes are scored, see `update_score`.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+pub struct Lives {
+    /// Whether the players turned the rule on.
+    pub enabled: bool,
+    /// Whether the rule applies to the game in progress, which it always does in an endless run.
+    pub in_play: bool,
+    pub remaining: u32,
+}
+
+impl Lives {
+    /// Costs a life if the rule is on, and says whether that was the last one.
+    pub fn lose_life(&mut self) -> bool {
+        if !self.in_play {
+            return false;
+        }
+        self.remaining = self.remaining.saturating_sub(1);
+        self.remaining == 0
+    }
+}
+
+/// What happens to a shape that falls out through the drain, applied as it's despawned, see
+/// `apply_drain_rule`.
+#[derive(Resource, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum DrainRule {
+    /// Nothing; the shape is simply gone.
+    #[default]
+    Neutral,
+    /// The shape's points come off the score.
+    CostsPoints,
+    /// Another shape is spawned in its place, so none of the level's shapes are lost.
+    RefundsSpawn,
+}
+
+impl DrainRule {
+    const ALL: [DrainRule; 3] = [
+        DrainRule::Neutral,
+        DrainRule::CostsPoints,
+        DrainRule::RefundsSpawn,
+    ];
+
+    fn label_key(self) -> &'static str {
+        match self {
+            DrainRule::Neutral => "drain_rule.neutral",
+            DrainRule::CostsPoints => "drain_rule.costs_points",
+            DrainRule::RefundsSpawn => "drain_rule.refunds_spawn",
+        }
+    }
+
+    fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|rule| *rule == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+#[derive(Resource)]
+struct RunClock {
+    elapsed_seconds: f32,
+    remaining_seconds: f32,
+    bonus_display: Timer,
+}
+
+#[derive(Resource)]
+struct ModeSelectGrace(Timer);
+
+#[derive(Component)]
+struct ModeSelectScreen;
+
+#[derive(Component)]
+struct ModeList;
+
+#[derive(Component)]
+struct ClockDisplay;
+
+#[derive(Component)]
+struct LivesDisplay;
+
+fn spawn_mode_select(mut commands: Commands, text_styler: TextStyler) {
+    commands.insert_resource(ModeSelectGrace(Timer::from_seconds(
+        MODE_SELECT_GRACE_SECONDS,
+        TimerMode::Once,
+    )));
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 3.0, 1.0).with_scale(Vec3::splat(0.003)),
+            text_anchor: bevy::sprite::Anchor::TopCenter,
+            text: Text {
+                sections: vec![TextSection::new(
+                    "",
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: TEXT_COLOR,
+                    },
+                )],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        ModeSelectScreen,
+        ModeList,
+        Name::new("ModeSelectScreen"),
+    ));
+}
+
+fn pick_mode(
+    keys: Res<Input<KeyCode>>,
+    mut mode: ResMut<GameMode>,
+    mut lives: ResMut<Lives>,
+    mut drain_rule: ResMut<DrainRule>,
+) {
+    let picked = GameMode::ALL
+        .into_iter()
+        .find(|option| keys.just_pressed(option.key().0));
+    if let Some(picked) = picked {
+        mode.set_if_neq(picked);
+    }
+    if keys.just_pressed(KeyCode::Key3) {
+        lives.enabled = !lives.enabled;
+    }
+    if keys.just_pressed(KeyCode::Key4) {
+        *drain_rule = drain_rule.next();
+    }
+}
+
+fn display_modes(
+    mode: Res<GameMode>,
+    lives: Res<Lives>,
+    drain_rule: Res<DrainRule>,
+    localizer: Localizer,
+    mut lists: Query<&mut Text, With<ModeList>>,
+) {
+    let mut lines = vec![localizer.get("game_mode.title"), String::new()];
+    for option in GameMode::ALL {
+        let marker = match option == *mode {
+            true => ">",
+            false => " ",
+        };
+        lines.push(format!(
+            "{} {}: {}",
+            marker,
+            option.key().1,
+            localizer.get(option.label_key())
+        ));
+    }
+    lines.push(String::new());
+    lines.push(localizer.format(
+        "game_mode.lives",
+        &[
+            &STARTING_LIVES.to_string(),
+            &localizer.get(match (lives.enabled, *mode) {
+                (_, GameMode::Endless) => "game_mode.lives_always_on",
+                (true, _) => "settings.on",
+                (false, _) => "settings.off",
+            }),
+        ],
+    ));
+    lines.push(localizer.format(
+        "game_mode.drain_rule",
+        &[&localizer.get(drain_rule.label_key())],
+    ));
+    lines.push(String::new());
+    lines.push(localizer.get("game_mode.start"));
+    for mut text in lists.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+fn start_game(
+    mut grace: ResMut<ModeSelectGrace>,
+    mut mischief_events: EventReader<MischiefEvent>,
+    mut app_state: ResMut<NextState<AppState>>,
+    time: Res<Time>,
+) {
+    let ready = grace.0.tick(time.delta()).finished();
+    for event in mischief_events.iter() {
+        if let MischiefEventData::Button { pressed: true, .. } = event.event_data {
+            if ready {
+                app_state.set(AppState::Playing);
+            }
+        }
+    }
+}
+
+fn start_clock(mut commands: Commands, text_styler: TextStyler) {
+    let mut bonus_display = Timer::from_seconds(BONUS_DISPLAY_SECONDS, TimerMode::Once);
+    bonus_display.tick(bonus_display.duration());
+    commands.insert_resource(RunClock {
+        elapsed_seconds: 0.0,
+        remaining_seconds: TIMED_RUN_SECONDS,
+        bonus_display,
+    });
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, HEIGHT / 2.0 - 1.8, 1.0)
+                .with_scale(Vec3::splat(0.005)),
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: TEXT_COLOR,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        ClockDisplay,
+        Name::new("ClockDisplay"),
+    ));
+}
+
+fn start_lives(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    mut lives: ResMut<Lives>,
+    text_styler: TextStyler,
+) {
+    lives.remaining = STARTING_LIVES;
+    lives.in_play = lives.enabled || *mode == GameMode::Endless;
+    if !lives.in_play {
+        return;
+    }
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(-WIDTH / 2.0 + 1.0, HEIGHT / 2.0 - 1.8, 1.0)
+                .with_scale(Vec3::splat(0.005)),
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: TEXT_COLOR,
+                },
+            )
+            .with_alignment(TextAlignment::Left),
+            text_anchor: bevy::sprite::Anchor::CenterLeft,
+            ..default()
+        },
+        LivesDisplay,
+        Name::new("LivesDisplay"),
+    ));
+}
+
+fn display_lives(
+    lives: Res<Lives>,
+    localizer: Localizer,
+    mut displays: Query<&mut Text, With<LivesDisplay>>,
+) {
+    for mut text in displays.iter_mut() {
+        text.sections[0].value = localizer.format("hud.lives", &[&lives.remaining.to_string()]);
+    }
+}
+
+// Like a classic level, the intensity follows the shapes spawned so far, but it never runs out.
+fn ramp_endless_intensity(mut level_state: ResMut<LevelState>) {
+    level_state.intensity = (level_state.num_spawned() as f32 / ENDLESS_RAMP_SHAPES).min(1.0);
+}
+
+fn stop_clock(mut commands: Commands) {
+    commands.remove_resource::<RunClock>();
+}
+
+fn award_streak_bonus(mut clock: ResMut<RunClock>, mut sorted_events: EventReader<ShapeSorted>) {
+    for event in sorted_events.iter() {
+        if event.correct && event.combo % STREAK_LENGTH == 0 {
+            clock.remaining_seconds += STREAK_BONUS_SECONDS;
+            clock.bonus_display.reset();
+        }
+    }
+}
+
+// The spawn intensity ramps up over the run's first two minutes, however long the bonuses stretch
+// it.
+fn tick_clock(
+    mut clock: ResMut<RunClock>,
+    mut level_state: ResMut<LevelState>,
+    mut app_state: ResMut<NextState<AppState>>,
+    time: Res<Time>,
+) {
+    clock.elapsed_seconds += time.delta_seconds();
+    level_state.intensity = (clock.elapsed_seconds / TIMED_RUN_SECONDS).min(1.0);
+
+    clock.bonus_display.tick(time.delta());
+    clock.remaining_seconds -= time.delta_seconds();
+    if clock.remaining_seconds <= 0.0 {
+        clock.remaining_seconds = 0.0;
+        app_state.set(AppState::GameOver);
+    }
+}
+
+fn display_clock(clock: Res<RunClock>, mut displays: Query<&mut Text, With<ClockDisplay>>) {
+    let seconds = clock.remaining_seconds.ceil() as u32;
+    let mut value = format!("{}:{:02}", seconds / 60, seconds % 60);
+    if !clock.bonus_display.finished() {
+        value.push_str(&format!("  +{:.0}s", STREAK_BONUS_SECONDS));
+    }
+    for mut text in displays.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
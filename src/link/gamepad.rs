@@ -0,0 +1,131 @@
+use bevy::{input::common_conditions::input_toggle_active, prelude::*};
+
+use super::{
+    locale::TextKey,
+    player::{
+        claim_cursor, claimable_slot, move_cursors, AttachState, Cursor, CursorSlot, TargetVelocity,
+    },
+    text_style::TextStyler,
+    title_hints::TitleHint,
+    AppState, DespawnOnExitInit, TEXT_COLOR,
+};
+use crate::mischief::GAMEPAD_DEVICE;
+
+// Full stick deflection moves the cursor at this speed, in meters per second.
+const GAMEPAD_CURSOR_SPEED: f32 = 10.0;
+
+// Lets a gamepad's left stick steer the right cursor, for couch play with one mouse and one
+// controller. In games with more than two players it takes a free middle cursor instead.
+pub struct GamepadCursorPlugin;
+
+impl Plugin for GamepadCursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CursorGamepad>()
+            .add_systems(OnEnter(AppState::Init), spawn_gamepad_hint)
+            .add_systems(
+                Update,
+                (
+                    claim_gamepad_cursor.run_if(in_state(AttachState::Waiting)),
+                    show_gamepad_hint,
+                )
+                    .run_if(in_state(AppState::Init)),
+            )
+            .add_systems(
+                Update,
+                drive_gamepad_cursor
+                    .after(move_cursors)
+                    .run_if(input_toggle_active(true, KeyCode::Grave)),
+            );
+    }
+}
+
+// The gamepad driving a cursor, if one has claimed it.
+#[derive(Resource, Default)]
+struct CursorGamepad(Option<Gamepad>);
+
+#[derive(Component)]
+struct GamepadHint;
+
+fn spawn_gamepad_hint(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: TitleHint::Gamepad.transform(),
+            text: Text {
+                sections: vec![TextSection::new(
+                    "",
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: TEXT_COLOR,
+                    },
+                )],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        GamepadHint,
+        DespawnOnExitInit,
+        TextKey("title.gamepad_hint"),
+        Name::new("GamepadHint"),
+    ));
+}
+
+fn show_gamepad_hint(
+    gamepads: Res<Gamepads>,
+    cursors: Query<(&CursorSlot, &Cursor)>,
+    mut hints: Query<&mut Visibility, With<GamepadHint>>,
+) {
+    let claimed = cursors
+        .iter()
+        .any(|(_, cursor)| cursor.0 == Some(GAMEPAD_DEVICE));
+    let claimable = claimable_slot(cursors.iter(), Some(CursorSlot::RIGHT)).is_some();
+    let shown = !claimed && claimable && gamepads.iter().next().is_some();
+    for mut visibility in hints.iter_mut() {
+        *visibility = match shown {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+    }
+}
+
+fn claim_gamepad_cursor(
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    mut cursor_gamepad: ResMut<CursorGamepad>,
+    mut cursors: Query<(&CursorSlot, &mut Cursor)>,
+) {
+    let pressed = gamepads.iter().find(|gamepad| {
+        buttons.just_pressed(GamepadButton::new(*gamepad, GamepadButtonType::South))
+    });
+    let Some(gamepad) = pressed else {
+        return;
+    };
+    if claim_cursor(&mut cursors, Some(CursorSlot::RIGHT), GAMEPAD_DEVICE).is_some() {
+        cursor_gamepad.0 = Some(gamepad);
+    }
+}
+
+fn drive_gamepad_cursor(
+    cursor_gamepad: Res<CursorGamepad>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut cursors: Query<(&mut TargetVelocity, &Cursor)>,
+) {
+    let Some(gamepad) = cursor_gamepad.0 else {
+        return;
+    };
+    // A disconnected gamepad has no axis values, which leaves the cursor still.
+    let stick = Vec2::new(
+        axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+            .unwrap_or(0.0),
+        axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+            .unwrap_or(0.0),
+    );
+
+    for (mut target_velocity, cursor) in cursors.iter_mut() {
+        if cursor.0 == Some(GAMEPAD_DEVICE) {
+            target_velocity.0 = stick.clamp_length_max(1.0) * GAMEPAD_CURSOR_SPEED;
+        }
+    }
+}
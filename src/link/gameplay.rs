@@ -5,29 +5,79 @@ use bevy::{
     sprite::{MaterialMesh2dBundle, Mesh2dHandle},
 };
 use bevy_xpbd_2d::prelude::*;
-use rand::Rng;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    seq::SliceRandom,
+    Rng,
+};
 
-use super::spawn_level::{
-    Layer, LEFT_SCORE_REGION, PLAY_REGION, RIGHT_SCORE_REGION, SHAPE_ALIVE_REGION,
-    SHAPE_SPAWN_REGION,
+use super::spawn_level::{BinSensor, Layer, PLAY_REGION};
+use super::{
+    frenzy::Frenzy,
+    game_mode::{shape_limited, DrainRule, GameMode, Lives},
+    levels::{CurrentLevel, LevelDefinition},
+    linked_pairs::LinkedPair,
+    palette::Palette,
+    power_ups::{PowerUp, PowerUps},
+    stalls::StallState,
+    AppState,
 };
-use super::{AppState, LEFT_COLOR, RIGHT_COLOR};
 
-const NUM_SHAPES: u32 = 20;
+// Golden shapes turn up this often compared to ordinary medium shapes, and are worth this much.
+const GOLDEN_FREQUENCY: f32 = 0.05;
+const GOLDEN_POINTS: i32 = 3;
+const GOLDEN_EXTRA_GRAVITY: f32 = 0.5;
+const GOLDEN_COLOR: Color = Color::rgb(1.0, 0.8, 0.2);
+// Boss shapes are rarer still, several times the size of the rest, and only a taut rope can move
+// them, see `Layer::TautRope`.
+const BOSS_FREQUENCY: f32 = 0.03;
+const BOSS_POINTS: i32 = 5;
+const BOSS_SIZE: f32 = 0.9;
+// A shape only counts as scored once it's been asleep in a bin for this long, so shapes bouncing
+// in and out of a bin don't make the score flicker.
+const SETTLE_SECONDS: f32 = 0.3;
 
 pub struct GameplayPlugin;
 
 impl Plugin for GameplayPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, configure_shapes)
+        app.add_event::<ShapeDrained>()
+            .add_event::<ShapeScored>()
+            .register_type::<SpawnTuning>()
+            .init_resource::<SpawnTuning>()
+            .init_resource::<BinMargin>()
+            .init_resource::<BinAssignment>()
+            .add_systems(Startup, configure_shapes)
+            .add_systems(Update, paint_shape_configs)
+            .add_systems(
+                Update,
+                (fit_bin_sensors, track_bin_contents, settle_shapes).chain(),
+            )
+            .add_systems(FixedUpdate, apply_extra_gravity.before(PhysicsSet::Prepare))
             .add_systems(OnEnter(AppState::Playing), start_level)
+            .add_systems(OnEnter(AppState::Tuning), start_endless_level)
             .add_systems(
                 Update,
                 (
-                    increase_intensity,
-                    (spawn_shapes, despawn_shapes),
+                    tune_intensity,
+                    (spawn_shapes, despawn_shapes, despawn_sorted_shapes),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Tuning)),
+            )
+            .add_systems(
+                Update,
+                (
+                    increase_intensity
+                        .run_if(in_state(StallState::Running))
+                        .run_if(shape_limited),
+                    (
+                        spawn_shapes.run_if(in_state(StallState::Running)),
+                        despawn_shapes,
+                    ),
+                    apply_drain_rule,
                     apply_deferred,
-                    detect_game_over,
+                    detect_game_over.run_if(shape_limited),
                 )
                     .chain()
                     .run_if(in_state(AppState::Playing)),
@@ -42,7 +92,22 @@ impl Plugin for GameplayPlugin {
     }
 }
 
-fn start_level(mut commands: Commands, shapes: Query<Entity, With<Shape>>) {
+// Timed and endless games have no shape limit; the clock or the lives end them, see
+// `GameModePlugin`.
+fn start_level(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    current_level: CurrentLevel,
+    mut tuning: ResMut<SpawnTuning>,
+    shapes: Query<Entity, With<Shape>>,
+) {
+    let level = current_level.get();
+    let num_shapes = match *mode {
+        GameMode::Classic | GameMode::Versus => level.num_shapes,
+        GameMode::Timed | GameMode::Endless => u32::MAX,
+    };
+    tuning.spawn_region_min = level.shape_spawn_region.min;
+    tuning.spawn_region_max = level.shape_spawn_region.max;
     commands.insert_resource(Score::default());
     commands.insert_resource(LevelState {
         spawn_state: ShapeSpawnState {
@@ -51,7 +116,25 @@ fn start_level(mut commands: Commands, shapes: Query<Entity, With<Shape>>) {
             num_shapes: 0,
             strategy: None,
         },
-        num_shapes_remaining: NUM_SHAPES,
+        num_shapes,
+        num_shapes_remaining: num_shapes,
+        intensity: 0.0,
+    });
+    for entity in shapes.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// In tuning mode shapes spawn forever, at whatever intensity the tuning panel says.
+fn start_endless_level(mut commands: Commands, shapes: Query<Entity, With<Shape>>) {
+    commands.insert_resource(LevelState {
+        spawn_state: ShapeSpawnState {
+            timer: Timer::from_seconds(1.0, TimerMode::Once),
+            num_shapes: 0,
+            strategy: None,
+        },
+        num_shapes: u32::MAX,
+        num_shapes_remaining: u32::MAX,
         intensity: 0.0,
     });
     for entity in shapes.iter() {
@@ -59,30 +142,42 @@ fn start_level(mut commands: Commands, shapes: Query<Entity, With<Shape>>) {
     }
 }
 
+fn tune_intensity(mut level_state: ResMut<LevelState>, tuning: Res<SpawnTuning>) {
+    level_state.intensity = tuning.tuning_intensity.clamp(0.0, 1.0);
+}
+
+// Sorted shapes aren't scored in tuning mode, so clear them out before the bins fill up.
+fn despawn_sorted_shapes(
+    mut commands: Commands,
+    shapes: Query<Entity, (With<Shape>, With<InBin>)>,
+) {
+    for entity in shapes.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
 fn increase_intensity(mut level_state: ResMut<LevelState>) {
-    level_state.intensity =
-        (NUM_SHAPES - level_state.num_shapes_remaining) as f32 / NUM_SHAPES as f32;
+    level_state.intensity = (level_state.num_shapes - level_state.num_shapes_remaining) as f32
+        / level_state.num_shapes as f32;
 }
 
 fn detect_game_over(
     mut app_state: ResMut<NextState<AppState>>,
     level_state: Res<LevelState>,
-    shapes: Query<&Transform, With<Shape>>,
+    shapes: Query<Option<&Settled>, With<Shape>>,
 ) {
     if level_state.num_shapes_remaining == 0 {
-        if shapes.iter().all(|transform| {
-            let location = transform.translation.truncate();
-            LEFT_SCORE_REGION.contains(location) || RIGHT_SCORE_REGION.contains(location)
-        }) {
+        if shapes.iter().all(|settled| settled.is_some()) {
             app_state.set(AppState::GameOver);
         }
     }
 }
 
 #[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
-enum Shape {
+pub enum Shape {
     Square,
     Circle,
+    Triangle,
 }
 
 impl std::fmt::Display for Shape {
@@ -90,6 +185,7 @@ impl std::fmt::Display for Shape {
         match self {
             Shape::Square => write!(f, "Square"),
             Shape::Circle => write!(f, "Circle"),
+            Shape::Triangle => write!(f, "Triangle"),
         }
     }
 }
@@ -98,50 +194,365 @@ impl std::fmt::Display for Shape {
 struct ShapeConfig {
     mesh: Mesh2dHandle,
     material: Handle<ColorMaterial>,
+    // The shade of its kind's color it's drawn in, see `shade`. Golden shapes keep their own color.
+    lightness_scale: Option<f32>,
     collider: Collider,
     shape: Shape,
+    weight: WeightClass,
+    // Scored for landing in the right bin, and lost for the wrong one.
+    points: i32,
+    // How often this config is picked, relative to the others in its weight class.
+    frequency: f32,
+    // Extra pull downwards, as a multiple of gravity.
+    extra_gravity: f32,
+    boss: bool,
+}
+
+/// How many points a shape is worth, see `BinAssignment::points`.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ShapeValue(pub i32);
+
+// Pulls a shape down harder than gravity alone, by this multiple of gravity.
+#[derive(Component)]
+struct ExtraGravity(f32);
+
+/// Marks a boss shape, which the rope passes straight through unless it's pulled taut.
+#[derive(Component)]
+pub struct Boss;
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeightClass {
+    Light,
+    Medium,
+    Heavy,
+}
+
+impl WeightClass {
+    const ALL: [WeightClass; 3] = [WeightClass::Light, WeightClass::Medium, WeightClass::Heavy];
+
+    fn size_scale(&self) -> f32 {
+        match self {
+            WeightClass::Light => 0.9,
+            WeightClass::Medium => 1.0,
+            WeightClass::Heavy => 1.15,
+        }
+    }
+
+    fn density(&self) -> f32 {
+        match self {
+            WeightClass::Light => 0.5,
+            WeightClass::Medium => 1.0,
+            WeightClass::Heavy => 2.5,
+        }
+    }
+
+    // Lighter shapes are drawn in a lighter shade of their color, heavier ones darker.
+    fn lightness_scale(&self) -> f32 {
+        match self {
+            WeightClass::Light => 1.25,
+            WeightClass::Medium => 1.0,
+            WeightClass::Heavy => 0.7,
+        }
+    }
+
+    // Heavy shapes become more common as intensity rises, at the expense of light ones.
+    fn spawn_weight(&self, intensity: f32) -> f32 {
+        match self {
+            WeightClass::Light => 1.0 - 0.8 * intensity,
+            WeightClass::Medium => 1.0,
+            WeightClass::Heavy => 0.1 + 0.9 * intensity,
+        }
+    }
+}
+
+impl std::fmt::Display for WeightClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeightClass::Light => write!(f, "Light"),
+            WeightClass::Medium => write!(f, "Medium"),
+            WeightClass::Heavy => write!(f, "Heavy"),
+        }
+    }
+}
+
+fn shade(color: Color, lightness_scale: f32) -> Color {
+    let [hue, saturation, lightness, alpha] = color.as_hsla_f32();
+    Color::hsla(
+        hue,
+        saturation,
+        (lightness * lightness_scale).clamp(0.0, 1.0),
+        alpha,
+    )
 }
 
 fn configure_shapes(
     mut commands: Commands,
+    palette: Res<Palette>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
     let default_size = 0.25;
-    commands.spawn((
-        ShapeConfig {
-            mesh: meshes
-                .add(
-                    shape::Quad {
-                        size: Vec2::splat(default_size),
-                        ..default()
-                    }
+    for weight in WeightClass::ALL {
+        let size = default_size * weight.size_scale();
+        commands.spawn((
+            ShapeConfig {
+                mesh: meshes
+                    .add(
+                        shape::Quad {
+                            size: Vec2::splat(size),
+                            ..default()
+                        }
+                        .into(),
+                    )
                     .into(),
-                )
-                .into(),
-            material: materials.add(ColorMaterial::from(LEFT_COLOR)),
-            collider: Collider::cuboid(default_size, default_size),
-            shape: Shape::Square,
-        },
-        Name::new("SquareConfig"),
-    ));
-    commands.spawn((
-        ShapeConfig {
-            mesh: meshes
-                .add(
-                    shape::Circle {
-                        radius: default_size / 2.0,
-                        ..default()
-                    }
+                material: materials.add(ColorMaterial::from(shade(
+                    palette.shape(Shape::Square),
+                    weight.lightness_scale(),
+                ))),
+                lightness_scale: Some(weight.lightness_scale()),
+                collider: Collider::cuboid(size, size),
+                shape: Shape::Square,
+                weight,
+                points: 1,
+                frequency: 1.0,
+                extra_gravity: 0.0,
+                boss: false,
+            },
+            Name::new(format!("{}SquareConfig", weight)),
+        ));
+        commands.spawn((
+            ShapeConfig {
+                mesh: meshes
+                    .add(
+                        shape::Circle {
+                            radius: size / 2.0,
+                            ..default()
+                        }
+                        .into(),
+                    )
                     .into(),
-                )
-                .into(),
-            material: materials.add(ColorMaterial::from(RIGHT_COLOR)),
-            collider: Collider::ball(default_size / 2.0),
-            shape: Shape::Circle,
-        },
-        Name::new("CircleConfig"),
-    ));
+                material: materials.add(ColorMaterial::from(shade(
+                    palette.shape(Shape::Circle),
+                    weight.lightness_scale(),
+                ))),
+                lightness_scale: Some(weight.lightness_scale()),
+                collider: Collider::ball(size / 2.0),
+                shape: Shape::Circle,
+                weight,
+                points: 1,
+                frequency: 1.0,
+                extra_gravity: 0.0,
+                boss: false,
+            },
+            Name::new(format!("{}CircleConfig", weight)),
+        ));
+        // Point up, matching the mesh's vertices.
+        let radius = size * 0.6;
+        let corner = Vec2::new(radius * 30f32.to_radians().cos(), -radius / 2.0);
+        commands.spawn((
+            ShapeConfig {
+                mesh: meshes
+                    .add(shape::RegularPolygon::new(radius, 3).into())
+                    .into(),
+                material: materials.add(ColorMaterial::from(shade(
+                    palette.shape(Shape::Triangle),
+                    weight.lightness_scale(),
+                ))),
+                lightness_scale: Some(weight.lightness_scale()),
+                collider: Collider::triangle(
+                    Vec2::new(0.0, radius),
+                    Vec2::new(-corner.x, corner.y),
+                    corner,
+                ),
+                shape: Shape::Triangle,
+                weight,
+                points: 1,
+                frequency: 1.0,
+                extra_gravity: 0.0,
+                boss: false,
+            },
+            Name::new(format!("{}TriangleConfig", weight)),
+        ));
+    }
+
+    // Rare golden squares and circles, worth more but falling faster, so they're harder to catch.
+    let golden = materials.add(ColorMaterial::from(GOLDEN_COLOR));
+    let golden_shapes: [(Shape, Mesh, Collider); 2] = [
+        (
+            Shape::Square,
+            shape::Quad {
+                size: Vec2::splat(default_size),
+                ..default()
+            }
+            .into(),
+            Collider::cuboid(default_size, default_size),
+        ),
+        (
+            Shape::Circle,
+            shape::Circle {
+                radius: default_size / 2.0,
+                ..default()
+            }
+            .into(),
+            Collider::ball(default_size / 2.0),
+        ),
+    ];
+    for (shape, mesh, collider) in golden_shapes {
+        commands.spawn((
+            ShapeConfig {
+                mesh: meshes.add(mesh).into(),
+                material: golden.clone(),
+                lightness_scale: None,
+                collider,
+                shape,
+                weight: WeightClass::Medium,
+                points: GOLDEN_POINTS,
+                frequency: GOLDEN_FREQUENCY,
+                extra_gravity: GOLDEN_EXTRA_GRAVITY,
+                boss: false,
+            },
+            Name::new(format!("Golden{}Config", shape)),
+        ));
+    }
+
+    let boss_shapes: [(Shape, Mesh, Collider); 2] = [
+        (
+            Shape::Square,
+            shape::Quad {
+                size: Vec2::splat(BOSS_SIZE),
+                ..default()
+            }
+            .into(),
+            Collider::cuboid(BOSS_SIZE, BOSS_SIZE),
+        ),
+        (
+            Shape::Circle,
+            shape::Circle {
+                radius: BOSS_SIZE / 2.0,
+                ..default()
+            }
+            .into(),
+            Collider::ball(BOSS_SIZE / 2.0),
+        ),
+    ];
+    for (shape, mesh, collider) in boss_shapes {
+        commands.spawn((
+            ShapeConfig {
+                mesh: meshes.add(mesh).into(),
+                material: materials.add(ColorMaterial::from(shade(palette.shape(shape), 0.55))),
+                lightness_scale: Some(0.55),
+                collider,
+                shape,
+                weight: WeightClass::Heavy,
+                points: BOSS_POINTS,
+                frequency: BOSS_FREQUENCY,
+                extra_gravity: 0.0,
+                boss: true,
+            },
+            Name::new(format!("Boss{}Config", shape)),
+        ));
+    }
+}
+
+// Every shape of a kind shares its config's material, so switching palettes only needs to repaint
+// the configs.
+fn paint_shape_configs(
+    palette: Res<Palette>,
+    shape_configs: Query<&ShapeConfig>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !palette.is_changed() {
+        return;
+    }
+    for config in shape_configs.iter() {
+        let Some(lightness_scale) = config.lightness_scale else {
+            continue;
+        };
+        if let Some(material) = materials.get_mut(&config.material) {
+            material.color = shade(palette.shape(config.shape), lightness_scale);
+        }
+    }
+}
+
+// Picks a random shape config, favoring weight classes according to intensity.
+fn pick_shape_config<'a>(
+    shape_configs: &'a Query<&ShapeConfig>,
+    intensity: f32,
+) -> &'a ShapeConfig {
+    let shape_configs = shape_configs.iter().collect::<Vec<_>>();
+    let weights = shape_configs
+        .iter()
+        .map(|config| config.weight.spawn_weight(intensity) * config.frequency);
+    let index = WeightedIndex::new(weights).unwrap();
+    shape_configs[index.sample(&mut rand::thread_rng())]
+}
+
+/// Spawn pacing parameters. Delay ranges are in seconds, as (min, max) pairs, and are interpolated
+/// between their zero and max intensity values as the level heats up.
+#[derive(Resource, Reflect, Debug, Clone)]
+#[reflect(Resource)]
+pub struct SpawnTuning {
+    /// Reset to the level's spawn region at the start of each game.
+    pub spawn_region_min: Vec2,
+    pub spawn_region_max: Vec2,
+    /// Delay before each group of shapes, and between shapes in a sequence.
+    pub sequence_delay_zero_intensity: Vec2,
+    pub sequence_delay_max_intensity: Vec2,
+    /// Delay after a shotgun blast.
+    pub shotgun_delay_zero_intensity: Vec2,
+    pub shotgun_delay_max_intensity: Vec2,
+    /// Intensity used in tuning mode, in place of the usual ramp.
+    pub tuning_intensity: f32,
+}
+
+impl Default for SpawnTuning {
+    fn default() -> Self {
+        let spawn_region = LevelDefinition::default().shape_spawn_region;
+        Self {
+            spawn_region_min: spawn_region.min,
+            spawn_region_max: spawn_region.max,
+            sequence_delay_zero_intensity: Vec2::new(2.0, 3.0),
+            sequence_delay_max_intensity: Vec2::new(0.75, 1.25),
+            shotgun_delay_zero_intensity: Vec2::new(3.0, 4.0),
+            shotgun_delay_max_intensity: Vec2::new(1.25, 1.75),
+            tuning_intensity: 0.5,
+        }
+    }
+}
+
+impl SpawnTuning {
+    fn sequence_delay(&self, intensity: f32) -> f32 {
+        sample_delay(
+            self.sequence_delay_zero_intensity,
+            self.sequence_delay_max_intensity,
+            intensity,
+        )
+    }
+
+    fn shotgun_delay(&self, intensity: f32) -> f32 {
+        sample_delay(
+            self.shotgun_delay_zero_intensity,
+            self.shotgun_delay_max_intensity,
+            intensity,
+        )
+    }
+}
+
+// Samples a range which may have been scrubbed empty or backwards in tuning mode.
+fn sample_range(range: std::ops::Range<f32>) -> f32 {
+    match range.end > range.start {
+        true => rand::thread_rng().gen_range(range),
+        false => range.start,
+    }
+}
+
+fn sample_delay(zero_intensity: Vec2, max_intensity: Vec2, intensity: f32) -> f32 {
+    sample_range(interpolate_ranges(
+        zero_intensity.x..zero_intensity.y,
+        max_intensity.x..max_intensity.y,
+        intensity,
+    ))
+    .max(0.0)
 }
 
 struct ShapeSpawnState {
@@ -157,6 +568,7 @@ impl ShapeSpawnState {
         shape_configs: Query<&ShapeConfig>,
         time: Res<Time>,
         intensity: f32,
+        tuning: &SpawnTuning,
     ) -> u32 {
         if !self.timer.tick(time.delta()).just_finished() {
             return 0;
@@ -170,7 +582,7 @@ impl ShapeSpawnState {
 
         let (num_shapes, duration) = match strategy {
             Some(mut s) => {
-                let result = s.on_timer_finish(self, commands, shape_configs, intensity);
+                let result = s.on_timer_finish(self, commands, shape_configs, intensity, tuning);
                 self.strategy = Some(s);
                 result
             }
@@ -198,6 +610,7 @@ trait ShapeSpawnStrategy: Send + Sync {
         commands: &mut Commands,
         shape_configs: Query<&ShapeConfig>,
         intensity: f32,
+        tuning: &SpawnTuning,
     ) -> (u32, Option<Duration>);
 }
 
@@ -213,20 +626,11 @@ fn interpolate_ranges(
 struct RandomSequence;
 
 impl RandomSequence {
-    fn new(num_shapes_remaining: u32, intensity: f32) -> ShapeSpawnState {
-        let zero_intensity_timer_range = 2.0..3.0;
-        let max_intensity_timer_range = 0.75..1.25;
+    fn new(num_shapes_remaining: u32, intensity: f32, tuning: &SpawnTuning) -> ShapeSpawnState {
         let mut rng = rand::thread_rng();
         ShapeSpawnState {
             num_shapes: u32::min(rng.gen_range(1..=3), num_shapes_remaining),
-            timer: Timer::from_seconds(
-                rng.gen_range(interpolate_ranges(
-                    zero_intensity_timer_range,
-                    max_intensity_timer_range,
-                    intensity,
-                )),
-                TimerMode::Once,
-            ),
+            timer: Timer::from_seconds(tuning.sequence_delay(intensity), TimerMode::Once),
             strategy: Some(Box::new(RandomSequence)),
         }
     }
@@ -240,25 +644,17 @@ impl ShapeSpawnStrategy for RandomSequence {
         commands: &mut Commands,
         shape_configs: Query<&ShapeConfig>,
         intensity: f32,
+        tuning: &SpawnTuning,
     ) -> (u32, Option<Duration>) {
-        let mut rng = rand::thread_rng();
-        // Pick a random shape config
-        let shape_configs = shape_configs.iter().collect::<Vec<_>>();
-        let shape_config = &shape_configs[rng.gen_range(0..shape_configs.len())];
+        let shape_config = pick_shape_config(&shape_configs, intensity);
 
-        spawn_shape(commands, shape_config);
+        spawn_shape(commands, shape_config, tuning);
 
-        let zero_intensity_timer_range = 2.0..3.0;
-        let max_intensity_timer_range = 0.75..1.25;
         (
             1,
             match state.num_shapes {
                 0 => None,
-                _ => Some(Duration::from_secs_f32(rng.gen_range(interpolate_ranges(
-                    zero_intensity_timer_range,
-                    max_intensity_timer_range,
-                    intensity,
-                )))),
+                _ => Some(Duration::from_secs_f32(tuning.sequence_delay(intensity))),
             },
         )
     }
@@ -267,22 +663,12 @@ impl ShapeSpawnStrategy for RandomSequence {
 struct Shotgun;
 
 impl Shotgun {
-    fn new(num_shapes_remaining: u32, intensity: f32) -> ShapeSpawnState {
+    fn new(num_shapes_remaining: u32, intensity: f32, tuning: &SpawnTuning) -> ShapeSpawnState {
         let mut rng = rand::thread_rng();
 
-        let zero_intensity_timer_range = 2.0..3.0;
-        let max_intensity_timer_range = 0.75..1.25;
-
         ShapeSpawnState {
             num_shapes: u32::min(rng.gen_range(2..=3), num_shapes_remaining),
-            timer: Timer::from_seconds(
-                rng.gen_range(interpolate_ranges(
-                    zero_intensity_timer_range,
-                    max_intensity_timer_range,
-                    intensity,
-                )),
-                TimerMode::Once,
-            ),
+            timer: Timer::from_seconds(tuning.sequence_delay(intensity), TimerMode::Once),
             strategy: Some(Box::new(Shotgun)),
         }
     }
@@ -296,104 +682,693 @@ impl ShapeSpawnStrategy for Shotgun {
         commands: &mut Commands,
         shape_configs: Query<&ShapeConfig>,
         intensity: f32,
+        tuning: &SpawnTuning,
     ) -> (u32, Option<Duration>) {
-        let mut rng = rand::thread_rng();
-        // Pick a random shape config
-        let shape_configs = shape_configs.iter().collect::<Vec<_>>();
-        let shape_config = &shape_configs[rng.gen_range(0..shape_configs.len())];
-
-        let zero_intensity_timer_range = 3.0..4.0;
-        let max_intensity_timer_range = 1.25..1.75;
+        let shape_config = pick_shape_config(&shape_configs, intensity);
 
         for _ in 0..state.num_shapes {
-            spawn_shape(commands, shape_config);
+            spawn_shape(commands, shape_config, tuning);
         }
         (
             state.num_shapes,
-            Some(Duration::from_secs_f32(rng.gen_range(interpolate_ranges(
-                zero_intensity_timer_range,
-                max_intensity_timer_range,
-                intensity,
-            )))),
+            Some(Duration::from_secs_f32(tuning.shotgun_delay(intensity))),
+        )
+    }
+}
+
+// Spaces shapes evenly across the whole inlet, dropping them one at a time in a random order.
+struct Rain {
+    columns: Vec<f32>,
+}
+
+impl Rain {
+    // Drops come this much faster than shapes in a random sequence.
+    const DELAY_SCALE: f32 = 0.4;
+
+    fn new(num_shapes_remaining: u32, intensity: f32, tuning: &SpawnTuning) -> ShapeSpawnState {
+        let mut rng = rand::thread_rng();
+        let num_shapes = u32::min(rng.gen_range(5..=8), num_shapes_remaining);
+        let spacing = (tuning.spawn_region_max.x - tuning.spawn_region_min.x) / num_shapes as f32;
+        let mut columns = (0..num_shapes)
+            .map(|column| tuning.spawn_region_min.x + spacing * (column as f32 + 0.5))
+            .collect::<Vec<_>>();
+        columns.shuffle(&mut rng);
+        ShapeSpawnState {
+            num_shapes,
+            timer: Timer::from_seconds(tuning.sequence_delay(intensity), TimerMode::Once),
+            strategy: Some(Box::new(Rain { columns })),
+        }
+    }
+}
+
+impl ShapeSpawnStrategy for Rain {
+    fn on_timer_finish(
+        &mut self,
+        _state: &ShapeSpawnState,
+        commands: &mut Commands,
+        shape_configs: Query<&ShapeConfig>,
+        intensity: f32,
+        tuning: &SpawnTuning,
+    ) -> (u32, Option<Duration>) {
+        let shape_config = pick_shape_config(&shape_configs, intensity);
+        let x = self.columns.pop().unwrap_or(tuning.spawn_region_min.x);
+        spawn_shape_at(
+            commands,
+            shape_config,
+            Vec2::new(x, tuning.spawn_region_min.y),
+        );
+        (
+            1,
+            Some(Duration::from_secs_f32(
+                tuning.sequence_delay(intensity) * Self::DELAY_SCALE,
+            )),
+        )
+    }
+}
+
+// Drops shapes near one side of the inlet and then the other, strictly alternating.
+struct AlternatingColumns {
+    left: bool,
+}
+
+impl AlternatingColumns {
+    // The columns are this far in from the edges of the spawn region, as a fraction of its width.
+    const INSET: f32 = 0.2;
+    const DELAY_SCALE: f32 = 0.6;
+
+    fn new(num_shapes_remaining: u32, intensity: f32, tuning: &SpawnTuning) -> ShapeSpawnState {
+        let mut rng = rand::thread_rng();
+        ShapeSpawnState {
+            num_shapes: u32::min(rng.gen_range(4..=6), num_shapes_remaining),
+            timer: Timer::from_seconds(tuning.sequence_delay(intensity), TimerMode::Once),
+            strategy: Some(Box::new(AlternatingColumns {
+                left: rng.gen_bool(0.5),
+            })),
+        }
+    }
+}
+
+impl ShapeSpawnStrategy for AlternatingColumns {
+    fn on_timer_finish(
+        &mut self,
+        _state: &ShapeSpawnState,
+        commands: &mut Commands,
+        shape_configs: Query<&ShapeConfig>,
+        intensity: f32,
+        tuning: &SpawnTuning,
+    ) -> (u32, Option<Duration>) {
+        let shape_config = pick_shape_config(&shape_configs, intensity);
+        let inset = (tuning.spawn_region_max.x - tuning.spawn_region_min.x) * Self::INSET;
+        let x = match self.left {
+            true => tuning.spawn_region_min.x + inset,
+            false => tuning.spawn_region_max.x - inset,
+        };
+        self.left = !self.left;
+        spawn_shape_at(
+            commands,
+            shape_config,
+            Vec2::new(x, tuning.spawn_region_min.y),
+        );
+        (
+            1,
+            Some(Duration::from_secs_f32(
+                tuning.sequence_delay(intensity) * Self::DELAY_SCALE,
+            )),
+        )
+    }
+}
+
+// Sweeps around the spawn region in a widening spiral, one shape at a time.
+struct Spiral {
+    step: u32,
+    num_steps: u32,
+    clockwise: bool,
+}
+
+impl Spiral {
+    // Each shape is this far around the spiral from the last, in radians.
+    const STEP_ANGLE: f32 = std::f32::consts::TAU / 5.0;
+    const DELAY_SCALE: f32 = 0.3;
+
+    fn new(num_shapes_remaining: u32, intensity: f32, tuning: &SpawnTuning) -> ShapeSpawnState {
+        let mut rng = rand::thread_rng();
+        let num_shapes = u32::min(rng.gen_range(6..=9), num_shapes_remaining);
+        ShapeSpawnState {
+            num_shapes,
+            timer: Timer::from_seconds(tuning.sequence_delay(intensity), TimerMode::Once),
+            strategy: Some(Box::new(Spiral {
+                step: 0,
+                num_steps: num_shapes,
+                clockwise: rng.gen_bool(0.5),
+            })),
+        }
+    }
+}
+
+impl ShapeSpawnStrategy for Spiral {
+    fn on_timer_finish(
+        &mut self,
+        _state: &ShapeSpawnState,
+        commands: &mut Commands,
+        shape_configs: Query<&ShapeConfig>,
+        intensity: f32,
+        tuning: &SpawnTuning,
+    ) -> (u32, Option<Duration>) {
+        let shape_config = pick_shape_config(&shape_configs, intensity);
+        let region = Rect::from_corners(tuning.spawn_region_min, tuning.spawn_region_max);
+        let angle = match self.clockwise {
+            true => -(self.step as f32) * Self::STEP_ANGLE,
+            false => self.step as f32 * Self::STEP_ANGLE,
+        };
+        let radius = (self.step + 1) as f32 / self.num_steps.max(1) as f32;
+        let offset = Vec2::new(angle.cos(), angle.sin()) * radius * region.half_size();
+        self.step += 1;
+        spawn_shape_at(commands, shape_config, region.center() + offset);
+        (
+            1,
+            Some(Duration::from_secs_f32(
+                tuning.sequence_delay(intensity) * Self::DELAY_SCALE,
+            )),
         )
     }
 }
 
-fn spawn_shape(commands: &mut Commands, shape: &ShapeConfig) {
-    let mut rng = rand::thread_rng();
-    let x = rng.gen_range(SHAPE_SPAWN_REGION.min.x..SHAPE_SPAWN_REGION.max.x);
-    let y = rng.gen_range(SHAPE_SPAWN_REGION.min.y..SHAPE_SPAWN_REGION.max.y);
-    commands.spawn((
+// Drops two ordinary shapes of different kinds side by side in the middle of the inlet, chained
+// together, see `LinkedPairPlugin`.
+struct LinkedPairs;
+
+impl LinkedPairs {
+    // Between the middles of the two shapes.
+    const SPACING: f32 = 0.9;
+
+    fn new(num_shapes_remaining: u32, intensity: f32, tuning: &SpawnTuning) -> ShapeSpawnState {
+        ShapeSpawnState {
+            num_shapes: u32::min(2, num_shapes_remaining),
+            timer: Timer::from_seconds(tuning.sequence_delay(intensity), TimerMode::Once),
+            strategy: Some(Box::new(LinkedPairs)),
+        }
+    }
+}
+
+impl ShapeSpawnStrategy for LinkedPairs {
+    fn on_timer_finish(
+        &mut self,
+        state: &ShapeSpawnState,
+        commands: &mut Commands,
+        shape_configs: Query<&ShapeConfig>,
+        intensity: f32,
+        tuning: &SpawnTuning,
+    ) -> (u32, Option<Duration>) {
+        let delay = Some(Duration::from_secs_f32(tuning.sequence_delay(intensity)));
+        // With only one shape left to spawn there's nothing to pair it with.
+        if state.num_shapes < 2 {
+            spawn_shape(
+                commands,
+                pick_shape_config(&shape_configs, intensity),
+                tuning,
+            );
+            return (1, delay);
+        }
+
+        let mut rng = rand::thread_rng();
+        let ordinary = shape_configs
+            .iter()
+            .filter(|config| !config.boss && config.points == 1)
+            .collect::<Vec<_>>();
+        let Ok(first) =
+            ordinary.choose_weighted(&mut rng, |config| config.weight.spawn_weight(intensity))
+        else {
+            return (0, None);
+        };
+        let Some(second) = ordinary
+            .iter()
+            .filter(|config| config.weight == first.weight && config.shape != first.shape)
+            .collect::<Vec<_>>()
+            .choose(&mut rng)
+            .copied()
+        else {
+            return (0, None);
+        };
+
+        let middle = Vec2::new(
+            (tuning.spawn_region_min.x + tuning.spawn_region_max.x) / 2.0,
+            tuning.spawn_region_min.y,
+        );
+        let offset = Vec2::new(Self::SPACING / 2.0, 0.0);
+        let shapes = [
+            spawn_shape_at(commands, first, middle - offset),
+            spawn_shape_at(commands, second, middle + offset),
+        ];
+        commands.spawn((
+            LinkedPair::new(shapes),
+            SpatialBundle::default(),
+            Name::new(format!("Linked {} and {}", first.shape, second.shape)),
+        ));
+        (2, delay)
+    }
+}
+
+fn spawn_shape(commands: &mut Commands, shape: &ShapeConfig, tuning: &SpawnTuning) {
+    let x = sample_range(tuning.spawn_region_min.x..tuning.spawn_region_max.x);
+    let y = sample_range(tuning.spawn_region_min.y..tuning.spawn_region_max.y);
+    spawn_shape_at(commands, shape, Vec2::new(x, y));
+}
+
+fn spawn_shape_at(commands: &mut Commands, shape: &ShapeConfig, position: Vec2) -> Entity {
+    // Only a taut rope touches a boss.
+    let (rope_layer, name) = match shape.boss {
+        true => (Layer::TautRope, format!("Boss {}", shape.shape)),
+        false => (Layer::Rope, format!("{} {}", shape.weight, shape.shape)),
+    };
+    let mut entity = commands.spawn((
         MaterialMesh2dBundle {
-            transform: Transform::from_xyz(x, y, 0.0),
+            transform: Transform::from_translation(position.extend(0.0)),
             mesh: shape.mesh.clone(),
             material: shape.material.clone(),
             ..default()
         },
         RigidBody::Dynamic,
+        // The collider is the only source of mass, so its density sets how heavy the shape is.
+        ColliderMassProperties::new_computed(&shape.collider, shape.weight.density()),
         shape.collider.clone(),
         shape.shape.clone(),
-        CollisionLayers::new([Layer::Shapes], [Layer::Rope, Layer::Level, Layer::Shapes]),
-        Name::new(shape.shape.to_string()),
+        shape.weight,
+        ShapeValue(shape.points),
+        // Reapplied every step, see `apply_extra_gravity`.
+        ExternalForce::default().with_persistence(false),
+        CollisionLayers::new(
+            [Layer::Shapes],
+            [rope_layer, Layer::Level, Layer::Shapes, Layer::Hazards],
+        ),
+        Name::new(name),
     ));
+    if shape.extra_gravity > 0.0 {
+        entity.insert(ExtraGravity(shape.extra_gravity));
+    }
+    if shape.boss {
+        entity.insert(Boss);
+    }
+    entity.id()
+}
+
+// Left off once a shape falls asleep, or the fresh force would wake it straight back up.
+fn apply_extra_gravity(
+    gravity: Res<Gravity>,
+    mut shapes: Query<(&Mass, &ExtraGravity, &mut ExternalForce), Without<Sleeping>>,
+) {
+    for (mass, extra_gravity, mut force) in shapes.iter_mut() {
+        force.apply_force(gravity.0 * mass.0 * extra_gravity.0);
+    }
 }
 
 fn spawn_shapes(
     mut commands: Commands,
     shape_configs: Query<&ShapeConfig>,
     mut level_state: ResMut<LevelState>,
+    tuning: Res<SpawnTuning>,
     time: Res<Time>,
 ) {
     if level_state.num_shapes_remaining == 0 {
         return;
     }
+    let _span = info_span!("spawn_shapes").entered();
     let intensity = level_state.intensity;
-    let num_shapes = level_state
-        .spawn_state
-        .tick(&mut commands, shape_configs, time, intensity);
+    let num_shapes =
+        level_state
+            .spawn_state
+            .tick(&mut commands, shape_configs, time, intensity, &tuning);
     level_state.num_shapes_remaining -= num_shapes;
 
     if level_state.spawn_state.is_done() {
-        let mut rng = rand::thread_rng();
-
-        level_state.spawn_state = match rng.gen_bool((1.0 - intensity) as f64) {
-            true => RandomSequence::new(level_state.num_shapes_remaining, level_state.intensity),
-            false => Shotgun::new(level_state.num_shapes_remaining, level_state.intensity),
-        };
+        level_state.spawn_state =
+            pick_spawn_pattern(level_state.num_shapes_remaining, intensity, &tuning);
     }
 }
 
-fn despawn_shapes(mut commands: Commands, mut shapes: Query<(Entity, &Transform), With<Shape>>) {
-    for (entity, transform) in shapes.iter_mut() {
+type SpawnPatternConstructor = fn(u32, f32, &SpawnTuning) -> ShapeSpawnState;
+
+// Random sequences give way to the busier patterns as the intensity rises. Alternating columns turn
+// up now and then at any intensity; linked pairs are rare early on and grow more common.
+fn pick_spawn_pattern(
+    num_shapes_remaining: u32,
+    intensity: f32,
+    tuning: &SpawnTuning,
+) -> ShapeSpawnState {
+    let patterns: [(f32, SpawnPatternConstructor); 6] = [
+        (1.0 - intensity, RandomSequence::new),
+        (intensity, Shotgun::new),
+        (0.5 * intensity, Rain::new),
+        (0.25, AlternatingColumns::new),
+        (0.5 * intensity * intensity, Spiral::new),
+        (0.3 * intensity, LinkedPairs::new),
+    ];
+    let index = WeightedIndex::new(patterns.iter().map(|(weight, _)| weight.max(0.0))).unwrap();
+    let (_, new) = patterns[index.sample(&mut rand::thread_rng())];
+    new(num_shapes_remaining, intensity, tuning)
+}
+
+/// Sent when a shape falls out of the play area without being sorted.
+#[derive(Event, Debug)]
+pub struct ShapeDrained {
+    pub shape: Shape,
+    pub value: ShapeValue,
+    pub position: Vec2,
+}
+
+fn despawn_shapes(
+    mut commands: Commands,
+    mut shapes: Query<(Entity, &Transform, &Shape, &ShapeValue)>,
+    mut drained_events: EventWriter<ShapeDrained>,
+    tuning: Res<SpawnTuning>,
+) {
+    // Shapes above the play area are still on their way in.
+    let alive_region = Rect::from_corners(
+        Vec2::new(tuning.spawn_region_min.x, PLAY_REGION.max.y),
+        tuning.spawn_region_max,
+    );
+    for (entity, transform, shape, value) in shapes.iter_mut() {
         if !PLAY_REGION.contains(transform.translation.truncate())
-            && !SHAPE_ALIVE_REGION.contains(transform.translation.truncate())
+            && !alive_region.contains(transform.translation.truncate())
         {
             commands.entity(entity).despawn_recursive();
+            drained_events.send(ShapeDrained {
+                shape: *shape,
+                value: *value,
+                position: transform.translation.truncate(),
+            });
         }
     }
 }
 
+// Drained shapes are only penalized or replaced during a game, not while tuning.
+fn apply_drain_rule(
+    drain_rule: Res<DrainRule>,
+    mut drained_events: EventReader<ShapeDrained>,
+    mut score: ResMut<Score>,
+    mut level_state: ResMut<LevelState>,
+) {
+    for event in drained_events.iter() {
+        match *drain_rule {
+            DrainRule::Neutral => {}
+            DrainRule::CostsPoints => score.drained += event.value.0,
+            DrainRule::RefundsSpawn => {
+                level_state.num_shapes_remaining =
+                    level_state.num_shapes_remaining.saturating_add(1);
+            }
+        }
+    }
+}
+
+/// Points for the shapes in each bin, recounted every frame, and bonus points banked on top.
 #[derive(Resource, Default)]
-struct Score {
+pub struct Score {
     left: i32,
     right: i32,
+    center: i32,
+    /// Awarded for how the shapes were sorted rather than where they are, see `ComboPlugin`.
+    pub bonus: i32,
+    /// Lost to shapes falling out through the drain, under `DrainRule::CostsPoints`.
+    drained: i32,
+    /// Lost to the rope snapping and staying snapped, see `RopeTensionPlugin`.
+    pub snapped: i32,
+}
+
+impl Score {
+    pub fn total(&self) -> i32 {
+        self.left + self.right + self.center + self.bonus - self.drained - self.snapped
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bin {
+    Left,
+    Right,
+    Center,
+}
+
+impl Bin {
+    pub const ALL: [Bin; 3] = [Bin::Left, Bin::Right, Bin::Center];
+
+    // The bin's place in `Bin::ALL`.
+    fn index(self) -> usize {
+        match self {
+            Bin::Left => 0,
+            Bin::Right => 1,
+            Bin::Center => 2,
+        }
+    }
+
+    /// Where a shape has to come to rest to count as in this bin on the given level, see
+    /// `BinSensor`.
+    pub fn region(self, level: &LevelDefinition) -> Rect {
+        match self {
+            Bin::Left => level.left_score_region(),
+            Bin::Right => level.right_score_region(),
+            Bin::Center => level.center_score_region(),
+        }
+    }
+}
+
+/// Which shape each bin accepts. Shuffled now and then late in a game, see `BinSwapPlugin`.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BinAssignment {
+    // In the order of `Bin::ALL`.
+    shapes: [Shape; 3],
+    // The assignment before the latest swap, still honored for a moment after it.
+    grace: Option<[Shape; 3]>,
+}
+
+// Squares go left, circles right, and triangles in the middle.
+impl Default for BinAssignment {
+    fn default() -> Self {
+        Self {
+            shapes: [Shape::Square, Shape::Circle, Shape::Triangle],
+            grace: None,
+        }
+    }
+}
+
+impl BinAssignment {
+    pub fn shape(&self, bin: Bin) -> Shape {
+        self.shapes[bin.index()]
+    }
+
+    pub fn bin(&self, shape: Shape) -> Option<Bin> {
+        Bin::ALL.into_iter().find(|bin| self.shape(*bin) == shape)
+    }
+
+    fn accepts(&self, bin: Bin, shape: Shape) -> bool {
+        self.shape(bin) == shape
+            || self
+                .grace
+                .is_some_and(|shapes| shapes[bin.index()] == shape)
+    }
+
+    /// The points a shape is worth in this bin: its value in its own bin, minus that in the wrong
+    /// one.
+    pub fn points(&self, bin: Bin, shape: Shape, value: ShapeValue) -> i32 {
+        match self.accepts(bin, shape) {
+            true => value.0,
+            false => -value.0,
+        }
+    }
+
+    /// Trades two bins' shapes. Shapes landing where they belonged before still count as correct
+    /// until `end_grace`.
+    pub fn swap(&mut self, a: Bin, b: Bin) {
+        self.grace = Some(self.shapes);
+        self.shapes.swap(a.index(), b.index());
+    }
+
+    pub fn end_grace(&mut self) {
+        self.grace = None;
+    }
+
+    pub fn is_swapped(&self) -> bool {
+        self.shapes != Self::default().shapes
+    }
+}
+
+/// Extra room around each bin, in meters, within which shapes still count as in it. Opened up for a
+/// while by a power-up, see `PowerUpPlugin`.
+#[derive(Resource, Default, Debug, PartialEq)]
+pub struct BinMargin(pub f32);
+
+/// Marks a body inside one of the bins' sensors, see `track_bin_contents`.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct InBin(pub Bin);
+
+/// Sent once for each shape the first time it lands in a bin, with the points it's worth there.
+#[derive(Event, Debug)]
+pub struct ShapeScored {
+    pub entity: Entity,
+    pub bin: Bin,
+    pub points: i32,
+    pub position: Vec2,
+}
+
+// Sizes each bin's sensor to the bin, plus whatever margin is open around it. Sensors are rebuilt
+// along with the rest of the level, and picked up here when they're added.
+fn fit_bin_sensors(
+    current_level: CurrentLevel,
+    margin: Res<BinMargin>,
+    mut sensors: Query<(Ref<BinSensor>, &mut Collider)>,
+) {
+    for (sensor, mut collider) in sensors.iter_mut() {
+        if !margin.is_changed() && !sensor.is_added() {
+            continue;
+        }
+        let region = sensor.0.region(current_level.get()).inset(margin.0);
+        *collider = Collider::cuboid(region.width(), region.height());
+    }
+}
+
+// Keeps track of which bin each body is in as it passes in and out of the bins' sensors. A body
+// asleep in a bin stays in it, since the sensors are static and sleeping bodies don't collide with
+// static ones.
+fn track_bin_contents(
+    mut commands: Commands,
+    mut started: EventReader<CollisionStarted>,
+    mut ended: EventReader<CollisionEnded>,
+    sensors: Query<&BinSensor>,
+    in_bins: Query<&InBin>,
+) {
+    for CollisionStarted(a, b) in started.iter() {
+        for (sensor, body) in [(*a, *b), (*b, *a)] {
+            if let Ok(BinSensor(bin)) = sensors.get(sensor) {
+                commands.entity(body).insert(InBin(*bin));
+            }
+        }
+    }
+    for CollisionEnded(a, b) in ended.iter() {
+        for (sensor, body) in [(*a, *b), (*b, *a)] {
+            let Ok(BinSensor(bin)) = sensors.get(sensor) else {
+                continue;
+            };
+            // Unless it's moved straight on into another bin.
+            if in_bins.get(body).map_or(true, |in_bin| in_bin.0 == *bin) {
+                commands.entity(body).remove::<InBin>();
+            }
+        }
+    }
+}
+
+/// Marks a shape which has come to rest in a bin, and is scored there until it leaves, see
+/// `settle_shapes`.
+#[derive(Component)]
+pub struct Settled;
+
+// How long a shape in a bin has been asleep.
+#[derive(Component, Default)]
+struct Resting(f32);
+
+// A shape settles once it's been asleep in a bin for a moment. Once settled it stays settled until
+// it leaves the bin, even if something landing on it wakes it up.
+fn settle_shapes(
+    mut commands: Commands,
+    mut shapes: Query<
+        (
+            Entity,
+            Option<&InBin>,
+            Option<&Sleeping>,
+            Option<&mut Resting>,
+            Option<&Settled>,
+        ),
+        With<Shape>,
+    >,
+    time: Res<Time>,
+) {
+    for (entity, in_bin, sleeping, resting, settled) in shapes.iter_mut() {
+        if in_bin.is_none() {
+            if resting.is_some() || settled.is_some() {
+                commands.entity(entity).remove::<(Resting, Settled)>();
+            }
+            continue;
+        }
+        if settled.is_some() {
+            continue;
+        }
+        match (sleeping, resting) {
+            (Some(_), Some(mut resting)) => {
+                resting.0 += time.delta_seconds();
+                if resting.0 >= SETTLE_SECONDS {
+                    commands.entity(entity).insert(Settled);
+                }
+            }
+            (Some(_), None) => {
+                commands.entity(entity).insert(Resting::default());
+            }
+            (None, Some(mut resting)) => resting.0 = 0.0,
+            (None, None) => {}
+        }
+    }
 }
 
-fn update_score(mut score: ResMut<Score>, shapes: Query<(&Transform, &Shape)>) {
+// Marks shapes which have landed in a bin at least once, with the bins' assignment at the time.
+#[derive(Component)]
+pub(super) struct Landed(BinAssignment);
+
+pub(super) fn update_score(
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    mut lives: ResMut<Lives>,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut scored_events: EventWriter<ShapeScored>,
+    frenzy: Res<Frenzy>,
+    power_ups: Res<PowerUps>,
+    assignment: Res<BinAssignment>,
+    mut shapes: Query<
+        (
+            Entity,
+            &Transform,
+            &Shape,
+            &mut ShapeValue,
+            &InBin,
+            Option<&Landed>,
+            Option<&Boss>,
+        ),
+        With<Settled>,
+    >,
+) {
+    let _span = info_span!("update_score").entered();
     score.left = 0;
     score.right = 0;
-    for (transform, shape) in shapes.iter() {
-        if LEFT_SCORE_REGION.contains(transform.translation.truncate()) {
-            match shape {
-                Shape::Square => score.left += 1,
-                Shape::Circle => score.left -= 1,
-            }
-        } else if RIGHT_SCORE_REGION.contains(transform.translation.truncate()) {
-            match shape {
-                Shape::Square => score.right -= 1,
-                Shape::Circle => score.right += 1,
+    score.center = 0;
+    for (entity, transform, shape, mut value, &InBin(bin), landed, boss) in shapes.iter_mut() {
+        // A shape that lands during a frenzy is worth more for as long as it's kept.
+        if landed.is_none() {
+            value.0 *= frenzy.multiplier();
+            commands.entity(entity).insert(Landed(*assignment));
+            // With the lives rule, the first landing is the one that counts. While the score is
+            // frozen, a shape that lands in the wrong bin costs nothing at all.
+            let wrong_bin = assignment.points(bin, *shape, *value) < 0;
+            if wrong_bin && power_ups.is_active(PowerUp::ScoreFreeze) {
+                value.0 = 0;
+            } else if wrong_bin && lives.lose_life() {
+                app_state.set(AppState::GameOver);
             }
         }
+        // Shapes are judged by the bins as they were when they landed, so a swap doesn't turn the
+        // shapes already sorted into mistakes.
+        let judged_by = landed.map_or(&*assignment, |landed| &landed.0);
+        let points = match judged_by.points(bin, *shape, *value) {
+            // A boss is worth a lot in its own bin, but only costs a single point in the wrong one.
+            points if boss.is_some() && points < 0 => -1,
+            points => points,
+        };
+        if landed.is_none() {
+            scored_events.send(ShapeScored {
+                entity,
+                bin,
+                points,
+                position: transform.translation.truncate(),
+            });
+        }
+        match bin {
+            Bin::Left => score.left += points,
+            Bin::Right => score.right += points,
+            Bin::Center => score.center += points,
+        }
     }
 }
 
@@ -401,6 +1376,7 @@ fn update_score(mut score: ResMut<Score>, shapes: Query<(&Transform, &Shape)>) {
 pub enum ScoreDisplay {
     Left,
     Right,
+    Center,
     Sum,
 }
 
@@ -409,14 +1385,32 @@ fn display_score(score: Res<Score>, mut displays: Query<(&mut Text, &ScoreDispla
         text.sections[0].value = match display {
             ScoreDisplay::Left => format!("{}", score.left),
             ScoreDisplay::Right => format!("{}", score.right),
-            ScoreDisplay::Sum => format!("{}", score.left + score.right),
+            ScoreDisplay::Center => format!("{}", score.center),
+            ScoreDisplay::Sum => format!("{}", score.total()),
         };
     }
 }
 
 #[derive(Resource)]
-struct LevelState {
+pub struct LevelState {
+    num_shapes: u32,
     num_shapes_remaining: u32,
     spawn_state: ShapeSpawnState,
-    intensity: f32,
+    pub intensity: f32,
+}
+
+impl LevelState {
+    /// Shapes the level spawns in all, or `u32::MAX` if it never runs out.
+    pub fn num_shapes(&self) -> u32 {
+        self.num_shapes
+    }
+
+    /// Shapes still to be spawned, counting any refunded by the drain rule.
+    pub fn num_shapes_remaining(&self) -> u32 {
+        self.num_shapes_remaining
+    }
+
+    pub fn num_spawned(&self) -> u32 {
+        self.num_shapes - self.num_shapes_remaining
+    }
 }
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use bevy_xpbd_2d::prelude::*;
+
+use super::{
+    cursor_physics::CursorPhysicsConfig,
+    gameplay::Shape,
+    player::{Cursor, CursorSlot},
+    spawn_level::Layer,
+};
+use crate::mischief::{MischiefEvent, MischiefEventData};
+
+// Shapes within this distance of a cursor's edge, in meters, count as touching it.
+const GRAB_REACH: f32 = 0.05;
+// A full grip lasts this long, and takes this long to recover once it's run out.
+const GRIP_SECONDS: f32 = 1.5;
+const RECOVER_SECONDS: f32 = 3.0;
+// The stamina bar under each cursor, at full stamina.
+const BAR_SIZE: Vec2 = Vec2::new(0.3, 0.04);
+const BAR_OFFSET: f32 = -0.25;
+
+// Holding the left button grips a shape the cursor is touching, so it can be carried directly.
+// Grips tire quickly and take a while to recover, so the rope is still the main way to move
+// shapes around.
+pub struct GrabPlugin;
+
+impl Plugin for GrabPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (add_grips, apply_deferred, grip_shapes, display_stamina).chain(),
+        );
+    }
+}
+
+#[derive(Component, Debug)]
+struct Grip {
+    held: bool,
+    // The joint holding the gripped shape, if any.
+    joint: Option<(Entity, Entity)>,
+    stamina: f32,
+    // Set when stamina runs out, until it's fully recovered.
+    exhausted: bool,
+}
+
+impl Default for Grip {
+    fn default() -> Self {
+        Self {
+            held: false,
+            joint: None,
+            stamina: 1.0,
+            exhausted: false,
+        }
+    }
+}
+
+#[derive(Component)]
+struct StaminaBar;
+
+fn add_grips(mut commands: Commands, cursors: Query<Entity, Added<CursorSlot>>) {
+    for cursor in cursors.iter() {
+        let bar = commands
+            .spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::WHITE.with_a(0.0),
+                        custom_size: Some(BAR_SIZE),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, BAR_OFFSET, 0.1),
+                    ..default()
+                },
+                StaminaBar,
+                Name::new("Stamina bar"),
+            ))
+            .id();
+        commands
+            .entity(cursor)
+            .insert(Grip::default())
+            .add_child(bar);
+    }
+}
+
+fn grip_shapes(
+    mut commands: Commands,
+    mut mouse_events: EventReader<MischiefEvent>,
+    mut cursors: Query<(Entity, &Cursor, &Transform, &mut Grip)>,
+    shapes: Query<&Transform, With<Shape>>,
+    spatial_query: SpatialQuery,
+    config: Res<CursorPhysicsConfig>,
+    time: Res<Time>,
+) {
+    for event in mouse_events.iter() {
+        let MischiefEventData::Button { button: 0, pressed } = event.event_data else {
+            continue;
+        };
+        for (_, cursor, _, mut grip) in cursors.iter_mut() {
+            if cursor.0 == Some(event.device) {
+                grip.held = pressed;
+            }
+        }
+    }
+
+    let probe = Collider::cuboid(
+        config.size + GRAB_REACH * 2.0,
+        config.size + GRAB_REACH * 2.0,
+    );
+    let filter = SpatialQueryFilter::new().with_masks([Layer::Shapes]);
+    for (entity, cursor, transform, mut grip) in cursors.iter_mut() {
+        // Let go when the button is, when the grip tires, or when the shape is gone.
+        if let Some((joint, shape)) = grip.joint {
+            if !grip.held || grip.exhausted || cursor.0.is_none() || !shapes.contains(shape) {
+                commands.entity(joint).despawn_recursive();
+                grip.joint = None;
+            }
+        }
+
+        grip.stamina = match grip.joint {
+            Some(_) => grip.stamina - time.delta_seconds() / GRIP_SECONDS,
+            None => grip.stamina + time.delta_seconds() / RECOVER_SECONDS,
+        }
+        .clamp(0.0, 1.0);
+        if grip.stamina == 0.0 {
+            grip.exhausted = true;
+        } else if grip.stamina == 1.0 {
+            grip.exhausted = false;
+        }
+
+        if grip.joint.is_some() || !grip.held || grip.exhausted {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let Some((shape, shape_transform)) = spatial_query
+            .shape_intersections(&probe, position, 0.0, filter.clone())
+            .into_iter()
+            .filter_map(|shape| Some((shape, shapes.get(shape).ok()?)))
+            .min_by(|(_, a), (_, b)| {
+                let a = a.translation.truncate().distance_squared(position);
+                let b = b.translation.truncate().distance_squared(position);
+                a.total_cmp(&b)
+            })
+        else {
+            continue;
+        };
+        // Pinned by its center, where it's touching the cursor now, so it can still swing.
+        let joint = commands
+            .spawn((
+                RevoluteJoint::new(entity, shape)
+                    .with_local_anchor_1(shape_transform.translation.truncate() - position)
+                    .with_local_anchor_2(Vec2::ZERO),
+                Name::new("Grip joint"),
+            ))
+            .id();
+        // Goes when the cursor does.
+        commands.entity(entity).add_child(joint);
+        grip.joint = Some((joint, shape));
+    }
+}
+
+// Hidden while stamina is full, and red while it's recovering from running out.
+fn display_stamina(
+    cursors: Query<(&Grip, &Children)>,
+    mut bars: Query<(&mut Sprite, &mut Transform), With<StaminaBar>>,
+) {
+    for (grip, children) in cursors.iter() {
+        let mut bars = bars.iter_many_mut(children);
+        while let Some((mut sprite, mut transform)) = bars.fetch_next() {
+            let color = match grip.exhausted {
+                true => Color::RED,
+                false => Color::WHITE,
+            };
+            let alpha = match grip.stamina < 1.0 {
+                true => 0.8,
+                false => 0.0,
+            };
+            sprite.color = color.with_a(alpha);
+            transform.scale.x = grip.stamina;
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_xpbd_2d::prelude::*;
+use rand::Rng;
+
+use super::{
+    gameplay::LevelState, locale::Localizer, stalls::StallState, text_style::TextStyler, AppState,
+    TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+// Gravity phases only start once the level is this intense, and come more often as it rises.
+const PHASE_MIN_INTENSITY: f32 = 0.4;
+const PHASE_WARNING_SECONDS: f32 = 2.0;
+const PHASE_SECONDS: f32 = 6.0;
+// Gravity is scaled by this much during each kind of phase.
+const LOW_GRAVITY_SCALE: f32 = 0.35;
+const REVERSED_GRAVITY_SCALE: f32 = -0.4;
+// Reversed gravity only turns up near the top of the intensity range, as a late-game twist.
+const REVERSED_MIN_INTENSITY: f32 = 0.7;
+
+// Late in a game, gravity is turned down or even upside down for a few seconds at a time, with a
+// banner announcing each phase just before it starts.
+pub struct GravityPhasePlugin;
+
+impl Plugin for GravityPhasePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Playing), start_gravity_phases)
+            .add_systems(
+                Update,
+                (run_gravity_phases, announce_gravity_phase)
+                    .chain()
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(in_state(StallState::Running)),
+            )
+            .add_systems(
+                OnExit(AppState::Playing),
+                (cleanup_system::<GravityBanner>, end_gravity_phases),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GravityPhaseKind {
+    Low,
+    Reversed,
+}
+
+impl GravityPhaseKind {
+    fn scale(self) -> f32 {
+        match self {
+            GravityPhaseKind::Low => LOW_GRAVITY_SCALE,
+            GravityPhaseKind::Reversed => REVERSED_GRAVITY_SCALE,
+        }
+    }
+
+    // The key of its name, see `Localizer`.
+    fn label_key(self) -> &'static str {
+        match self {
+            GravityPhaseKind::Low => "gravity.low",
+            GravityPhaseKind::Reversed => "gravity.reversed",
+        }
+    }
+}
+
+#[derive(Resource)]
+struct GravityPhases {
+    // Restored when each phase ends.
+    normal_gravity: Vec2,
+    timer: Timer,
+    warning: Option<(GravityPhaseKind, Timer)>,
+    active: Option<(GravityPhaseKind, Timer)>,
+}
+
+#[derive(Component)]
+struct GravityBanner;
+
+fn phase_delay(intensity: f32) -> Duration {
+    let mut rng = rand::thread_rng();
+    let t = ((intensity - PHASE_MIN_INTENSITY) / (1.0 - PHASE_MIN_INTENSITY)).clamp(0.0, 1.0);
+    let min = 20.0 * (1.0 - t) + 10.0 * t;
+    Duration::from_secs_f32(rng.gen_range(min..min + 8.0))
+}
+
+fn pick_phase_kind(intensity: f32) -> GravityPhaseKind {
+    let t = ((intensity - REVERSED_MIN_INTENSITY) / (1.0 - REVERSED_MIN_INTENSITY)).clamp(0.0, 1.0);
+    match rand::thread_rng().gen_bool((0.5 * t) as f64) {
+        true => GravityPhaseKind::Reversed,
+        false => GravityPhaseKind::Low,
+    }
+}
+
+fn start_gravity_phases(mut commands: Commands, gravity: Res<Gravity>, text_styler: TextStyler) {
+    commands.insert_resource(GravityPhases {
+        normal_gravity: gravity.0,
+        timer: Timer::new(phase_delay(0.0), TimerMode::Once),
+        warning: None,
+        active: None,
+    });
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 0.6, 3.0).with_scale(Vec3::splat(0.005)),
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: TEXT_COLOR,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        GravityBanner,
+        Name::new("GravityBanner"),
+    ));
+}
+
+fn end_gravity_phases(
+    mut commands: Commands,
+    phases: Option<Res<GravityPhases>>,
+    mut gravity: ResMut<Gravity>,
+) {
+    if let Some(phases) = phases {
+        gravity.0 = phases.normal_gravity;
+    }
+    commands.remove_resource::<GravityPhases>();
+}
+
+// Waits out the delay, then the warning, then the phase itself, and starts over.
+fn run_gravity_phases(
+    mut phases: ResMut<GravityPhases>,
+    mut gravity: ResMut<Gravity>,
+    level_state: Res<LevelState>,
+    time: Res<Time>,
+) {
+    let normal_gravity = phases.normal_gravity;
+    if let Some((kind, timer)) = phases.active.as_mut() {
+        if timer.tick(time.delta()).just_finished() {
+            println!("{:?} gravity is over", kind);
+            gravity.0 = normal_gravity;
+            phases.active = None;
+        }
+        return;
+    }
+    if let Some((kind, warning)) = phases.warning.as_mut() {
+        if warning.tick(time.delta()).just_finished() {
+            let kind = *kind;
+            println!("{:?} gravity for {}s", kind, PHASE_SECONDS);
+            gravity.0 = normal_gravity * kind.scale();
+            phases.warning = None;
+            phases.active = Some((kind, Timer::from_seconds(PHASE_SECONDS, TimerMode::Once)));
+        }
+        return;
+    }
+    if level_state.intensity < PHASE_MIN_INTENSITY {
+        return;
+    }
+    if phases.timer.tick(time.delta()).just_finished() {
+        phases.warning = Some((
+            pick_phase_kind(level_state.intensity),
+            Timer::from_seconds(PHASE_WARNING_SECONDS, TimerMode::Once),
+        ));
+        phases
+            .timer
+            .set_duration(phase_delay(level_state.intensity));
+        phases.timer.reset();
+    }
+}
+
+fn announce_gravity_phase(
+    phases: Res<GravityPhases>,
+    localizer: Localizer,
+    mut banners: Query<&mut Text, With<GravityBanner>>,
+) {
+    let value = match (&phases.warning, &phases.active) {
+        (_, Some((kind, timer))) => localizer.format(
+            "gravity.active",
+            &[
+                &localizer.get(kind.label_key()),
+                &timer.remaining_secs().ceil().to_string(),
+            ],
+        ),
+        (Some((kind, _)), None) => {
+            localizer.format("gravity.warning", &[&localizer.get(kind.label_key())])
+        }
+        (None, None) => String::new(),
+    };
+    for mut text in banners.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
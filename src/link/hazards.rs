@@ -0,0 +1,295 @@
+use std::time::Duration;
+
+use bevy::{
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+use bevy_xpbd_2d::prelude::*;
+use rand::Rng;
+
+use super::{
+    gameplay::LevelState,
+    spawn_level::{Layer, WIDTH},
+    stalls::StallState,
+    AppState, BAD_COLOR,
+};
+use crate::util::{cleanup_system, path::Path};
+
+// Hazards only start showing up once the level is this intense.
+const HAZARD_MIN_INTENSITY: f32 = 0.5;
+
+// Sweepers cross the upper playfield, above the bins but below the inlet.
+const SWEEPER_HEIGHT: f32 = 1.4;
+const SWEEPER_WIDTH: f32 = 0.3;
+const SWEEPER_Y: f32 = 2.7;
+const SWEEPER_SPEED: f32 = 2.0;
+const SWEEPER_WARNING_SECONDS: f32 = 1.5;
+const WARNING_BLINK_SECONDS: f32 = 0.2;
+
+// Saw blades cross lower down, at a random height, passing through everything but the rope, which
+// they cut.
+const SAW_RADIUS: f32 = 0.35;
+const SAW_TEETH: usize = 12;
+const SAW_MIN_Y: f32 = -1.5;
+const SAW_MAX_Y: f32 = 1.5;
+const SAW_SPEED: f32 = 3.0;
+const SAW_SPIN: f32 = 12.0;
+
+pub struct HazardPlugin;
+
+impl Plugin for HazardPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Playing), start_hazards)
+            .add_systems(
+                Update,
+                (schedule_hazards, warn_sweepers, despawn_sweepers)
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(in_state(StallState::Running)),
+            )
+            .add_systems(OnExit(AppState::Playing), cleanup_system::<Hazard>);
+    }
+}
+
+#[derive(Component)]
+struct Hazard;
+
+#[derive(Debug, Clone, Copy)]
+enum HazardKind {
+    Sweeper,
+    Saw,
+}
+
+/// A saw blade crossing the playfield, see `RopeCuttingPlugin`.
+#[derive(Component, Default)]
+pub struct SawBlade {
+    pub has_cut: bool,
+}
+
+#[derive(Resource)]
+struct HazardScheduler {
+    timer: Timer,
+}
+
+fn hazard_delay(intensity: f32) -> Duration {
+    let mut rng = rand::thread_rng();
+    // Hazards get more frequent as intensity rises from the minimum to 1.
+    let t = ((intensity - HAZARD_MIN_INTENSITY) / (1.0 - HAZARD_MIN_INTENSITY)).clamp(0.0, 1.0);
+    let min = 14.0 * (1.0 - t) + 7.0 * t;
+    Duration::from_secs_f32(rng.gen_range(min..min + 4.0))
+}
+
+fn start_hazards(mut commands: Commands) {
+    commands.insert_resource(HazardScheduler {
+        timer: Timer::new(hazard_delay(0.0), TimerMode::Once),
+    });
+}
+
+fn schedule_hazards(
+    mut commands: Commands,
+    mut scheduler: ResMut<HazardScheduler>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    level_state: Res<LevelState>,
+    time: Res<Time>,
+) {
+    if level_state.intensity < HAZARD_MIN_INTENSITY {
+        return;
+    }
+    if !scheduler.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let direction = match rng.gen_bool(0.5) {
+        true => 1.0,
+        false => -1.0,
+    };
+    let (kind, y) = match rng.gen_bool(0.5) {
+        true => (HazardKind::Sweeper, SWEEPER_Y),
+        false => (HazardKind::Saw, rng.gen_range(SAW_MIN_Y..SAW_MAX_Y)),
+    };
+    spawn_sweeper_warning(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        kind,
+        direction,
+        y,
+    );
+
+    scheduler
+        .timer
+        .set_duration(hazard_delay(level_state.intensity));
+    scheduler.timer.reset();
+}
+
+// Arrows blink at the edge the hazard will enter from, pointing the way it will travel.
+#[derive(Component)]
+struct SweeperWarning {
+    timer: Timer,
+    kind: HazardKind,
+    direction: f32,
+}
+
+fn spawn_sweeper_warning(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    kind: HazardKind,
+    direction: f32,
+    y: f32,
+) {
+    let mut arrow = Path::new();
+    arrow.move_to(Vec2::new(0.3, 0.0));
+    arrow.line_to(Vec2::new(-0.15, 0.25));
+    arrow.line_to(Vec2::new(-0.15, -0.25));
+    arrow.close();
+    let arrow_mesh: Mesh2dHandle = meshes.add(arrow.build_triangle_mesh()).into();
+    let material = materials.add(ColorMaterial::from(BAD_COLOR));
+
+    let edge_x = -direction * (WIDTH / 2.0 - 1.2);
+    let rotation = match direction > 0.0 {
+        true => Quat::IDENTITY,
+        false => Quat::from_rotation_z(std::f32::consts::PI),
+    };
+
+    commands
+        .spawn((
+            SpatialBundle {
+                transform: Transform::from_xyz(edge_x, y, 2.0).with_rotation(rotation),
+                ..default()
+            },
+            SweeperWarning {
+                timer: Timer::from_seconds(SWEEPER_WARNING_SECONDS, TimerMode::Once),
+                kind,
+                direction,
+            },
+            Hazard,
+            Name::new("SweeperWarning"),
+        ))
+        .with_children(|parent| {
+            for i in 0..3 {
+                parent.spawn(MaterialMesh2dBundle {
+                    transform: Transform::from_xyz(i as f32 * 0.4, 0.0, 0.0),
+                    mesh: arrow_mesh.clone(),
+                    material: material.clone(),
+                    ..default()
+                });
+            }
+        });
+}
+
+// Anything crossing the playfield, sweepers and saw blades alike.
+#[derive(Component)]
+struct Sweeper {
+    direction: f32,
+}
+
+fn warn_sweepers(
+    mut commands: Commands,
+    mut warnings: Query<(Entity, &mut SweeperWarning, &Transform, &mut Visibility)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    for (entity, mut warning, transform, mut visibility) in warnings.iter_mut() {
+        if !warning.timer.tick(time.delta()).just_finished() {
+            let blink = (warning.timer.elapsed_secs() / WARNING_BLINK_SECONDS) as u32 % 2 == 0;
+            *visibility = match blink {
+                true => Visibility::Inherited,
+                false => Visibility::Hidden,
+            };
+            continue;
+        }
+
+        commands.entity(entity).despawn_recursive();
+        let start_x = -warning.direction * (WIDTH / 2.0 + SWEEPER_WIDTH);
+        if let HazardKind::Saw = warning.kind {
+            spawn_saw(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                Vec2::new(start_x, transform.translation.y),
+                warning.direction,
+            );
+            continue;
+        }
+        commands.spawn((
+            MaterialMesh2dBundle {
+                // Drawn behind the walls, so it looks like it emerges from them.
+                transform: Transform::from_xyz(start_x, SWEEPER_Y, -0.1),
+                mesh: meshes
+                    .add(
+                        shape::Quad {
+                            size: Vec2::new(SWEEPER_WIDTH, SWEEPER_HEIGHT),
+                            ..default()
+                        }
+                        .into(),
+                    )
+                    .into(),
+                material: materials.add(ColorMaterial::from(BAD_COLOR)),
+                ..default()
+            },
+            RigidBody::Kinematic,
+            LinearVelocity(Vec2::new(warning.direction * SWEEPER_SPEED, 0.0)),
+            Collider::cuboid(SWEEPER_WIDTH, SWEEPER_HEIGHT),
+            CollisionLayers::new([Layer::Hazards], [Layer::Shapes, Layer::Rope]),
+            Sweeper {
+                direction: warning.direction,
+            },
+            Hazard,
+            Name::new("Sweeper"),
+        ));
+    }
+}
+
+fn spawn_saw(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    start: Vec2,
+    direction: f32,
+) {
+    let mut blade = Path::new();
+    for i in 0..SAW_TEETH * 2 {
+        let angle = i as f32 * std::f32::consts::PI / SAW_TEETH as f32;
+        let radius = match i % 2 {
+            0 => SAW_RADIUS,
+            _ => SAW_RADIUS * 0.75,
+        };
+        let point = Vec2::from_angle(angle) * radius;
+        match i {
+            0 => blade.move_to(point),
+            _ => blade.line_to(point),
+        }
+    }
+    blade.close();
+
+    commands.spawn((
+        MaterialMesh2dBundle {
+            transform: Transform::from_xyz(start.x, start.y, -0.1),
+            mesh: meshes.add(blade.build_triangle_mesh()).into(),
+            material: materials.add(ColorMaterial::from(BAD_COLOR)),
+            ..default()
+        },
+        RigidBody::Kinematic,
+        LinearVelocity(Vec2::new(direction * SAW_SPEED, 0.0)),
+        AngularVelocity(-direction * SAW_SPIN),
+        Collider::ball(SAW_RADIUS),
+        // Cuts rather than pushes.
+        Sensor,
+        CollisionLayers::new([Layer::Hazards], [Layer::Rope]),
+        Sweeper { direction },
+        SawBlade::default(),
+        Hazard,
+        Name::new("SawBlade"),
+    ));
+}
+
+fn despawn_sweepers(mut commands: Commands, sweepers: Query<(Entity, &Transform, &Sweeper)>) {
+    for (entity, transform, sweeper) in sweepers.iter() {
+        if transform.translation.x * sweeper.direction > WIDTH / 2.0 + SWEEPER_WIDTH {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
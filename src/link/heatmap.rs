@@ -0,0 +1,191 @@
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use super::{
+    gameplay::ShapeDrained,
+    palette::Palette,
+    player::Cursor,
+    spawn_level::{HEIGHT, WIDTH},
+    stats::ShapeSorted,
+    AppState, DespawnOnExitGameOver, BAD_COLOR,
+};
+
+// One heatmap cell covers a quarter meter square of the screen.
+const CELLS_PER_METER: f32 = 4.0;
+const GRID_WIDTH: usize = (WIDTH * CELLS_PER_METER) as usize;
+const GRID_HEIGHT: usize = (HEIGHT * CELLS_PER_METER) as usize;
+// Shape resolutions are splatted over a small disc so single events are visible.
+const SPLAT_RADIUS_CELLS: i32 = 2;
+const HEATMAP_ALPHA: f32 = 0.6;
+
+// Records where the cursors spent their time, and where shapes were sorted or drained, then
+// shows it all as an overlay on the game over screen.
+pub struct HeatmapPlugin;
+
+impl Plugin for HeatmapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Playing), start_heatmap)
+            .add_systems(
+                Update,
+                (record_cursors, record_resolutions).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(OnEnter(AppState::GameOver), spawn_heatmap);
+    }
+}
+
+#[derive(Resource)]
+struct RunHeatmap {
+    // Seconds any cursor spent in each cell.
+    cursor_time: Vec<f32>,
+    sorted: Vec<f32>,
+    drained: Vec<f32>,
+}
+
+impl Default for RunHeatmap {
+    fn default() -> Self {
+        Self {
+            cursor_time: vec![0.0; GRID_WIDTH * GRID_HEIGHT],
+            sorted: vec![0.0; GRID_WIDTH * GRID_HEIGHT],
+            drained: vec![0.0; GRID_WIDTH * GRID_HEIGHT],
+        }
+    }
+}
+
+// Grid coordinates for a world position, with (0, 0) in the bottom left, clamped onto the grid.
+fn cell(position: Vec2) -> (i32, i32) {
+    let x = ((position.x + WIDTH / 2.0) * CELLS_PER_METER) as i32;
+    let y = ((position.y + HEIGHT / 2.0) * CELLS_PER_METER) as i32;
+    (
+        x.clamp(0, GRID_WIDTH as i32 - 1),
+        y.clamp(0, GRID_HEIGHT as i32 - 1),
+    )
+}
+
+fn index(x: i32, y: i32) -> Option<usize> {
+    match (0..GRID_WIDTH as i32).contains(&x) && (0..GRID_HEIGHT as i32).contains(&y) {
+        true => Some(y as usize * GRID_WIDTH + x as usize),
+        false => None,
+    }
+}
+
+fn splat(grid: &mut [f32], position: Vec2) {
+    let (center_x, center_y) = cell(position);
+    for dy in -SPLAT_RADIUS_CELLS..=SPLAT_RADIUS_CELLS {
+        for dx in -SPLAT_RADIUS_CELLS..=SPLAT_RADIUS_CELLS {
+            let distance = ((dx * dx + dy * dy) as f32).sqrt();
+            let weight = 1.0 - distance / (SPLAT_RADIUS_CELLS as f32 + 1.0);
+            if let (true, Some(i)) = (weight > 0.0, index(center_x + dx, center_y + dy)) {
+                grid[i] += weight;
+            }
+        }
+    }
+}
+
+fn start_heatmap(mut commands: Commands) {
+    commands.insert_resource(RunHeatmap::default());
+}
+
+fn record_cursors(
+    mut heatmap: ResMut<RunHeatmap>,
+    cursors: Query<&Transform, With<Cursor>>,
+    time: Res<Time>,
+) {
+    for transform in cursors.iter() {
+        let (x, y) = cell(transform.translation.truncate());
+        if let Some(i) = index(x, y) {
+            heatmap.cursor_time[i] += time.delta_seconds();
+        }
+    }
+}
+
+fn record_resolutions(
+    mut heatmap: ResMut<RunHeatmap>,
+    mut sorted_events: EventReader<ShapeSorted>,
+    mut drained_events: EventReader<ShapeDrained>,
+    shapes: Query<&Transform>,
+) {
+    for event in sorted_events.iter() {
+        if let Ok(transform) = shapes.get(event.shape) {
+            splat(&mut heatmap.sorted, transform.translation.truncate());
+        }
+    }
+    for event in drained_events.iter() {
+        splat(&mut heatmap.drained, event.position);
+    }
+}
+
+fn normalized(grid: &[f32]) -> Vec<f32> {
+    let max = grid.iter().copied().fold(0.0, f32::max);
+    match max > 0.0 {
+        true => grid.iter().map(|value| value / max).collect(),
+        false => grid.to_vec(),
+    }
+}
+
+fn spawn_heatmap(
+    mut commands: Commands,
+    heatmap: Option<Res<RunHeatmap>>,
+    palette: Res<Palette>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Some(heatmap) = heatmap else {
+        return;
+    };
+
+    // Each layer is drawn in its own color, blended by how hot it is.
+    let layers = [
+        (normalized(&heatmap.cursor_time), palette.left),
+        (normalized(&heatmap.sorted), palette.right),
+        (normalized(&heatmap.drained), BAD_COLOR),
+    ];
+    let mut data = Vec::with_capacity(GRID_WIDTH * GRID_HEIGHT * 4);
+    // Image rows run top to bottom, but the grid runs bottom to top.
+    for y in (0..GRID_HEIGHT).rev() {
+        for x in 0..GRID_WIDTH {
+            let i = y * GRID_WIDTH + x;
+            let mut color = Vec3::ZERO;
+            let mut total_heat = 0.0;
+            let mut alpha: f32 = 0.0;
+            for (grid, layer_color) in layers.iter() {
+                let heat = grid[i];
+                let [r, g, b, _] = layer_color.as_rgba_f32();
+                color += Vec3::new(r, g, b) * heat;
+                total_heat += heat;
+                alpha = alpha.max(heat * HEATMAP_ALPHA);
+            }
+            if total_heat > 0.0 {
+                color /= total_heat;
+            }
+            let [r, g, b] = color.clamp(Vec3::ZERO, Vec3::ONE).to_array();
+            data.extend([r, g, b, alpha].map(|channel| (channel * 255.0) as u8));
+        }
+    }
+
+    let image = Image::new(
+        Extent3d {
+            width: GRID_WIDTH as u32,
+            height: GRID_HEIGHT as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+
+    commands.spawn((
+        SpriteBundle {
+            texture: images.add(image),
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(WIDTH, HEIGHT)),
+                ..default()
+            },
+            // Above the level, but below the game over text.
+            transform: Transform::from_xyz(0.0, 0.0, 0.5),
+            ..default()
+        },
+        DespawnOnExitGameOver,
+        Name::new("Heatmap"),
+    ));
+}
@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use bevy_xpbd_2d::prelude::*;
+
+use super::player::{CursorSlot, TargetVelocity};
+
+// Only hits at this speed or faster, in meters per second, flash, so a cursor resting against a
+// wall doesn't flicker.
+const MIN_IMPACT_SPEED: f32 = 2.0;
+// Impacts this fast or faster get the brightest flash.
+const MAX_IMPACT_SPEED: f32 = 15.0;
+const FLASH_SECONDS: f32 = 0.15;
+// The flash starts the size of the cursor and grows to this many times that as it fades.
+const FLASH_GROWTH: f32 = 1.6;
+const FLASH_SIZE: f32 = 0.3;
+
+// In place of rumble, a cursor flashes when it strikes a wall or shape, brighter for harder hits.
+pub struct ImpactFlashPlugin;
+
+impl Plugin for ImpactFlashPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (spawn_impact_flashes, fade_impact_flashes));
+    }
+}
+
+#[derive(Component)]
+struct ImpactFlash {
+    strength: f32,
+    timer: Timer,
+}
+
+fn spawn_impact_flashes(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionStarted>,
+    cursors: Query<(&LinearVelocity, &TargetVelocity), With<CursorSlot>>,
+) {
+    for CollisionStarted(a, b) in collisions.iter() {
+        // Cursors only collide with walls, shapes and hazards, never each other or the rope.
+        let Some((cursor, (velocity, target_velocity))) = [*a, *b]
+            .into_iter()
+            .find_map(|entity| Some((entity, cursors.get(entity).ok()?)))
+        else {
+            continue;
+        };
+        // By the time the event arrives the hit has already slowed the cursor, so the speed the
+        // hand was driving it at stands in for how fast it was going.
+        let speed = velocity.0.length().max(target_velocity.0.length());
+        if speed < MIN_IMPACT_SPEED {
+            continue;
+        }
+        let strength =
+            ((speed - MIN_IMPACT_SPEED) / (MAX_IMPACT_SPEED - MIN_IMPACT_SPEED)).clamp(0.2, 1.0);
+        let flash = commands
+            .spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::WHITE.with_a(strength),
+                        custom_size: Some(Vec2::splat(FLASH_SIZE)),
+                        ..default()
+                    },
+                    // Just in front of the cursor.
+                    transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                    ..default()
+                },
+                ImpactFlash {
+                    strength,
+                    timer: Timer::from_seconds(FLASH_SECONDS, TimerMode::Once),
+                },
+                Name::new("Impact flash"),
+            ))
+            .id();
+        commands.entity(cursor).add_child(flash);
+    }
+}
+
+fn fade_impact_flashes(
+    mut commands: Commands,
+    mut flashes: Query<(Entity, &mut ImpactFlash, &mut Sprite, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut flash, mut sprite, mut transform) in flashes.iter_mut() {
+        flash.timer.tick(time.delta());
+        if flash.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let progress = flash.timer.percent();
+        sprite.color.set_a(flash.strength * (1.0 - progress));
+        transform.scale = Vec3::splat(1.0 + (FLASH_GROWTH - 1.0) * progress);
+    }
+}
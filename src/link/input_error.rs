@@ -0,0 +1,208 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle, window::CursorGrabMode};
+
+use super::{
+    locale::{Localizer, TextKey},
+    palette::Palette,
+    text_style::TextStyler,
+    AppState, BACKGROUND_COLOR, TEXT_COLOR,
+};
+use crate::{
+    mischief::{MischiefInitError, MischiefRetryInit},
+    util::cleanup_system,
+};
+
+const RETRY_BUTTON_SIZE: Vec2 = Vec2::new(3.0, 0.8);
+const RETRY_BUTTON_POSITION: Vec2 = Vec2::new(0.0, -1.5);
+
+// If ManyMouse can't start, the game can't read any mice. Rather than crashing, explain what went
+// wrong and offer a retry button, clickable with the system mouse since that still works.
+pub struct InputErrorPlugin;
+
+impl Plugin for InputErrorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            enter_input_error
+                .run_if(in_state(AppState::Init))
+                .run_if(resource_exists::<MischiefInitError>()),
+        )
+        .add_systems(
+            OnEnter(AppState::InputError),
+            (spawn_error_screen, release_os_cursor),
+        )
+        .add_systems(
+            Update,
+            (retry_on_click, display_error, leave_input_error)
+                .chain()
+                .run_if(in_state(AppState::InputError)),
+        )
+        .add_systems(
+            OnExit(AppState::InputError),
+            (cleanup_system::<InputErrorScreen>, capture_os_cursor),
+        );
+    }
+}
+
+#[derive(Component)]
+struct InputErrorScreen;
+
+#[derive(Component)]
+struct ErrorMessage;
+
+fn enter_input_error(mut app_state: ResMut<NextState<AppState>>) {
+    app_state.set(AppState::InputError);
+}
+
+fn leave_input_error(
+    error: Option<Res<MischiefInitError>>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    if error.is_none() {
+        println!("ManyMouse initialized, returning to the title screen");
+        app_state.set(AppState::Init);
+    }
+}
+
+// The OS cursor is normally hidden and locked, since the game reads the mice directly.
+fn release_os_cursor(mut windows: Query<&mut Window>) {
+    for mut window in windows.iter_mut() {
+        window.cursor.visible = true;
+        window.cursor.grab_mode = CursorGrabMode::None;
+    }
+}
+
+fn capture_os_cursor(mut windows: Query<&mut Window>) {
+    for mut window in windows.iter_mut() {
+        window.cursor.visible = false;
+        window.cursor.grab_mode = CursorGrabMode::Locked;
+    }
+}
+
+fn spawn_error_screen(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    palette: Res<Palette>,
+    text_styler: TextStyler,
+) {
+    let text_style = TextStyle {
+        font: text_styler.font(),
+        font_size: 100.0,
+        color: TEXT_COLOR,
+    };
+
+    commands
+        .spawn((
+            SpatialBundle::default(),
+            InputErrorScreen,
+            Name::new("InputErrorScreen"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, 2.0, 1.0).with_scale(Vec3::splat(0.006)),
+                    text: Text::from_section("", text_style.clone())
+                        .with_alignment(TextAlignment::Center),
+                    ..default()
+                },
+                TextKey("input_error.title"),
+                Name::new("InputErrorTitle"),
+            ));
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, 0.6, 1.0).with_scale(Vec3::splat(0.003)),
+                    text: Text {
+                        sections: vec![TextSection::new("", text_style.clone())],
+                        alignment: TextAlignment::Center,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    ..default()
+                },
+                ErrorMessage,
+                Name::new("InputErrorMessage"),
+            ));
+            parent
+                .spawn((
+                    MaterialMesh2dBundle {
+                        transform: Transform::from_xyz(
+                            RETRY_BUTTON_POSITION.x,
+                            RETRY_BUTTON_POSITION.y,
+                            1.0,
+                        ),
+                        mesh: meshes
+                            .add(
+                                shape::Quad {
+                                    size: RETRY_BUTTON_SIZE,
+                                    ..default()
+                                }
+                                .into(),
+                            )
+                            .into(),
+                        material: materials.add(ColorMaterial::from(palette.left)),
+                        ..default()
+                    },
+                    Name::new("RetryButton"),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text2dBundle {
+                            transform: Transform::from_xyz(0.0, 0.0, 0.1)
+                                .with_scale(Vec3::splat(0.004)),
+                            text: Text::from_section(
+                                "",
+                                TextStyle {
+                                    color: BACKGROUND_COLOR,
+                                    ..text_style.clone()
+                                },
+                            ),
+                            ..default()
+                        },
+                        TextKey("input_error.retry"),
+                    ));
+                });
+        });
+}
+
+fn display_error(
+    error: Option<Res<MischiefInitError>>,
+    localizer: Localizer,
+    mut messages: Query<&mut Text, With<ErrorMessage>>,
+) {
+    let Some(error) = error else {
+        return;
+    };
+    if !error.is_changed() {
+        return;
+    }
+    for mut text in messages.iter_mut() {
+        text.sections[0].value = format!("{}\n\n{}", error.0, localizer.get("input_error.help"));
+    }
+}
+
+fn retry_on_click(
+    mouse_buttons: Res<Input<MouseButton>>,
+    keys: Res<Input<KeyCode>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut retry: EventWriter<MischiefRetryInit>,
+) {
+    let clicked = mouse_buttons.just_pressed(MouseButton::Left) && {
+        let cursor = windows
+            .get_single()
+            .ok()
+            .and_then(|window| window.cursor_position());
+        let camera = cameras.get_single().ok();
+        let world_position = cursor
+            .zip(camera)
+            .and_then(|(cursor, (camera, transform))| {
+                camera.viewport_to_world_2d(transform, cursor)
+            });
+        world_position.is_some_and(|position| {
+            Rect::from_center_size(RETRY_BUTTON_POSITION, RETRY_BUTTON_SIZE).contains(position)
+        })
+    };
+    if clicked || keys.just_pressed(KeyCode::R) {
+        println!("Retrying ManyMouse initialization");
+        retry.send(MischiefRetryInit);
+    }
+}
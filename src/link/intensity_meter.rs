@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+
+use super::{
+    gameplay::LevelState, locale::TextKey, spawn_level::HEIGHT, text_style::TextStyler, AppState,
+    BAD_COLOR, TEXT_COLOR,
+};
+
+// The meter sits along the bottom of the screen, to the right of the tension meter.
+const METER_SIZE: Vec2 = Vec2::new(2.0, 0.12);
+const METER_POSITION: Vec2 = Vec2::new(4.5, -HEIGHT / 2.0 + 0.15);
+// The meter closes most of the gap to the actual intensity over this long, so it creeps up rather
+// than ticking with each shape.
+const EASE_SECONDS: f32 = 2.0;
+
+// Shows the spawn intensity during play on a gauge which slowly fills and reddens as the pressure
+// builds.
+pub struct IntensityMeterPlugin;
+
+impl Plugin for IntensityMeterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_intensity_meter)
+            .add_systems(Update, display_intensity_meter);
+    }
+}
+
+#[derive(Component)]
+struct IntensityMeter;
+
+#[derive(Component, Default)]
+struct IntensityMeterFill {
+    // The intensity the meter shows, easing towards the level's.
+    shown: f32,
+}
+
+fn spawn_intensity_meter(mut commands: Commands, text_styler: TextStyler) {
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: TEXT_COLOR.with_a(0.2),
+                    custom_size: Some(METER_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_translation(METER_POSITION.extend(1.0)),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            IntensityMeter,
+            Name::new("IntensityMeter"),
+        ))
+        .with_children(|meter| {
+            meter.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: TEXT_COLOR,
+                        custom_size: Some(METER_SIZE),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, 0.1)
+                        .with_scale(Vec3::new(0.0, 1.0, 1.0)),
+                    ..default()
+                },
+                IntensityMeterFill::default(),
+            ));
+            meter.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(-METER_SIZE.x / 2.0 - 0.1, 0.0, 0.1)
+                        .with_scale(Vec3::splat(0.002)),
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: text_styler.font(),
+                            font_size: 100.0,
+                            color: TEXT_COLOR,
+                        },
+                    )
+                    .with_alignment(TextAlignment::Right),
+                    text_anchor: bevy::sprite::Anchor::CenterRight,
+                    ..default()
+                },
+                TextKey("hud.intensity"),
+            ));
+        });
+}
+
+// Drains back to empty between runs, so the next one starts from nothing.
+fn display_intensity_meter(
+    level_state: Option<Res<LevelState>>,
+    state: Res<State<AppState>>,
+    mut meters: Query<&mut Visibility, With<IntensityMeter>>,
+    mut fills: Query<(&mut IntensityMeterFill, &mut Sprite, &mut Transform)>,
+    time: Res<Time>,
+) {
+    let playing = *state.get() == AppState::Playing;
+    for mut visibility in meters.iter_mut() {
+        *visibility = match playing {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+    }
+
+    let intensity = match (playing, level_state) {
+        (true, Some(level_state)) => level_state.intensity.clamp(0.0, 1.0),
+        _ => 0.0,
+    };
+    let ease = 1.0 - (-time.delta_seconds() / EASE_SECONDS).exp();
+    for (mut fill, mut sprite, mut transform) in fills.iter_mut() {
+        fill.shown += (intensity - fill.shown) * ease;
+        let [r, g, b, _] = TEXT_COLOR.as_rgba_f32();
+        let [bad_r, bad_g, bad_b, _] = BAD_COLOR.as_rgba_f32();
+        sprite.color = Color::rgb(
+            r + (bad_r - r) * fill.shown,
+            g + (bad_g - g) * fill.shown,
+            b + (bad_b - b) * fill.shown,
+        );
+        transform.scale.x = fill.shown;
+        // Grows from the left edge.
+        transform.translation.x = -METER_SIZE.x / 2.0 * (1.0 - fill.shown);
+    }
+}
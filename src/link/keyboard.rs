@@ -0,0 +1,123 @@
+use bevy::{
+    input::common_conditions::{input_just_pressed, input_toggle_active},
+    prelude::*,
+};
+
+use super::{
+    locale::TextKey,
+    player::{claim_cursor, move_cursors, AttachState, Cursor, CursorSlot, TargetVelocity},
+    text_style::TextStyler,
+    title_hints::TitleHint,
+    AppState, DespawnOnExitInit, TEXT_COLOR,
+};
+use crate::mischief::{MischiefDevices, KEYBOARD_DEVICE};
+
+// Keyboard cursors move at this speed, in meters per second.
+const KEYBOARD_CURSOR_SPEED: f32 = 8.0;
+
+// With only one mouse around, the second cursor can be steered with WASD or the arrow keys, so
+// the game is still playable solo on a laptop.
+pub struct KeyboardCursorPlugin;
+
+impl Plugin for KeyboardCursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Init), spawn_keyboard_hint)
+            .add_systems(
+                Update,
+                (
+                    claim_keyboard_cursor
+                        .run_if(in_state(AttachState::Waiting))
+                        .run_if(input_just_pressed(KeyCode::K))
+                        .run_if(single_mouse),
+                    show_keyboard_hint,
+                )
+                    .run_if(in_state(AppState::Init)),
+            )
+            .add_systems(
+                Update,
+                drive_keyboard_cursor
+                    .after(move_cursors)
+                    .run_if(input_toggle_active(true, KeyCode::Grave)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct KeyboardHint;
+
+fn single_mouse(devices: Res<MischiefDevices>) -> bool {
+    devices.connected().count() == 1
+}
+
+fn spawn_keyboard_hint(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: TitleHint::Keyboard.transform(),
+            text: Text {
+                sections: vec![TextSection::new(
+                    "",
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: TEXT_COLOR,
+                    },
+                )],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        KeyboardHint,
+        DespawnOnExitInit,
+        TextKey("title.keyboard_hint"),
+        Name::new("KeyboardHint"),
+    ));
+}
+
+fn show_keyboard_hint(
+    devices: Res<MischiefDevices>,
+    attach_state: Res<State<AttachState>>,
+    mut hints: Query<&mut Visibility, With<KeyboardHint>>,
+) {
+    let shown = single_mouse(devices) && attach_state.get() == &AttachState::Waiting;
+    for mut visibility in hints.iter_mut() {
+        *visibility = match shown {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+    }
+}
+
+// The keyboard takes whichever cursor the mouse hasn't claimed, preferring the right one.
+fn claim_keyboard_cursor(mut cursors: Query<(&CursorSlot, &mut Cursor)>) {
+    if claim_cursor(&mut cursors, Some(CursorSlot::RIGHT), KEYBOARD_DEVICE).is_none() {
+        claim_cursor(&mut cursors, Some(CursorSlot::LEFT), KEYBOARD_DEVICE);
+    }
+}
+
+fn drive_keyboard_cursor(
+    keys: Res<Input<KeyCode>>,
+    mut cursors: Query<(&mut TargetVelocity, &Cursor)>,
+) {
+    let pressed = |codes: [KeyCode; 2]| keys.any_pressed(codes);
+    let mut direction = Vec2::ZERO;
+    if pressed([KeyCode::W, KeyCode::Up]) {
+        direction.y += 1.0;
+    }
+    if pressed([KeyCode::S, KeyCode::Down]) {
+        direction.y -= 1.0;
+    }
+    if pressed([KeyCode::A, KeyCode::Left]) {
+        direction.x -= 1.0;
+    }
+    if pressed([KeyCode::D, KeyCode::Right]) {
+        direction.x += 1.0;
+    }
+
+    for (mut target_velocity, cursor) in cursors.iter_mut() {
+        if cursor.0 == Some(KEYBOARD_DEVICE) {
+            target_velocity.0 = direction.normalize_or_zero() * KEYBOARD_CURSOR_SPEED;
+        }
+    }
+}
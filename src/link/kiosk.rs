@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+
+use super::{
+    gameplay::Shape,
+    player::{AttachState, Cursor},
+    AppState,
+};
+use crate::mischief::{poll_events, MischiefEvent};
+
+// Launch with `--kiosk` when running unattended, e.g. at an event booth.
+const KIOSK_FLAG: &str = "--kiosk";
+const DEFAULT_IDLE_MINUTES: f32 = 3.0;
+
+// In kiosk mode, a session nobody has touched for a few minutes is abandoned: the run ends, the
+// cursors are released, and the game goes back to the title screen for the next visitors.
+pub struct KioskPlugin;
+
+impl Plugin for KioskPlugin {
+    fn build(&self, app: &mut App) {
+        if !std::env::args().any(|arg| arg == KIOSK_FLAG) {
+            return;
+        }
+
+        app.register_type::<KioskMode>()
+            .init_resource::<KioskMode>()
+            .init_resource::<IdleSeconds>()
+            .add_systems(OnEnter(AttachState::Attached), reset_idle_time)
+            .add_systems(
+                Update,
+                watch_for_idle
+                    .after(poll_events)
+                    .run_if(in_state(AttachState::Attached))
+                    .run_if(not(in_state(AppState::Init))),
+            );
+    }
+}
+
+/// Present when the game was launched in kiosk mode.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct KioskMode {
+    pub idle_minutes: f32,
+}
+
+impl Default for KioskMode {
+    fn default() -> Self {
+        KioskMode {
+            idle_minutes: DEFAULT_IDLE_MINUTES,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+struct IdleSeconds(f32);
+
+fn reset_idle_time(mut idle: ResMut<IdleSeconds>) {
+    idle.0 = 0.0;
+}
+
+fn watch_for_idle(
+    mut commands: Commands,
+    kiosk: Res<KioskMode>,
+    mut idle: ResMut<IdleSeconds>,
+    mut mouse_events: EventReader<MischiefEvent>,
+    keys: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut cursors: Query<&mut Cursor>,
+    shapes: Query<Entity, With<Shape>>,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut attach_state: ResMut<NextState<AttachState>>,
+    time: Res<Time>,
+) {
+    // Any input from an attached mouse counts, as do the keyboard and gamepad sticks, which may be
+    // driving a cursor.
+    let mouse_active = mouse_events
+        .iter()
+        .any(|event| cursors.iter().any(|cursor| cursor.0 == Some(event.device)));
+    let gamepad_active = gamepads.iter().any(|gamepad| {
+        [GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY]
+            .into_iter()
+            .any(|axis| {
+                gamepad_axes
+                    .get(GamepadAxis::new(gamepad, axis))
+                    .is_some_and(|value| value != 0.0)
+            })
+    });
+    if mouse_active || gamepad_active || keys.get_pressed().next().is_some() {
+        idle.0 = 0.0;
+        return;
+    }
+
+    idle.0 += time.delta_seconds();
+    if idle.0 < kiosk.idle_minutes * 60.0 {
+        return;
+    }
+
+    println!(
+        "No input for {} minutes, returning to the title screen",
+        kiosk.idle_minutes
+    );
+    for mut cursor in cursors.iter_mut() {
+        cursor.0 = None;
+    }
+    for entity in shapes.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    app_state.set(AppState::Init);
+    attach_state.set(AttachState::Waiting);
+}
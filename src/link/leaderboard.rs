@@ -0,0 +1,395 @@
+use std::{cmp::Reverse, fs, io::Write};
+
+use bevy::prelude::*;
+
+use super::{
+    checksum::checksum_of, gameplay::Score, locale::Localizer, stats::RunStats,
+    text_style::TextStyler, AppState, DespawnOnExitGameOver, TEXT_COLOR,
+};
+use crate::{
+    mischief::{MischiefEvent, MischiefEventData},
+    util::cleanup_system,
+};
+
+// The ten best scores, one per line as initials, the score, the checksum of the run that scored it
+// and a checksum over all three, both in hex. Lines whose checksum doesn't match, most likely
+// because they were edited by hand, are dropped.
+const LEADERBOARD_PATH: &str = "leaderboard.txt";
+const LEADERBOARD_SIZE: usize = 10;
+const INITIALS_LENGTH: usize = 3;
+// Moving a mouse this many counts up or down steps the selected letter by one.
+const LETTER_STEP_COUNTS: i32 = 40;
+// Clicks are ignored for this long after the board is opened from the main menu, so the click that
+// opened it doesn't also close it.
+const BROWSE_GRACE_SECONDS: f32 = 0.5;
+
+// The best scores on this machine, shown at every game over. A score good enough to make the board
+// first asks for the players' initials, picked with the mouse: move it up or down to change the
+// letter, left click to go on to the next, right click to go back. The board can also be looked at
+// from the main menu, until the next click.
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<LeaderboardState>()
+            .insert_resource(Leaderboard::load())
+            .add_systems(OnEnter(AppState::GameOver), open_leaderboard)
+            .add_systems(OnEnter(LeaderboardState::EnteringInitials), start_initials)
+            .add_systems(
+                Update,
+                enter_initials.run_if(in_state(LeaderboardState::EnteringInitials)),
+            )
+            .add_systems(
+                Update,
+                display_leaderboard.run_if(not(in_state(LeaderboardState::Hidden))),
+            )
+            .add_systems(OnExit(AppState::GameOver), close_leaderboard)
+            .add_systems(OnEnter(AppState::Leaderboard), browse_leaderboard)
+            .add_systems(
+                Update,
+                stop_browsing.run_if(in_state(AppState::Leaderboard)),
+            )
+            .add_systems(
+                OnExit(AppState::Leaderboard),
+                (close_leaderboard, cleanup_system::<LeaderboardDisplay>),
+            );
+    }
+}
+
+/// Whether the leaderboard is up, and if so whether it's waiting on initials. A new game can't be
+/// started while it is.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
+pub enum LeaderboardState {
+    #[default]
+    Hidden,
+    EnteringInitials,
+    Shown,
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct Leaderboard {
+    // Best first.
+    entries: Vec<LeaderboardEntry>,
+}
+
+#[derive(Debug, Clone)]
+struct LeaderboardEntry {
+    initials: String,
+    score: i32,
+    // The run's simulation checksum, see `ChecksumPlugin`, so a score can't be copied to another
+    // line without its run.
+    run: u64,
+}
+
+impl LeaderboardEntry {
+    fn checksum(&self) -> u64 {
+        let letters = self.initials.bytes().map(i64::from);
+        checksum_of(
+            [self.run as i64, self.score as i64]
+                .into_iter()
+                .chain(letters),
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let [initials, score, run, checksum] = line.split_whitespace().collect::<Vec<_>>()[..]
+        else {
+            return None;
+        };
+        let entry = Self {
+            initials: initials.to_owned(),
+            score: score.parse().ok()?,
+            run: u64::from_str_radix(run, 16).ok()?,
+        };
+        let checksum = u64::from_str_radix(checksum, 16).ok()?;
+        (entry.checksum() == checksum).then_some(entry)
+    }
+}
+
+impl Leaderboard {
+    fn load() -> Self {
+        let mut leaderboard = Self::default();
+        let Ok(contents) = fs::read_to_string(LEADERBOARD_PATH) else {
+            return leaderboard;
+        };
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            match LeaderboardEntry::from_line(line) {
+                Some(entry) => leaderboard.entries.push(entry),
+                None => println!("Skipping malformed or altered leaderboard line: {}", line),
+            }
+        }
+        leaderboard
+            .entries
+            .sort_by_key(|entry| Reverse(entry.score));
+        leaderboard.entries.truncate(LEADERBOARD_SIZE);
+        leaderboard
+    }
+
+    fn save(&self) {
+        let result = fs::File::create(LEADERBOARD_PATH).and_then(|mut file| {
+            self.entries.iter().try_for_each(|entry| {
+                writeln!(
+                    file,
+                    "{} {} {:016x} {:016x}",
+                    entry.initials,
+                    entry.score,
+                    entry.run,
+                    entry.checksum()
+                )
+            })
+        });
+        if let Err(error) = result {
+            println!("Failed to save leaderboard: {}", error);
+        }
+    }
+
+    /// The top score on the board, if anyone's made it yet.
+    pub fn best(&self) -> Option<i32> {
+        self.entries.first().map(|entry| entry.score)
+    }
+
+    fn qualifies(&self, score: i32) -> bool {
+        self.entries.len() < LEADERBOARD_SIZE
+            || self
+                .entries
+                .last()
+                .map_or(true, |entry| score > entry.score)
+    }
+
+    // Ties go below the scores already on the board.
+    fn insert(&mut self, entry: LeaderboardEntry) -> usize {
+        let rank = self
+            .entries
+            .iter()
+            .position(|other| entry.score > other.score)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(rank, entry);
+        self.entries.truncate(LEADERBOARD_SIZE);
+        rank
+    }
+}
+
+// The initials being entered, and the score and run they're for.
+#[derive(Resource)]
+struct InitialsEntry {
+    score: i32,
+    run: u64,
+    letters: [u8; INITIALS_LENGTH],
+    selected: usize,
+    // Mouse motion not yet turned into a letter step.
+    counts: i32,
+}
+
+// The board entry to highlight, once the initials are in.
+#[derive(Resource)]
+struct NewEntry(usize);
+
+#[derive(Resource)]
+struct BrowseGrace(Timer);
+
+#[derive(Component)]
+struct LeaderboardDisplay;
+
+fn spawn_leaderboard_display<'w, 's, 'a>(
+    commands: &'a mut Commands<'w, 's>,
+    text_styler: &TextStyler,
+    x: f32,
+) -> bevy::ecs::system::EntityCommands<'w, 's, 'a> {
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(x, 3.0, 1.0).with_scale(Vec3::splat(0.003)),
+            text_anchor: bevy::sprite::Anchor::TopCenter,
+            text: Text {
+                sections: vec![TextSection::new(
+                    "",
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: TEXT_COLOR,
+                    },
+                )],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        LeaderboardDisplay,
+        Name::new("Leaderboard"),
+    ))
+}
+
+fn open_leaderboard(
+    mut commands: Commands,
+    leaderboard: Res<Leaderboard>,
+    score: Res<Score>,
+    mut next_state: ResMut<NextState<LeaderboardState>>,
+    text_styler: TextStyler,
+) {
+    spawn_leaderboard_display(&mut commands, &text_styler, -4.5).insert(DespawnOnExitGameOver);
+    next_state.set(match leaderboard.qualifies(score.total()) {
+        true => LeaderboardState::EnteringInitials,
+        false => LeaderboardState::Shown,
+    });
+}
+
+fn start_initials(mut commands: Commands, score: Res<Score>, run_stats: Res<RunStats>) {
+    commands.insert_resource(InitialsEntry {
+        score: score.total(),
+        run: run_stats.checksum,
+        letters: [b'A'; INITIALS_LENGTH],
+        selected: 0,
+        counts: 0,
+    });
+}
+
+fn browse_leaderboard(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<LeaderboardState>>,
+    text_styler: TextStyler,
+) {
+    commands.insert_resource(BrowseGrace(Timer::from_seconds(
+        BROWSE_GRACE_SECONDS,
+        TimerMode::Once,
+    )));
+    spawn_leaderboard_display(&mut commands, &text_styler, 0.0);
+    next_state.set(LeaderboardState::Shown);
+}
+
+fn stop_browsing(
+    mut grace: ResMut<BrowseGrace>,
+    mut mischief_events: EventReader<MischiefEvent>,
+    mut app_state: ResMut<NextState<AppState>>,
+    time: Res<Time>,
+) {
+    let ready = grace.0.tick(time.delta()).finished();
+    for event in mischief_events.iter() {
+        if let MischiefEventData::Button { pressed: true, .. } = event.event_data {
+            if ready {
+                app_state.set(AppState::Init);
+            }
+        }
+    }
+}
+
+fn close_leaderboard(mut commands: Commands, mut next_state: ResMut<NextState<LeaderboardState>>) {
+    commands.remove_resource::<InitialsEntry>();
+    commands.remove_resource::<NewEntry>();
+    commands.remove_resource::<BrowseGrace>();
+    next_state.set(LeaderboardState::Hidden);
+}
+
+fn enter_initials(
+    mut commands: Commands,
+    mut entry: ResMut<InitialsEntry>,
+    mut leaderboard: ResMut<Leaderboard>,
+    mut mischief_events: EventReader<MischiefEvent>,
+    mut next_state: ResMut<NextState<LeaderboardState>>,
+) {
+    for event in mischief_events.iter() {
+        match event.event_data {
+            MischiefEventData::RelMotion { y, .. } => {
+                // Mouse counts grow downwards, and moving up goes forwards through the alphabet.
+                entry.counts -= y;
+                let steps = entry.counts / LETTER_STEP_COUNTS;
+                entry.counts -= steps * LETTER_STEP_COUNTS;
+                let selected = entry.selected;
+                let letter = (entry.letters[selected] - b'A') as i32 + steps;
+                entry.letters[selected] = b'A' + letter.rem_euclid(26) as u8;
+            }
+            MischiefEventData::Button {
+                button: 0,
+                pressed: true,
+            } => {
+                entry.selected += 1;
+                entry.counts = 0;
+            }
+            MischiefEventData::Button {
+                button: 1,
+                pressed: true,
+            } => {
+                entry.selected = entry.selected.saturating_sub(1);
+                entry.counts = 0;
+            }
+            _ => {}
+        }
+        if entry.selected == INITIALS_LENGTH {
+            let initials = String::from_utf8_lossy(&entry.letters).into_owned();
+            println!("{} made the leaderboard with {}", initials, entry.score);
+            let rank = leaderboard.insert(LeaderboardEntry {
+                initials,
+                score: entry.score,
+                run: entry.run,
+            });
+            leaderboard.save();
+            commands.insert_resource(NewEntry(rank));
+            commands.remove_resource::<InitialsEntry>();
+            next_state.set(LeaderboardState::Shown);
+            return;
+        }
+    }
+}
+
+fn display_leaderboard(
+    leaderboard: Res<Leaderboard>,
+    entry: Option<Res<InitialsEntry>>,
+    new_entry: Option<Res<NewEntry>>,
+    browsing: Option<Res<BrowseGrace>>,
+    localizer: Localizer,
+    mut displays: Query<&mut Text, With<LeaderboardDisplay>>,
+) {
+    let lines = match entry {
+        Some(entry) => {
+            let letters = entry
+                .letters
+                .iter()
+                .enumerate()
+                .map(|(index, letter)| match index == entry.selected {
+                    true => format!("[{}]", *letter as char),
+                    false => format!(" {} ", *letter as char),
+                })
+                .collect::<String>();
+            vec![
+                localizer.format("leaderboard.new_high_score", &[&entry.score.to_string()]),
+                localizer.get("leaderboard.enter_initials"),
+                String::new(),
+                letters,
+                String::new(),
+                localizer.get("leaderboard.change_letter"),
+                localizer.get("leaderboard.next_letter"),
+            ]
+        }
+        None => {
+            let mut lines = vec![localizer.get("leaderboard.title"), String::new()];
+            lines.extend(
+                leaderboard
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .map(|(rank, board_entry)| {
+                        let marker = match new_entry.as_ref().is_some_and(|new| new.0 == rank) {
+                            true => ">",
+                            false => " ",
+                        };
+                        format!(
+                            "{} {:>2}. {}  {}",
+                            marker,
+                            rank + 1,
+                            board_entry.initials,
+                            board_entry.score
+                        )
+                    }),
+            );
+            if leaderboard.entries.is_empty() {
+                lines.push(localizer.get("leaderboard.empty"));
+            }
+            if browsing.is_some() {
+                lines.push(String::new());
+                lines.push(localizer.get("leaderboard.back"));
+            }
+            lines
+        }
+    };
+    for mut text in displays.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
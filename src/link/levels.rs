@@ -0,0 +1,272 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    ecs::system::SystemParam,
+    prelude::*,
+    reflect::{TypePath, TypeUuid},
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+use super::{
+    gameplay::Score,
+    locale::Localizer,
+    spawn_level::{BIN_BOTTOM, CENTER_BIN_WALL_THICKNESS, HEIGHT, OUTER_WALL_THICKNESS, WIDTH},
+    text_style::TextStyler,
+    AppState, DespawnOnExitGameOver, TEXT_COLOR,
+};
+
+// Levels are RON files in assets/levels, e.g. assets/levels/1_sorting_room.level.ron, played in
+// file name order. See `LevelDefinition` for what goes in one.
+const LEVEL_DIRECTORY: &str = "levels";
+const LEVEL_EXTENSION: &str = "level.ron";
+
+// Games are played through a sequence of levels, each with its own walls, bins and number of
+// shapes. Reaching a level's target score moves the players on to the next one for the following
+// game; the sequence starts over back at the title screen.
+pub struct LevelsPlugin;
+
+impl Plugin for LevelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<LevelDefinition>()
+            .init_asset_loader::<LevelLoader>()
+            .register_type::<LevelIndex>()
+            .init_resource::<LevelIndex>()
+            .init_resource::<LevelSequence>()
+            .add_systems(OnEnter(AppState::Init), reset_level)
+            .add_systems(OnEnter(AppState::GameOver), advance_level);
+    }
+}
+
+/// The layout and goal of one level, loaded from a .level.ron file. Distances are in meters.
+#[derive(Debug, Clone, Deserialize, TypeUuid, TypePath)]
+#[uuid = "5d0f3c1e-8a4b-4f61-9c2d-7b3e9a6f1d42"]
+pub struct LevelDefinition {
+    pub name: String,
+    /// Shapes spawned in a classic game.
+    pub num_shapes: u32,
+    /// Points needed to move on to the next level.
+    pub target_score: i32,
+    /// Where shapes appear, above the inlet.
+    pub shape_spawn_region: Rect,
+    /// Inside width of the left and right bins.
+    pub bin_width: f32,
+    /// Height of the left and right bins' openings.
+    pub bin_top: f32,
+    /// Gap in the floor between the bins, through which missed shapes fall.
+    pub drain_width: f32,
+    /// Gap in the ceiling through which shapes enter.
+    pub inlet_width: f32,
+    /// Inside width of the center bin, which stands in the drain.
+    pub center_bin_width: f32,
+    /// Height of the center bin above the floor of the other bins.
+    pub center_bin_height: f32,
+    /// Spinning obstacles which knock falling shapes aside, but let the rope through.
+    #[serde(default)]
+    pub paddles: Vec<PaddleDefinition>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaddleDefinition {
+    /// Where the paddle spins about its middle.
+    pub position: Vec2,
+    pub length: f32,
+    /// In radians per second, counterclockwise.
+    pub angular_speed: f32,
+}
+
+// The original layout, played until the level files have loaded, or if there are none.
+impl Default for LevelDefinition {
+    fn default() -> Self {
+        Self {
+            name: "The Sorting Room".to_owned(),
+            num_shapes: 20,
+            target_score: 12,
+            shape_spawn_region: Rect::new(-3.0, 5.0, 3.0, 6.0),
+            bin_width: 1.35,
+            bin_top: 0.0,
+            drain_width: 3.0,
+            inlet_width: 8.0,
+            center_bin_width: 1.2,
+            center_bin_height: 0.9,
+            paddles: Vec::new(),
+        }
+    }
+}
+
+impl LevelDefinition {
+    pub fn left_score_region(&self) -> Rect {
+        let left = -WIDTH / 2.0 + OUTER_WALL_THICKNESS;
+        Rect::new(left, BIN_BOTTOM, left + self.bin_width, self.bin_top)
+    }
+
+    pub fn right_score_region(&self) -> Rect {
+        let right = WIDTH / 2.0 - OUTER_WALL_THICKNESS;
+        Rect::new(right - self.bin_width, BIN_BOTTOM, right, self.bin_top)
+    }
+
+    pub fn center_score_region(&self) -> Rect {
+        Rect::new(
+            -self.center_bin_width / 2.0,
+            BIN_BOTTOM + CENTER_BIN_WALL_THICKNESS,
+            self.center_bin_width / 2.0,
+            BIN_BOTTOM + self.center_bin_height,
+        )
+    }
+}
+
+#[derive(Default)]
+struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let level: LevelDefinition = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(level));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[LEVEL_EXTENSION]
+    }
+}
+
+/// Which level is being played, as an index into the level files. The level's walls are respawned
+/// whenever it changes.
+#[derive(Resource, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct LevelIndex(pub usize);
+
+/// The level files found at startup, in the order they're played.
+#[derive(Resource)]
+pub struct LevelSequence {
+    levels: Vec<Handle<LevelDefinition>>,
+    fallback: LevelDefinition,
+}
+
+impl FromWorld for LevelSequence {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let handles = asset_server
+            .load_folder(LEVEL_DIRECTORY)
+            .unwrap_or_else(|error| {
+                println!("Failed to load levels: {}", error);
+                Vec::new()
+            });
+        let mut levels: Vec<_> = handles
+            .into_iter()
+            .filter_map(|handle| {
+                let path = asset_server.get_handle_path(&handle)?;
+                let name = path.path().file_name()?.to_string_lossy().into_owned();
+                name.ends_with(&format!(".{}", LEVEL_EXTENSION))
+                    .then(|| (name, handle.typed()))
+            })
+            .collect();
+        levels.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let names: Vec<_> = levels.iter().map(|(name, _)| name.as_str()).collect();
+        println!("Levels: {}", names.join(", "));
+        Self {
+            levels: levels.into_iter().map(|(_, handle)| handle).collect(),
+            fallback: LevelDefinition::default(),
+        }
+    }
+}
+
+impl LevelSequence {
+    fn count(&self) -> usize {
+        self.levels.len().max(1)
+    }
+
+    fn get<'a>(
+        &'a self,
+        index: usize,
+        definitions: &'a Assets<LevelDefinition>,
+    ) -> &'a LevelDefinition {
+        self.levels
+            .get(index)
+            .and_then(|handle| definitions.get(handle))
+            .unwrap_or(&self.fallback)
+    }
+}
+
+/// The level being played, for the systems which depend on its layout.
+#[derive(SystemParam)]
+pub struct CurrentLevel<'w> {
+    index: Res<'w, LevelIndex>,
+    sequence: Res<'w, LevelSequence>,
+    definitions: Res<'w, Assets<LevelDefinition>>,
+}
+
+impl<'w> CurrentLevel<'w> {
+    pub fn get(&self) -> &LevelDefinition {
+        self.sequence.get(self.index.0, &self.definitions)
+    }
+
+    /// Whether another level has been picked since the system last ran.
+    pub fn is_changed(&self) -> bool {
+        self.index.is_changed() && !self.index.is_added()
+    }
+
+    /// Whether this event means the current level's file has been loaded or edited.
+    pub fn is_loaded_by(&self, event: &AssetEvent<LevelDefinition>) -> bool {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                self.sequence.levels.get(self.index.0) == Some(handle)
+            }
+            AssetEvent::Removed { .. } => false,
+        }
+    }
+}
+
+fn reset_level(mut level_index: ResMut<LevelIndex>) {
+    level_index.set_if_neq(LevelIndex(0));
+}
+
+fn advance_level(
+    mut commands: Commands,
+    mut level_index: ResMut<LevelIndex>,
+    sequence: Res<LevelSequence>,
+    definitions: Res<Assets<LevelDefinition>>,
+    score: Res<Score>,
+    localizer: Localizer,
+    text_styler: TextStyler,
+) {
+    let level = sequence.get(level_index.0, &definitions);
+    let message = if score.total() < level.target_score {
+        localizer.format(
+            "level.not_cleared",
+            &[&level.target_score.to_string(), &level.name],
+        )
+    } else if level_index.0 + 1 < sequence.count() {
+        level_index.0 += 1;
+        localizer.format(
+            "level.cleared",
+            &[&level.name, &sequence.get(level_index.0, &definitions).name],
+        )
+    } else {
+        localizer.format("level.cleared_last", &[&level.name])
+    };
+    println!("{}", message);
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, -HEIGHT / 2.0 + 2.3, 1.0)
+                .with_scale(Vec3::splat(0.003)),
+            text: Text::from_section(
+                message,
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: TEXT_COLOR,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        DespawnOnExitGameOver,
+        Name::new("LevelResult"),
+    ));
+}
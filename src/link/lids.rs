@@ -0,0 +1,174 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_xpbd_2d::prelude::*;
+
+use super::{levels::CurrentLevel, spawn_level::Layer, stalls::StallState, AppState, BAD_COLOR};
+use crate::util::cleanup_system;
+
+// Launch with `--bin-lids` to play with lids on the bins.
+const BIN_LIDS_FLAG: &str = "--bin-lids";
+
+const LID_THICKNESS: f32 = 0.15;
+// How long a lid takes to slide fully open or closed.
+const LID_MOVE_SECONDS: f32 = 0.5;
+
+pub struct LidPlugin;
+
+impl Plugin for LidPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<BinLidConfig>()
+            .insert_resource(BinLidConfig {
+                enabled: std::env::args().any(|arg| arg == BIN_LIDS_FLAG),
+                ..default()
+            })
+            .add_systems(OnEnter(AppState::Playing), spawn_lids)
+            .add_systems(
+                Update,
+                (cycle_lids, move_lids)
+                    .chain()
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(in_state(StallState::Running))
+                    .run_if(resource_exists::<LidCycle>()),
+            )
+            .add_systems(
+                OnExit(AppState::Playing),
+                (cleanup_system::<BinLid>, remove_lid_cycle),
+            );
+    }
+}
+
+/// Optional lids which periodically close over the bins, forcing players to hold shapes until
+/// they reopen. Read at the start of each level.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct BinLidConfig {
+    pub enabled: bool,
+    pub open_seconds: f32,
+    pub closed_seconds: f32,
+}
+
+impl Default for BinLidConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            open_seconds: 8.0,
+            closed_seconds: 4.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct LidCycle {
+    timer: Timer,
+    closed: bool,
+    open_seconds: f32,
+    closed_seconds: f32,
+}
+
+#[derive(Component)]
+struct BinLid {
+    open_x: f32,
+    closed_x: f32,
+    // 0 when fully open, 1 when fully closed.
+    closedness: f32,
+}
+
+fn spawn_lids(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    config: Res<BinLidConfig>,
+    current_level: CurrentLevel,
+) {
+    if !config.enabled {
+        return;
+    }
+
+    commands.insert_resource(LidCycle {
+        timer: Timer::from_seconds(config.open_seconds, TimerMode::Once),
+        closed: false,
+        open_seconds: config.open_seconds,
+        closed_seconds: config.closed_seconds,
+    });
+
+    let level = current_level.get();
+    let material = materials.add(ColorMaterial::from(BAD_COLOR));
+    // Open lids are tucked away past the outer walls, sliding inward to close.
+    for (region, outward, name) in [
+        (level.left_score_region(), -1.0, "LeftBinLid"),
+        (level.right_score_region(), 1.0, "RightBinLid"),
+    ] {
+        let width = region.width();
+        let closed_x = region.center().x;
+        let open_x = closed_x + outward * width;
+        commands.spawn((
+            MaterialMesh2dBundle {
+                // Drawn behind the walls so the open lid is hidden inside them.
+                transform: Transform::from_xyz(open_x, region.max.y + LID_THICKNESS / 2.0, -0.1),
+                mesh: meshes
+                    .add(
+                        shape::Quad {
+                            size: Vec2::new(width, LID_THICKNESS),
+                            ..default()
+                        }
+                        .into(),
+                    )
+                    .into(),
+                material: material.clone(),
+                ..default()
+            },
+            RigidBody::Kinematic,
+            LinearVelocity::default(),
+            Collider::cuboid(width, LID_THICKNESS),
+            CollisionLayers::new([Layer::Level], [Layer::Rope, Layer::Shapes]),
+            BinLid {
+                open_x,
+                closed_x,
+                closedness: 0.0,
+            },
+            Name::new(name),
+        ));
+    }
+}
+
+fn remove_lid_cycle(mut commands: Commands) {
+    commands.remove_resource::<LidCycle>();
+}
+
+fn cycle_lids(mut cycle: ResMut<LidCycle>, time: Res<Time>) {
+    if !cycle.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    cycle.closed = !cycle.closed;
+    let seconds = match cycle.closed {
+        true => cycle.closed_seconds,
+        false => cycle.open_seconds,
+    };
+    cycle
+        .timer
+        .set_duration(std::time::Duration::from_secs_f32(seconds));
+    cycle.timer.reset();
+}
+
+// Lids are kinematic, so they're moved by velocity rather than teleported, letting them shove
+// anything in the way.
+fn move_lids(
+    cycle: Res<LidCycle>,
+    mut lids: Query<(&mut BinLid, &Transform, &mut LinearVelocity)>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds();
+    if dt <= 0.0 {
+        return;
+    }
+
+    for (mut lid, transform, mut velocity) in lids.iter_mut() {
+        let step = dt / LID_MOVE_SECONDS;
+        lid.closedness = match cycle.closed {
+            true => f32::min(lid.closedness + step, 1.0),
+            false => f32::max(lid.closedness - step, 0.0),
+        };
+        let target_x = lid.open_x + (lid.closed_x - lid.open_x) * lid.closedness;
+        velocity.0 = Vec2::new((target_x - transform.translation.x) / dt, 0.0);
+    }
+}
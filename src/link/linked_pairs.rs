@@ -0,0 +1,153 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_xpbd_2d::prelude::*;
+
+use super::spawn_level::Layer;
+
+const LINK_COUNT: u32 = 5;
+const LINK_THICKNESS: f32 = 0.04;
+const CHAIN_COLOR: Color = Color::rgb(0.6, 0.6, 0.65);
+// A chain snaps once it's been draped over a wall for this long, with a shape hanging down on
+// either side, each at least this far below the wall.
+const SNAP_SECONDS: f32 = 0.5;
+const SNAP_DROP: f32 = 0.1;
+
+// Now and then two shapes of different kinds are dropped tied together by a short chain, see
+// `LinkedPairs`. The chain is too strong to pull apart directly: it only snaps when it's draped
+// over the top of a wall with the shapes hanging down on either side, after which each can be
+// sorted into its own bin. If either shape goes missing the chain goes with it.
+pub struct LinkedPairPlugin;
+
+impl Plugin for LinkedPairPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (build_link_chains, snap_link_chains).chain());
+    }
+}
+
+/// Two shapes to be tied together. The chain between them is built from wherever they are when
+/// this is added, and is despawned along with it.
+#[derive(Component)]
+pub struct LinkedPair {
+    pub shapes: [Entity; 2],
+    // How long the chain has been draped over a wall.
+    draped_seconds: f32,
+}
+
+impl LinkedPair {
+    pub fn new(shapes: [Entity; 2]) -> Self {
+        Self {
+            shapes,
+            draped_seconds: 0.0,
+        }
+    }
+}
+
+// One link in the chain between a linked pair. Only walls collide with it, so it can hang over
+// them without catching on the shapes or the rope.
+#[derive(Component)]
+struct ChainLink;
+
+fn build_link_chains(
+    mut commands: Commands,
+    pairs: Query<(Entity, &LinkedPair), Added<LinkedPair>>,
+    shapes: Query<&Transform>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (pair_id, pair) in pairs.iter() {
+        let Ok([start, end]) = shapes.get_many(pair.shapes) else {
+            commands.entity(pair_id).despawn_recursive();
+            continue;
+        };
+        let (start, end) = (start.translation.truncate(), end.translation.truncate());
+        let per_link_vector = (end - start) / LINK_COUNT as f32;
+        let link_length = per_link_vector.length();
+        let rotation = Quat::from_rotation_z(per_link_vector.y.atan2(per_link_vector.x));
+        let mesh = meshes.add(
+            shape::Quad {
+                size: Vec2::new(link_length, LINK_THICKNESS),
+                ..default()
+            }
+            .into(),
+        );
+        let material = materials.add(ColorMaterial::from(CHAIN_COLOR));
+
+        // Each link hangs from the middle of the shape or link before it, and the last is tied to
+        // the middle of the second shape.
+        let mut prev_id = pair.shapes[0];
+        let mut prev_anchor = Vec2::ZERO;
+        for i in 0..LINK_COUNT {
+            let center = start + per_link_vector * (i as f32 + 0.5);
+            let link_id = commands
+                .spawn((
+                    MaterialMesh2dBundle {
+                        // Behind the shapes, which cover the chain's ends.
+                        transform: Transform::from_translation(center.extend(-0.1))
+                            .with_rotation(rotation),
+                        mesh: mesh.clone().into(),
+                        material: material.clone(),
+                        ..default()
+                    },
+                    RigidBody::Dynamic,
+                    Collider::cuboid(link_length, LINK_THICKNESS),
+                    CollisionLayers::new([Layer::Shapes], [Layer::Level]),
+                    ChainLink,
+                    Name::new(format!("Chain link {}", i)),
+                ))
+                .id();
+            let joint_id = commands
+                .spawn((
+                    RevoluteJoint::new(prev_id, link_id)
+                        .with_local_anchor_1(prev_anchor)
+                        .with_local_anchor_2(Vec2::new(-link_length / 2.0, 0.0)),
+                    Name::new(format!("Chain joint {}", i)),
+                ))
+                .id();
+            commands.entity(pair_id).push_children(&[link_id, joint_id]);
+            prev_id = link_id;
+            prev_anchor = Vec2::new(link_length / 2.0, 0.0);
+        }
+        let joint_id = commands
+            .spawn((
+                RevoluteJoint::new(prev_id, pair.shapes[1])
+                    .with_local_anchor_1(prev_anchor)
+                    .with_local_anchor_2(Vec2::ZERO),
+                Name::new(format!("Chain joint {}", LINK_COUNT)),
+            ))
+            .id();
+        commands.entity(pair_id).push_children(&[joint_id]);
+    }
+}
+
+fn snap_link_chains(
+    mut commands: Commands,
+    mut pairs: Query<(Entity, &mut LinkedPair, &Children)>,
+    shapes: Query<&Transform>,
+    links: Query<(&Transform, &CollidingEntities), With<ChainLink>>,
+    time: Res<Time>,
+) {
+    for (pair_id, mut pair, children) in pairs.iter_mut() {
+        let Ok([first, second]) = shapes.get_many(pair.shapes) else {
+            commands.entity(pair_id).despawn_recursive();
+            continue;
+        };
+        let (first, second) = (first.translation.truncate(), second.translation.truncate());
+        // Links only collide with walls, so any link touching anything is resting on one.
+        let draped = links
+            .iter_many(children)
+            .filter(|(_, colliding)| !colliding.is_empty())
+            .any(|(transform, _)| {
+                let top = transform.translation.truncate();
+                (first.x - top.x).signum() != (second.x - top.x).signum()
+                    && first.y < top.y - SNAP_DROP
+                    && second.y < top.y - SNAP_DROP
+            });
+        pair.draped_seconds = match draped {
+            true => pair.draped_seconds + time.delta_seconds(),
+            false => 0.0,
+        };
+        if pair.draped_seconds >= SNAP_SECONDS {
+            println!("A linked pair was pulled apart");
+            commands.entity(pair_id).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadState, LoadedAsset},
+    ecs::system::SystemParam,
+    prelude::*,
+    reflect::{TypePath, TypeUuid},
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+// Languages are RON files in assets/languages, named for the language's code, e.g.
+// assets/languages/en.lang.ron. See `Language` for what goes in one.
+const LANGUAGE_DIRECTORY: &str = "languages";
+const LANGUAGE_EXTENSION: &str = "lang.ron";
+// Launch with e.g. `--language=es` to play in Spanish.
+const LANGUAGE_FLAG: &str = "--language=";
+// Strings missing from the chosen language are shown in this one instead.
+const FALLBACK_LANGUAGE: &str = "en";
+
+// Player-facing text is looked up by key in the chosen language's file rather than written into the
+// code, so the game can ship in more than one language. Fixed text is tagged with a `TextKey` and
+// filled in here, again whenever a language file is edited; text put together as the game goes, like
+// the tutorial prompts, is looked up through `Localizer`.
+pub struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Language>()
+            .init_asset_loader::<LanguageLoader>()
+            .init_resource::<Locale>()
+            .add_systems(Update, (apply_text_keys, report_language));
+    }
+}
+
+/// The strings for one language by key, loaded from a .lang.ron file. In a string, each `{}` is
+/// filled in by the game, see `Localizer::format`.
+#[derive(Debug, Clone, Deserialize, TypeUuid, TypePath)]
+#[uuid = "b3a7e4d2-6c1f-4e8a-9f25-3d8c0b7a1e64"]
+pub struct Language {
+    /// The language's name, in that language.
+    pub name: String,
+    pub strings: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct LanguageLoader;
+
+impl AssetLoader for LanguageLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let language: Language = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(language));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[LANGUAGE_EXTENSION]
+    }
+}
+
+/// The language the game is shown in, picked at launch.
+#[derive(Resource)]
+pub struct Locale {
+    /// The chosen language's code, e.g. "en".
+    pub code: String,
+    chosen: Handle<Language>,
+    fallback: Handle<Language>,
+}
+
+impl FromWorld for Locale {
+    fn from_world(world: &mut World) -> Self {
+        let path = |code: &str| format!("{}/{}.{}", LANGUAGE_DIRECTORY, code, LANGUAGE_EXTENSION);
+        let code = std::env::args()
+            .find_map(|arg| arg.strip_prefix(LANGUAGE_FLAG).map(str::to_owned))
+            .unwrap_or_else(|| FALLBACK_LANGUAGE.to_owned());
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            chosen: asset_server.load(path(&code)),
+            fallback: asset_server.load(path(FALLBACK_LANGUAGE)),
+            code,
+        }
+    }
+}
+
+/// Shows the string with this key, in the text's first section.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct TextKey(pub &'static str);
+
+/// Looks up strings in the chosen language.
+#[derive(SystemParam)]
+pub struct Localizer<'w> {
+    locale: Res<'w, Locale>,
+    languages: Res<'w, Assets<Language>>,
+}
+
+impl<'w> Localizer<'w> {
+    /// The string for `key`, or the key itself if no language has it, so it stands out as missing.
+    /// Shown as the key until the language files have loaded, too.
+    pub fn get(&self, key: &str) -> String {
+        [&self.locale.chosen, &self.locale.fallback]
+            .into_iter()
+            .filter_map(|handle| self.languages.get(handle))
+            .find_map(|language| language.strings.get(key))
+            .cloned()
+            .unwrap_or_else(|| key.to_owned())
+    }
+
+    /// The string for `key`, with each `{}` in it replaced by the next of `args`.
+    pub fn format(&self, key: &str, args: &[&str]) -> String {
+        let template = self.get(key);
+        let mut pieces = template.split("{}");
+        let mut formatted = pieces.next().unwrap_or_default().to_owned();
+        for (piece, arg) in pieces.zip(args.iter().chain(std::iter::repeat(&""))) {
+            formatted.push_str(arg);
+            formatted.push_str(piece);
+        }
+        formatted
+    }
+}
+
+// Keyed text is filled in as it's spawned, and all of it again whenever a language file loads or
+// changes.
+fn apply_text_keys(
+    localizer: Localizer,
+    mut language_events: EventReader<AssetEvent<Language>>,
+    mut texts: Query<(Ref<TextKey>, &mut Text)>,
+) {
+    let reloaded = language_events.iter().count() > 0;
+    for (key, mut text) in texts.iter_mut() {
+        if reloaded || key.is_changed() {
+            text.sections[0].value = localizer.get(key.0);
+        }
+    }
+}
+
+// A language with no file only shows up as a failed load, by which point `Localizer` is already
+// showing the fallback language's strings in its place.
+fn report_language(
+    locale: Res<Locale>,
+    languages: Res<Assets<Language>>,
+    asset_server: Res<AssetServer>,
+    mut reported: Local<bool>,
+) {
+    if *reported {
+        return;
+    }
+    if let Some(language) = languages.get(&locale.chosen) {
+        println!("Language: {}", language.name);
+        *reported = true;
+    } else if asset_server.get_load_state(&locale.chosen) == LoadState::Failed {
+        println!(
+            "No language file for '{}', falling back to '{}'",
+            locale.code, FALLBACK_LANGUAGE
+        );
+        *reported = true;
+    }
+}
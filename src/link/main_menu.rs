@@ -0,0 +1,202 @@
+use bevy::{app::AppExit, prelude::*};
+
+use super::{
+    locale::TextKey,
+    player::{AttachState, Cursor},
+    text_style::TextStyler,
+    AppState, DespawnOnExitInit, TEXT_COLOR,
+};
+use crate::mischief::{MischiefEvent, MischiefEventData};
+
+const BUTTON_SIZE: Vec2 = Vec2::new(2.2, 0.6);
+// From the center of one button to the next.
+const BUTTON_SPACING: f32 = 2.5;
+const MENU_Y: f32 = -0.4;
+const BUTTON_ALPHA: f32 = 0.15;
+const HOVERED_BUTTON_ALPHA: f32 = 0.4;
+// Clicks are ignored for this long after the title screen comes up, so the click that ended the
+// last screen doesn't also press a button.
+const MENU_GRACE_SECONDS: f32 = 0.5;
+
+// A row of buttons across the title screen, pressed by moving a cursor over one and clicking the
+// mouse driving it. Only claimed cursors can press them, so the clicks that claim the mice don't.
+// Playing from here skips whatever's left of the tutorial.
+pub struct MainMenuPlugin;
+
+impl Plugin for MainMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Init), spawn_main_menu)
+            .add_systems(
+                Update,
+                (
+                    highlight_buttons,
+                    press_buttons.run_if(in_state(AttachState::Attached)),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Init)),
+            )
+            .add_systems(OnExit(AppState::Init), end_main_menu);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MenuEntry {
+    Play,
+    ModeSelect,
+    Settings,
+    Leaderboard,
+    Quit,
+}
+
+impl MenuEntry {
+    const ALL: [MenuEntry; 5] = [
+        MenuEntry::Play,
+        MenuEntry::ModeSelect,
+        MenuEntry::Settings,
+        MenuEntry::Leaderboard,
+        MenuEntry::Quit,
+    ];
+
+    fn text_key(self) -> &'static str {
+        match self {
+            MenuEntry::Play => "main_menu.play",
+            MenuEntry::ModeSelect => "main_menu.mode_select",
+            MenuEntry::Settings => "main_menu.settings",
+            MenuEntry::Leaderboard => "main_menu.leaderboard",
+            MenuEntry::Quit => "main_menu.quit",
+        }
+    }
+}
+
+#[derive(Resource)]
+struct MainMenuGrace(Timer);
+
+#[derive(Component)]
+struct MenuButton(MenuEntry);
+
+fn spawn_main_menu(mut commands: Commands, text_styler: TextStyler) {
+    commands.insert_resource(MainMenuGrace(Timer::from_seconds(
+        MENU_GRACE_SECONDS,
+        TimerMode::Once,
+    )));
+    let first_x = -BUTTON_SPACING * (MenuEntry::ALL.len() - 1) as f32 / 2.0;
+    for (index, entry) in MenuEntry::ALL.into_iter().enumerate() {
+        let x = first_x + BUTTON_SPACING * index as f32;
+        commands
+            .spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: TEXT_COLOR.with_a(BUTTON_ALPHA),
+                        custom_size: Some(BUTTON_SIZE),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(x, MENU_Y, 1.0),
+                    ..default()
+                },
+                MenuButton(entry),
+                DespawnOnExitInit,
+                Name::new(format!("MenuButton ({:?})", entry)),
+            ))
+            .with_children(|button| {
+                button.spawn((
+                    Text2dBundle {
+                        transform: Transform::from_xyz(0.0, 0.0, 0.1)
+                            .with_scale(Vec3::splat(0.003)),
+                        text: Text::from_section(
+                            "",
+                            TextStyle {
+                                font: text_styler.font(),
+                                font_size: 100.0,
+                                color: TEXT_COLOR,
+                            },
+                        )
+                        .with_alignment(TextAlignment::Center),
+                        ..default()
+                    },
+                    TextKey(entry.text_key()),
+                ));
+            });
+    }
+}
+
+fn end_main_menu(mut commands: Commands) {
+    commands.remove_resource::<MainMenuGrace>();
+}
+
+fn button_under<'a>(
+    point: Vec2,
+    buttons: impl IntoIterator<Item = (&'a MenuButton, &'a Transform)>,
+) -> Option<MenuEntry> {
+    buttons
+        .into_iter()
+        .find(|(_, transform)| {
+            Rect::from_center_size(transform.translation.truncate(), BUTTON_SIZE).contains(point)
+        })
+        .map(|(button, _)| button.0)
+}
+
+fn highlight_buttons(
+    cursors: Query<(&Cursor, &Transform)>,
+    mut buttons: Query<(&MenuButton, &Transform, &mut Sprite)>,
+) {
+    let hovered = cursors
+        .iter()
+        .filter(|(cursor, _)| cursor.0.is_some())
+        .filter_map(|(_, transform)| {
+            let point = transform.translation.truncate();
+            button_under(
+                point,
+                buttons
+                    .iter()
+                    .map(|(button, transform, _)| (button, transform)),
+            )
+        })
+        .collect::<Vec<_>>();
+    for (button, _, mut sprite) in buttons.iter_mut() {
+        let alpha = match hovered.contains(&button.0) {
+            true => HOVERED_BUTTON_ALPHA,
+            false => BUTTON_ALPHA,
+        };
+        if sprite.color.a() != alpha {
+            sprite.color.set_a(alpha);
+        }
+    }
+}
+
+// A click counts for whichever button is under the cursor driven by the mouse that clicked.
+fn press_buttons(
+    mut grace: ResMut<MainMenuGrace>,
+    mut mischief_events: EventReader<MischiefEvent>,
+    cursors: Query<(&Cursor, &Transform)>,
+    buttons: Query<(&MenuButton, &Transform)>,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut exit: EventWriter<AppExit>,
+    time: Res<Time>,
+) {
+    let ready = grace.0.tick(time.delta()).finished();
+    for event in mischief_events.iter() {
+        let MischiefEventData::Button { pressed: true, .. } = event.event_data else {
+            continue;
+        };
+        if !ready {
+            continue;
+        }
+        let Some((_, transform)) = cursors
+            .iter()
+            .find(|(cursor, _)| cursor.0 == Some(event.device))
+        else {
+            continue;
+        };
+        let Some(entry) = button_under(transform.translation.truncate(), buttons.iter()) else {
+            continue;
+        };
+        match entry {
+            MenuEntry::Play => app_state.set(AppState::Warmup),
+            MenuEntry::ModeSelect => app_state.set(AppState::ModeSelect),
+            MenuEntry::Settings => app_state.set(AppState::Settings),
+            MenuEntry::Leaderboard => app_state.set(AppState::Leaderboard),
+            MenuEntry::Quit => exit.send(AppExit),
+        }
+        return;
+    }
+}
@@ -0,0 +1,123 @@
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use super::{
+    locale::TextKey,
+    player::{claim_cursor, move_cursors, AttachState, Cursor, CursorSlot, TargetVelocity},
+    text_style::TextStyler,
+    title_hints::TitleHint,
+    AppState, DespawnOnExitInit, TEXT_COLOR,
+};
+use crate::mischief::{MischiefDevices, MIRROR_DEVICE};
+
+// The mirrored cursor drifts from its reflection as the rope tugs the two ends differently, and is
+// pulled back towards it at a speed that would close the gap in this long.
+const MIRROR_CATCHUP_SECONDS: f32 = 0.2;
+
+// Practice for a player on their own: one mouse drives both ends of the rope, the second cursor
+// mirroring the first across the center line. A way to get a feel for the rope before finding a
+// second mouse or a second player.
+pub struct MirrorPlugin;
+
+impl Plugin for MirrorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Init), spawn_mirror_hint)
+            .add_systems(
+                Update,
+                (
+                    claim_mirrored_cursor
+                        .run_if(in_state(AttachState::Waiting))
+                        .run_if(input_just_pressed(KeyCode::T))
+                        .run_if(single_mouse),
+                    show_mirror_hint,
+                )
+                    .run_if(in_state(AppState::Init)),
+            )
+            .add_systems(Update, drive_mirrored_cursor.after(move_cursors));
+    }
+}
+
+#[derive(Component)]
+struct MirrorHint;
+
+fn single_mouse(devices: Res<MischiefDevices>) -> bool {
+    devices.connected().count() == 1
+}
+
+fn spawn_mirror_hint(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: TitleHint::Mirror.transform(),
+            text: Text {
+                sections: vec![TextSection::new(
+                    "",
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: TEXT_COLOR,
+                    },
+                )],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        MirrorHint,
+        TextKey("title.mirror_hint"),
+        DespawnOnExitInit,
+        Name::new("MirrorHint"),
+    ));
+}
+
+fn show_mirror_hint(
+    devices: Res<MischiefDevices>,
+    attach_state: Res<State<AttachState>>,
+    mut hints: Query<&mut Visibility, With<MirrorHint>>,
+) {
+    let shown = single_mouse(devices) && attach_state.get() == &AttachState::Waiting;
+    for mut visibility in hints.iter_mut() {
+        *visibility = match shown {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+    }
+}
+
+// Like the keyboard, the mirror takes whichever end of the rope the mouse hasn't claimed.
+fn claim_mirrored_cursor(mut cursors: Query<(&CursorSlot, &mut Cursor)>) {
+    if claim_cursor(&mut cursors, Some(CursorSlot::RIGHT), MIRROR_DEVICE).is_none() {
+        claim_cursor(&mut cursors, Some(CursorSlot::LEFT), MIRROR_DEVICE);
+    }
+}
+
+fn drive_mirrored_cursor(
+    mut cursors: Query<(&CursorSlot, &Cursor, &Transform, &mut TargetVelocity)>,
+) {
+    let Some(mirrored_slot) = cursors
+        .iter()
+        .find(|(_, cursor, _, _)| cursor.0 == Some(MIRROR_DEVICE))
+        .map(|(slot, _, _, _)| *slot)
+    else {
+        return;
+    };
+    let leader_slot = match mirrored_slot {
+        CursorSlot::LEFT => CursorSlot::RIGHT,
+        _ => CursorSlot::LEFT,
+    };
+    let Some((leader_position, leader_velocity)) = cursors
+        .iter()
+        .find(|(slot, _, _, _)| **slot == leader_slot)
+        .map(|(_, _, transform, velocity)| (transform.translation.truncate(), velocity.0))
+    else {
+        return;
+    };
+
+    let reflect = |v: Vec2| Vec2::new(-v.x, v.y);
+    for (slot, _, transform, mut target_velocity) in cursors.iter_mut() {
+        if *slot != mirrored_slot {
+            continue;
+        }
+        let drift = reflect(leader_position) - transform.translation.truncate();
+        target_velocity.0 = reflect(leader_velocity) + drift / MIRROR_CATCHUP_SECONDS;
+    }
+}
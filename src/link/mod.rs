@@ -1,17 +1,144 @@
 use crate::mischief::{MischiefEvent, MischiefEventData};
 use crate::util::cleanup_system;
+use aiming::AimingGuidePlugin;
+use background::BackgroundPlugin;
 use bevy::{
     core_pipeline::clear_color::ClearColorConfig, input::common_conditions::input_just_pressed,
-    prelude::*, window::WindowResolution,
+    prelude::*, render::camera::ScalingMode,
 };
 use bevy_xpbd_2d::prelude::*;
+use bin_magnets::BinMagnetPlugin;
+use bin_swaps::BinSwapPlugin;
+use calibration::CalibrationPlugin;
+use checksum::ChecksumPlugin;
+use combo::ComboPlugin;
+use cursor_control::CursorControlPlugin;
+use cursor_physics::CursorPhysicsPlugin;
+use cursor_skins::CursorSkinPlugin;
+use device_select::DeviceSelectPlugin;
+use disconnect::DisconnectPlugin;
+use double_rope::DoubleRopePlugin;
+use elastic_rope::ElasticRopePlugin;
+use force_display::ForceDisplayPlugin;
+use frenzy::FrenzyPlugin;
+use gain_tuning::GainTuningPlugin;
+use game_mode::GameModePlugin;
+use gamepad::GamepadCursorPlugin;
 use gameplay::GameplayPlugin;
-use player::{AttachState, PlayerPlugin};
-use spawn_level::{SpawnPlugin, SpawnState};
+use grab::GrabPlugin;
+use gravity_phases::GravityPhasePlugin;
+use hazards::HazardPlugin;
+use heatmap::HeatmapPlugin;
+use impact_flash::ImpactFlashPlugin;
+use input_error::InputErrorPlugin;
+use intensity_meter::IntensityMeterPlugin;
+use keyboard::KeyboardCursorPlugin;
+use kiosk::KioskPlugin;
+use leaderboard::{LeaderboardPlugin, LeaderboardState};
+use levels::LevelsPlugin;
+use lids::LidPlugin;
+use linked_pairs::LinkedPairPlugin;
+use locale::LocalePlugin;
+use main_menu::MainMenuPlugin;
+use mirror::MirrorPlugin;
+use mouse_diagrams::MouseDiagramPlugin;
+use mutators::MutatorsPlugin;
+use pairing::PairingPlugin;
+use palette::PalettePlugin;
+use party::PartyPlugin;
+use player::PlayerPlugin;
+use popups::PopupPlugin;
+use power_ups::PowerUpPlugin;
+use quit::QuitPlugin;
+use rope_cutting::RopeCuttingPlugin;
+use rope_physics::RopePhysicsPlugin;
+use rope_skins::RopeSkinPlugin;
+use rope_slack::RopeSlackPlugin;
+use rope_tension::RopeTensionPlugin;
+use screensaver::ScreensaverPlugin;
+use settings::SettingsPlugin;
+use shape_progress::ShapeProgressPlugin;
+use spawn_level::{SpawnPlugin, HEIGHT, WIDTH};
+use stalls::StallPlugin;
+use stats::StatsPlugin;
+use stiffen::StiffenPlugin;
+use swap::SwapPlugin;
+use text_style::TextStylePlugin;
+use trails::TrailPlugin;
+use tuning::TuningPlugin;
+use tunneling::TunnelingPlugin;
+use tutorial::TutorialPlugin;
+use upgrades::UpgradesPlugin;
+use versus::VersusPlugin;
+use warmup::WarmupPlugin;
 
+mod aiming;
+mod background;
+mod bin_magnets;
+mod bin_swaps;
+mod calibration;
+mod checksum;
+mod combo;
+mod cursor_control;
+mod cursor_physics;
+mod cursor_skins;
+mod device_select;
+mod disconnect;
+mod double_rope;
+mod elastic_rope;
+mod force_display;
+mod frenzy;
+mod gain_tuning;
+mod game_mode;
+mod gamepad;
 mod gameplay;
+mod grab;
+mod gravity_phases;
+mod hazards;
+mod heatmap;
+mod impact_flash;
+mod input_error;
+mod intensity_meter;
+mod keyboard;
+mod kiosk;
+mod leaderboard;
+mod levels;
+mod lids;
+mod linked_pairs;
+mod locale;
+mod main_menu;
+mod mirror;
+mod mouse_diagrams;
+mod mutators;
+mod pairing;
+mod palette;
+mod party;
 mod player;
+mod popups;
+mod power_ups;
+mod quit;
+mod rope_cutting;
+mod rope_physics;
+mod rope_skins;
+mod rope_slack;
+mod rope_tension;
+mod screensaver;
+mod settings;
+mod shape_progress;
 mod spawn_level;
+mod stalls;
+mod stats;
+mod stiffen;
+mod swap;
+mod text_style;
+mod title_hints;
+mod trails;
+mod tuning;
+mod tunneling;
+mod tutorial;
+mod upgrades;
+mod versus;
+mod warmup;
 
 // MVP brief features:
 
@@ -31,24 +158,18 @@ mod spawn_level;
 // Round the rest of the corners on the right side of the level.
 // Visual polish on the level shapes.
 // Add drop shadows to shapes and cursor/chain.
-// Improve the game over screen layout.
-// Add left and right mouse button images to the title/setup screen.
 
 // Done polish:
 // Differentiate left vs right cursors visually. (done)
 // Pick a nicer color palette and recolor everything with it. (done)
 // Add a title screen shown during AppState::Init. (done)
 // Add game over screen shown during AppState::GameOver. (done)
+// Improve the game over screen layout. (done)
+// Add left and right mouse button images to the title/setup screen. (done)
 // Increase intensity over time. (done)
 // Two shape patterns (sequence and shotgun). (done)
 
-// Bugs:
-// - Window resolution doesn't seem to be working as I expect it to.
-
-const PIXELS_PER_METER: f32 = 100.0;
 pub const BACKGROUND_COLOR: Color = Color::rgb(64.0 / 255.0, 67.0 / 255.0, 78.0 / 255.0);
-pub const LEFT_COLOR: Color = Color::rgb(17.0 / 255.0, 159.0 / 255.0, 166.0 / 255.0);
-pub const RIGHT_COLOR: Color = Color::rgb(226.0 / 255.0, 101.0 / 255.0, 60.0 / 255.0);
 pub const TEXT_COLOR: Color = Color::rgb(215.0 / 255.0, 217.0 / 255.0, 206.0 / 255.0);
 pub const BAD_COLOR: Color = Color::rgb(229.0 / 255.0, 39.0 / 255.0, 36.0 / 255.0);
 
@@ -56,22 +177,82 @@ pub struct LinkPlugin;
 
 impl Plugin for LinkPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(PlayerPlugin)
+        app.add_plugins(TextStylePlugin)
+            .add_plugins(PlayerPlugin)
             .add_plugins(SpawnPlugin)
             .add_plugins(GameplayPlugin)
+            .add_plugins(StatsPlugin)
+            .add_plugins(QuitPlugin)
+            .add_plugins(BackgroundPlugin)
+            .add_plugins(HazardPlugin)
+            .add_plugins(WarmupPlugin)
+            .add_plugins(LidPlugin)
+            .add_plugins(AimingGuidePlugin)
+            .add_plugins(PairingPlugin)
+            .add_plugins(HeatmapPlugin)
+            .add_plugins(DeviceSelectPlugin)
+            .add_plugins(StallPlugin)
+            .add_plugins(ChecksumPlugin)
+            .add_plugins(TuningPlugin)
+            .add_plugins(KeyboardCursorPlugin)
+            .add_plugins(KioskPlugin)
+            .add_plugins(GamepadCursorPlugin)
+            .add_plugins(DisconnectPlugin)
+            .add_plugins(PartyPlugin)
+            .add_plugins(ScreensaverPlugin)
+            .add_plugins(InputErrorPlugin)
+            .add_plugins(CalibrationPlugin)
+            .add_plugins(GainTuningPlugin)
+            .add_plugins(SwapPlugin)
+            .add_plugins(TrailPlugin)
+            .add_plugins(CursorSkinPlugin)
+            .add_plugins(CursorPhysicsPlugin)
+            .add_plugins(TunnelingPlugin)
+            .add_plugins(CursorControlPlugin)
+            .add_plugins(GrabPlugin)
+            .add_plugins(ForceDisplayPlugin)
+            .add_plugins(ImpactFlashPlugin)
+            .add_plugins(MirrorPlugin)
+            .add_plugins(RopeTensionPlugin)
+            .add_plugins(MutatorsPlugin)
+            .add_plugins(ElasticRopePlugin)
+            .add_plugins(RopeCuttingPlugin)
+            .add_plugins(RopeSkinPlugin)
+            .add_plugins(StiffenPlugin)
+            .add_plugins(RopePhysicsPlugin)
+            .add_plugins(RopeSlackPlugin)
+            .add_plugins(DoubleRopePlugin)
+            .add_plugins(UpgradesPlugin)
+            .add_plugins(BinMagnetPlugin)
+            .add_plugins(GameModePlugin)
+            .add_plugins(ComboPlugin)
+            .add_plugins(FrenzyPlugin)
+            .add_plugins(LeaderboardPlugin)
+            .add_plugins(LevelsPlugin)
+            .add_plugins(GravityPhasePlugin)
+            .add_plugins(PowerUpPlugin)
+            .add_plugins(TutorialPlugin)
+            .add_plugins(BinSwapPlugin)
+            .add_plugins(LinkedPairPlugin)
+            .add_plugins(PopupPlugin)
+            .add_plugins(VersusPlugin)
+            .add_plugins(SettingsPlugin)
+            .add_plugins(MainMenuPlugin)
+            .add_plugins(ShapeProgressPlugin)
+            .add_plugins(IntensityMeterPlugin)
+            .add_plugins(MouseDiagramPlugin)
+            .add_plugins(PalettePlugin)
+            .add_plugins(LocalePlugin)
             .add_plugins(PhysicsPlugins::new(FixedUpdate))
             .insert_resource(SubstepCount(20))
+            .register_type::<Difficulty>()
+            .init_resource::<Difficulty>()
             .add_systems(
                 Update,
                 toggle_os_cursor.run_if(input_just_pressed(KeyCode::Grave)),
             )
-            .add_systems(
-                Startup,
-                (size_window, spawn_camera, toggle_os_cursor).chain(),
-            )
-            .add_systems(Update, bevy::window::close_on_esc)
+            .add_systems(Startup, (spawn_camera, toggle_os_cursor).chain())
             .add_state::<AppState>()
-            .add_systems(Update, start_playing.run_if(in_state(AppState::Init)))
             .add_systems(OnExit(AppState::Init), cleanup_system::<DespawnOnExitInit>)
             .add_systems(Update, start_new_game.run_if(in_state(AppState::GameOver)))
             .add_systems(
@@ -81,14 +262,6 @@ impl Plugin for LinkPlugin {
     }
 }
 
-fn size_window(mut windows: Query<&mut Window>) {
-    let mut window = windows.single_mut();
-    let scale_factor = window.scale_factor() as f32;
-    window.resolution = WindowResolution::new(1600.0 * scale_factor, 900.0 * scale_factor)
-        .with_scale_factor_override(scale_factor as f64);
-    window.position.center(MonitorSelection::Current);
-}
-
 fn toggle_os_cursor(mut windows: Query<&mut Window>) {
     let mut window = windows.single_mut();
     let window_center = Vec2::new(window.width() / 2.0, window.height() / 2.0);
@@ -100,12 +273,16 @@ fn toggle_os_cursor(mut windows: Query<&mut Window>) {
     };
 }
 
+// The whole arena stays in view at any resolution or window shape, see `Settings::resolution`.
 fn spawn_camera(mut commands: Commands) {
     commands.spawn(Camera2dBundle {
         projection: OrthographicProjection {
             far: 1000.,
             near: -1000.,
-            scale: 1.0 / PIXELS_PER_METER,
+            scaling_mode: ScalingMode::AutoMin {
+                min_width: WIDTH,
+                min_height: HEIGHT,
+            },
             ..default()
         },
         camera_2d: Camera2d {
@@ -115,35 +292,58 @@ fn spawn_camera(mut commands: Commands) {
     });
 }
 
+#[derive(Resource, Reflect, Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[reflect(Resource)]
+pub enum Difficulty {
+    #[default]
+    Normal,
+    Hard,
+}
+
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
 pub enum AppState {
     #[default]
     Init,
+    Warmup,
     Playing,
     GameOver,
+    /// Dev mode for tuning spawn pacing, see `TuningPlugin`.
+    Tuning,
+    /// ManyMouse failed to start, see `InputErrorPlugin`.
+    InputError,
+    /// Measuring each mouse's resolution, see `CalibrationPlugin`.
+    Calibrating,
+    /// Deriving the cursors' PID gains from step tests, see `GainTuningPlugin`.
+    GainTuning,
+    /// Choosing optional changes to the game, see `MutatorsPlugin`.
+    Mutators,
+    /// Choosing how the rope looks, see `RopeSkinPlugin`.
+    RopeSkins,
+    /// Choosing how the next game is played, see `GameModePlugin`.
+    ModeSelect,
+    /// Changing the players' preferences, see `SettingsPlugin`.
+    Settings,
+    /// Looking over the best scores from the main menu, see `LeaderboardPlugin`.
+    Leaderboard,
 }
 
-fn start_playing(
-    spawn_state: Res<State<SpawnState>>,
-    attach_state: Res<State<AttachState>>,
-    mut app_state: ResMut<NextState<AppState>>,
-) {
-    if spawn_state.get() == &SpawnState::Done && attach_state.get() == &AttachState::Attached {
-        app_state.set(AppState::Playing);
-    }
-}
-
+// Clicks while initials are being entered are read all the same, so they don't start a game later.
 fn start_new_game(
     mut app_state: ResMut<NextState<AppState>>,
+    leaderboard_state: Res<State<LeaderboardState>>,
     mut mischief_events: EventReader<MischiefEvent>,
 ) {
+    let entering_initials = leaderboard_state.get() == &LeaderboardState::EnteringInitials;
     for event in mischief_events.iter() {
+        if entering_initials {
+            continue;
+        }
         if let MischiefEventData::Button {
             button: _,
             pressed: true,
         } = event.event_data
         {
-            app_state.set(AppState::Playing);
+            app_state.set(AppState::ModeSelect);
         }
     }
 }
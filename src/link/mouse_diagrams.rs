@@ -0,0 +1,187 @@
+use bevy::prelude::*;
+
+use super::{
+    locale::TextKey,
+    palette::Palette,
+    player::{Cursor, CursorSlot},
+    text_style::TextStyler,
+    tutorial::TutorialStep,
+    AppState, DespawnOnExitInit, TEXT_COLOR,
+};
+
+// The diagram is drawn in layers from images in assets/mouse: the outline of the mouse, and each of
+// its buttons. White parts of the images take the diagram's colors.
+const MOUSE_DIRECTORY: &str = "mouse";
+const DIAGRAM_SIZE: Vec2 = Vec2::new(0.64, 0.96);
+// Either side of the tutorial prompt.
+const DIAGRAM_X: f32 = 5.5;
+const DIAGRAM_Y: f32 = 2.0;
+// The button to click pulses this many times a second until it's been clicked.
+const PULSE_RATE: f32 = 1.5;
+const IDLE_BUTTON_ALPHA: f32 = 0.2;
+// A claimed mouse sways from side to side, as a hint to start moving it.
+const SWAY_DISTANCE: f32 = 0.15;
+const SWAY_RATE: f32 = 0.8;
+const SWAY_TILT: f32 = 0.15;
+
+// Shows a mouse on each side of the title screen while players claim theirs, in place of a written
+// prompt, with the button that claims that side's cursor lit up in its color: the left button for
+// the left cursor, the right button for the right one. Once a mouse is claimed its diagram fills in
+// and starts to move. Players past the second claim cursors along the middle of the rope with
+// either button, so they don't get a diagram of their own.
+pub struct MouseDiagramPlugin;
+
+impl Plugin for MouseDiagramPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Init), spawn_mouse_diagrams)
+            .add_systems(
+                Update,
+                animate_mouse_diagrams.run_if(in_state(AppState::Init)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct MouseDiagram(CursorSlot);
+
+#[derive(Component)]
+struct MouseBody;
+
+// The button which claims the diagram's cursor.
+#[derive(Component)]
+struct ClaimButton;
+
+fn spawn_mouse_diagrams(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    palette: Res<Palette>,
+    text_styler: TextStyler,
+) {
+    let image = |name: &str| asset_server.load(format!("{}/{}.png", MOUSE_DIRECTORY, name));
+    let body: Handle<Image> = image("body");
+    let left_button: Handle<Image> = image("left_button");
+    let right_button: Handle<Image> = image("right_button");
+
+    for slot in [CursorSlot::LEFT, CursorSlot::RIGHT] {
+        let (claim_button, other_button) = match slot {
+            CursorSlot::LEFT => (left_button.clone(), right_button.clone()),
+            _ => (right_button.clone(), left_button.clone()),
+        };
+        let sprite = |color: Color| Sprite {
+            color,
+            custom_size: Some(DIAGRAM_SIZE),
+            ..default()
+        };
+        commands
+            .spawn((
+                SpatialBundle {
+                    transform: Transform::from_xyz(diagram_x(slot), DIAGRAM_Y, 1.0),
+                    ..default()
+                },
+                MouseDiagram(slot),
+                DespawnOnExitInit,
+                Name::new(format!("MouseDiagram ({})", slot.label())),
+            ))
+            .with_children(|diagram| {
+                diagram.spawn((
+                    SpriteBundle {
+                        sprite: sprite(TEXT_COLOR),
+                        texture: body.clone(),
+                        ..default()
+                    },
+                    MouseBody,
+                ));
+                diagram.spawn(SpriteBundle {
+                    sprite: sprite(TEXT_COLOR.with_a(IDLE_BUTTON_ALPHA)),
+                    texture: other_button,
+                    transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                    ..default()
+                });
+                diagram.spawn((
+                    SpriteBundle {
+                        sprite: sprite(palette.slot(slot)),
+                        texture: claim_button,
+                        transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                        ..default()
+                    },
+                    ClaimButton,
+                ));
+                diagram.spawn((
+                    Text2dBundle {
+                        transform: Transform::from_xyz(0.0, -DIAGRAM_SIZE.y / 2.0 - 0.2, 0.1)
+                            .with_scale(Vec3::splat(0.002)),
+                        text: Text::from_section(
+                            "",
+                            TextStyle {
+                                font: text_styler.font(),
+                                font_size: 100.0,
+                                color: palette.slot(slot),
+                            },
+                        )
+                        .with_alignment(TextAlignment::Center),
+                        ..default()
+                    },
+                    TextKey(match slot {
+                        CursorSlot::LEFT => "title.left_player",
+                        _ => "title.right_player",
+                    }),
+                ));
+            });
+    }
+}
+
+fn diagram_x(slot: CursorSlot) -> f32 {
+    match slot {
+        CursorSlot::LEFT => -DIAGRAM_X,
+        _ => DIAGRAM_X,
+    }
+}
+
+// The diagrams stay up while the players first move their cursors, and are hidden once the
+// practice shape drops.
+fn animate_mouse_diagrams(
+    step: Res<State<TutorialStep>>,
+    cursors: Query<(&CursorSlot, &Cursor)>,
+    palette: Res<Palette>,
+    mut diagrams: Query<(&MouseDiagram, &Children, &mut Transform, &mut Visibility)>,
+    mut bodies: Query<&mut Sprite, (With<MouseBody>, Without<ClaimButton>)>,
+    mut buttons: Query<&mut Sprite, (With<ClaimButton>, Without<MouseBody>)>,
+    time: Res<Time>,
+) {
+    let shown = matches!(
+        step.get(),
+        TutorialStep::ClaimMice | TutorialStep::ReachTargets
+    );
+    let seconds = time.elapsed_seconds();
+    for (MouseDiagram(slot), children, mut transform, mut visibility) in diagrams.iter_mut() {
+        *visibility = match shown {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+        let claimed = cursors
+            .iter()
+            .any(|(cursor_slot, cursor)| cursor_slot == slot && cursor.0.is_some());
+
+        let (sway, body_color, button_alpha) = match claimed {
+            true => (
+                (seconds * SWAY_RATE * std::f32::consts::TAU).sin(),
+                palette.slot(*slot),
+                1.0,
+            ),
+            false => {
+                let pulse = (seconds * PULSE_RATE * std::f32::consts::TAU).sin() * 0.5 + 0.5;
+                (0.0, TEXT_COLOR, 0.4 + 0.6 * pulse)
+            }
+        };
+        transform.translation.x = diagram_x(*slot) + SWAY_DISTANCE * sway;
+        transform.rotation = Quat::from_rotation_z(-SWAY_TILT * sway);
+        for child in children.iter() {
+            if let Ok(mut sprite) = bodies.get_mut(*child) {
+                sprite.color = body_color;
+            }
+            if let Ok(mut sprite) = buttons.get_mut(*child) {
+                sprite.color.set_a(button_alpha);
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use super::{
+    locale::{Localizer, TextKey},
+    stats::LifetimeStats,
+    text_style::TextStyler,
+    title_hints::TitleHint,
+    AppState, DespawnOnExitInit, TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+// Each press of [ or ] scales the elastic rope's stiffness by this much.
+const STIFFNESS_STEP: f32 = 1.5;
+const MIN_STIFFNESS: f32 = 100.0;
+const MAX_STIFFNESS: f32 = 100_000.0;
+// The net in the middle of the rope is an assist, earned by sorting this many shapes in total.
+const NET_UNLOCK_SHAPES: u32 = 100;
+
+// Press O on the title screen for a menu of mutators: optional changes to how the game plays,
+// which take effect right away and last until they're turned off again.
+pub struct MutatorsPlugin;
+
+impl Plugin for MutatorsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Mutators>()
+            .init_resource::<Mutators>()
+            .add_systems(OnEnter(AppState::Init), spawn_mutators_hint)
+            .add_systems(
+                Update,
+                toggle_mutators_menu
+                    .run_if(input_just_pressed(KeyCode::O))
+                    .run_if(in_state(AppState::Init).or_else(in_state(AppState::Mutators))),
+            )
+            .add_systems(OnEnter(AppState::Mutators), spawn_mutators_menu)
+            .add_systems(
+                Update,
+                (edit_mutators, display_mutators)
+                    .chain()
+                    .run_if(in_state(AppState::Mutators)),
+            )
+            .add_systems(OnExit(AppState::Mutators), cleanup_system::<MutatorsMenu>);
+    }
+}
+
+#[derive(Resource, Reflect, Debug, Clone)]
+#[reflect(Resource)]
+pub struct Mutators {
+    /// Join the rope's segments with springs rather than rigid links, see `ElasticRopePlugin`.
+    pub elastic_rope: bool,
+    /// How hard an elastic rope's springs pull its segments back together, in newtons per meter.
+    pub rope_stiffness: f32,
+    /// Hang a small net from the middle of the rope, which can cradle a shape. Only available once
+    /// unlocked, see `NET_UNLOCK_SHAPES`.
+    pub rope_net: bool,
+}
+
+impl Default for Mutators {
+    fn default() -> Self {
+        Self {
+            elastic_rope: false,
+            rope_stiffness: 2000.0,
+            rope_net: false,
+        }
+    }
+}
+
+#[derive(Component)]
+struct MutatorsMenu;
+
+#[derive(Component)]
+struct MutatorsList;
+
+fn toggle_mutators_menu(state: Res<State<AppState>>, mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(match state.get() {
+        AppState::Mutators => AppState::Init,
+        _ => AppState::Mutators,
+    });
+}
+
+fn spawn_mutators_hint(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: TitleHint::Mutators.transform(),
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: TEXT_COLOR,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        DespawnOnExitInit,
+        TextKey("title.mutators_hint"),
+        Name::new("MutatorsHint"),
+    ));
+}
+
+fn spawn_mutators_menu(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 3.0, 1.0).with_scale(Vec3::splat(0.003)),
+            text_anchor: bevy::sprite::Anchor::TopCenter,
+            text: Text {
+                sections: vec![TextSection::new(
+                    "",
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: TEXT_COLOR,
+                    },
+                )],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        MutatorsMenu,
+        MutatorsList,
+        Name::new("MutatorsMenu"),
+    ));
+}
+
+fn net_unlocked(stats: &LifetimeStats) -> bool {
+    stats.shapes_sorted() >= NET_UNLOCK_SHAPES
+}
+
+fn edit_mutators(
+    keys: Res<Input<KeyCode>>,
+    stats: Res<LifetimeStats>,
+    mut mutators: ResMut<Mutators>,
+) {
+    if keys.just_pressed(KeyCode::Key1) {
+        mutators.elastic_rope = !mutators.elastic_rope;
+    }
+    if keys.just_pressed(KeyCode::Key2) && net_unlocked(&stats) {
+        mutators.rope_net = !mutators.rope_net;
+    }
+    if keys.just_pressed(KeyCode::BracketLeft) {
+        mutators.rope_stiffness = (mutators.rope_stiffness / STIFFNESS_STEP).max(MIN_STIFFNESS);
+    }
+    if keys.just_pressed(KeyCode::BracketRight) {
+        mutators.rope_stiffness = (mutators.rope_stiffness * STIFFNESS_STEP).min(MAX_STIFFNESS);
+    }
+}
+
+fn display_mutators(
+    mutators: Res<Mutators>,
+    stats: Res<LifetimeStats>,
+    localizer: Localizer,
+    mut lists: Query<&mut Text, With<MutatorsList>>,
+) {
+    let on_off = |on: bool| match on {
+        true => localizer.get("settings.on"),
+        false => localizer.get("settings.off"),
+    };
+    let lines = [
+        localizer.get("mutators.title"),
+        String::new(),
+        localizer.format("mutators.elastic_rope", &[&on_off(mutators.elastic_rope)]),
+        localizer.format(
+            "mutators.stiffness",
+            &[&format!("{:.0}", mutators.rope_stiffness)],
+        ),
+        match net_unlocked(&stats) {
+            true => localizer.format("mutators.rope_net", &[&on_off(mutators.rope_net)]),
+            false => localizer.format(
+                "mutators.rope_net_locked",
+                &[&(NET_UNLOCK_SHAPES - stats.shapes_sorted()).to_string()],
+            ),
+        },
+        String::new(),
+        localizer.get("mutators.back"),
+    ];
+    for mut text in lists.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
@@ -0,0 +1,112 @@
+use std::fs;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{
+    kiosk::KioskMode,
+    player::{AttachState, Cursor, CursorSlot, ReleasedDevices},
+};
+use crate::mischief::MischiefDevices;
+
+// Remembers which mouse drove which cursor, by device name, so players don't have to claim their
+// cursors again every launch.
+const PAIRING_PATH: &str = "cursor_pairing.txt";
+
+pub struct PairingPlugin;
+
+impl Plugin for PairingPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SavedPairing::load())
+            .add_systems(
+                Update,
+                restore_pairing
+                    .run_if(in_state(AttachState::Waiting))
+                    // Each kiosk visitor claims their own cursors.
+                    .run_if(not(resource_exists::<KioskMode>())),
+            )
+            .add_systems(OnEnter(AttachState::Attached), save_pairing);
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+struct SavedPairing(HashMap<CursorSlot, String>);
+
+impl SavedPairing {
+    fn load() -> Self {
+        let mut pairing = Self::default();
+        let Ok(contents) = fs::read_to_string(PAIRING_PATH) else {
+            return pairing;
+        };
+        for line in contents.lines() {
+            let slot_and_name = line
+                .split_once('=')
+                .and_then(|(label, name)| Some((CursorSlot::from_label(label)?, name)));
+            match slot_and_name {
+                Some((slot, name)) => {
+                    pairing.0.insert(slot, name.to_owned());
+                }
+                None => println!("Skipping malformed cursor pairing line: {}", line),
+            }
+        }
+        pairing
+    }
+
+    fn save(&self) {
+        let mut slots = self.0.keys().copied().collect::<Vec<_>>();
+        slots.sort_by_key(|slot| slot.0);
+        let mut contents = String::new();
+        for slot in slots {
+            contents += &format!("{}={}\n", slot.label(), self.0[&slot]);
+        }
+        if let Err(error) = fs::write(PAIRING_PATH, contents) {
+            println!("Failed to save cursor pairing: {}", error);
+        }
+    }
+}
+
+// Finds a connected device with the given name that isn't already driving another cursor.
+// Identical mice share a name, so with two of the same model the cursors may come back swapped.
+fn find_device(devices: &MischiefDevices, name: &str, claimed: &[u32]) -> Option<u32> {
+    devices
+        .connected()
+        .find(|device| device.name == name && !claimed.contains(&device.id))
+        .map(|device| device.id)
+}
+
+fn restore_pairing(
+    devices: Res<MischiefDevices>,
+    pairing: Res<SavedPairing>,
+    mut released: ResMut<ReleasedDevices>,
+    mut cursors: Query<(&CursorSlot, &mut Cursor)>,
+) {
+    let mut claimed = cursors
+        .iter()
+        .filter_map(|(_, cursor)| cursor.0)
+        .collect::<Vec<_>>();
+    // A released mouse stays released until it claims a cursor again, and until then is skipped as
+    // if it were already driving one.
+    released.0.retain(|device| !claimed.contains(device));
+    claimed.extend(released.0.iter().copied());
+    for (slot, mut cursor) in cursors.iter_mut() {
+        if let (None, Some(name)) = (cursor.0, pairing.0.get(slot)) {
+            cursor.0 = find_device(&devices, name, &claimed);
+            claimed.extend(cursor.0);
+        }
+    }
+}
+
+fn save_pairing(
+    devices: Res<MischiefDevices>,
+    mut pairing: ResMut<SavedPairing>,
+    cursors: Query<(&CursorSlot, &Cursor)>,
+) {
+    let device_name = |cursor: &Cursor| {
+        let id = cursor.0?;
+        devices.get(id).map(|device| device.name.clone())
+    };
+    pairing.0 = cursors
+        .iter()
+        .filter_map(|(slot, cursor)| Some((*slot, device_name(cursor)?)))
+        .collect();
+    pairing.save();
+}
@@ -0,0 +1,282 @@
+use bevy::{
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+
+use super::{
+    gameplay::Shape,
+    player::{CursorSlot, MAX_PLAYERS},
+    settings::Settings,
+};
+
+// Patterns are images in assets/patterns, tiled once across each shape. White parts of the image
+// are drawn in PATTERN_COLOR.
+const PATTERN_DIRECTORY: &str = "patterns";
+const PATTERN_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.35);
+
+// The players, their bins and the shapes that go in them are drawn in the colors of the `Palette`
+// resource, which follows the palette picked in the settings. Anything drawn in those colors reads
+// them from the resource as it's spawned, and whatever outlives a switch of palettes, like the
+// shapes' and cursors' materials, is repainted by its own module when the resource changes. Optionally, shapes are also marked
+// with a pattern, so they can be told apart without relying on color at all: stripes on squares and
+// dots on circles. Triangles already stand out by their shape and their bin in the middle.
+pub struct PalettePlugin;
+
+impl Plugin for PalettePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Palette>()
+            .add_systems(Startup, (load_patterns, create_player_materials))
+            .add_systems(Update, (repaint_player_materials, apply_shape_patterns))
+            .add_systems(PostUpdate, follow_settings);
+    }
+}
+
+/// The palettes to pick from in the settings.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteChoice {
+    Standard,
+    /// Blue, vermillion, yellow and reddish purple, from the Okabe-Ito palette, which stay
+    /// distinct with the common kinds of color blindness.
+    Colorblind,
+    /// Teal, crimson, pink and plum, which stay distinct without telling blue from yellow.
+    BlueYellow,
+    /// Saturated colors which stand out more from the background.
+    HighContrast,
+}
+
+impl PaletteChoice {
+    pub const ALL: [PaletteChoice; 4] = [
+        PaletteChoice::Standard,
+        PaletteChoice::Colorblind,
+        PaletteChoice::BlueYellow,
+        PaletteChoice::HighContrast,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            PaletteChoice::Standard => "standard",
+            PaletteChoice::Colorblind => "colorblind",
+            PaletteChoice::BlueYellow => "blue_yellow",
+            PaletteChoice::HighContrast => "high_contrast",
+        }
+    }
+
+    /// The key of its name, see `Localizer`.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            PaletteChoice::Standard => "palette.standard",
+            PaletteChoice::Colorblind => "palette.colorblind",
+            PaletteChoice::BlueYellow => "palette.blue_yellow",
+            PaletteChoice::HighContrast => "palette.high_contrast",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|choice| *choice == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// The colors the players, their bins and the shapes that go in them are currently drawn in.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub left: Color,
+    pub right: Color,
+    pub third: Color,
+    pub fourth: Color,
+}
+
+// Starts out in the saved palette, so nothing spawned before the settings are first applied comes
+// up in the wrong colors.
+impl FromWorld for Palette {
+    fn from_world(world: &mut World) -> Self {
+        world
+            .get_resource::<Settings>()
+            .map_or(PaletteChoice::Standard, |settings| settings.palette)
+            .into()
+    }
+}
+
+impl From<PaletteChoice> for Palette {
+    fn from(choice: PaletteChoice) -> Self {
+        let [left, right, third, fourth] = match choice {
+            PaletteChoice::Standard => [
+                Color::rgb_u8(17, 159, 166),
+                Color::rgb_u8(226, 101, 60),
+                Color::rgb_u8(239, 193, 62),
+                Color::rgb_u8(155, 109, 201),
+            ],
+            PaletteChoice::Colorblind => [
+                Color::rgb_u8(0, 114, 178),
+                Color::rgb_u8(213, 94, 0),
+                Color::rgb_u8(240, 228, 66),
+                Color::rgb_u8(204, 121, 167),
+            ],
+            PaletteChoice::BlueYellow => [
+                Color::rgb_u8(0, 140, 140),
+                Color::rgb_u8(220, 38, 77),
+                Color::rgb_u8(255, 153, 178),
+                Color::rgb_u8(90, 51, 128),
+            ],
+            PaletteChoice::HighContrast => [
+                Color::rgb_u8(0, 115, 255),
+                Color::rgb_u8(255, 128, 0),
+                Color::rgb_u8(242, 242, 51),
+                Color::rgb_u8(204, 0, 204),
+            ],
+        };
+        Self {
+            left,
+            right,
+            third,
+            fourth,
+        }
+    }
+}
+
+impl Palette {
+    pub fn slot(&self, slot: CursorSlot) -> Color {
+        match slot.0 {
+            0 => self.left,
+            1 => self.right,
+            2 => self.third,
+            _ => self.fourth,
+        }
+    }
+
+    /// The color shapes of this kind are drawn in, and the walls of the bin that accepts them.
+    pub fn shape(&self, shape: Shape) -> Color {
+        match shape {
+            Shape::Square => self.left,
+            Shape::Circle => self.right,
+            Shape::Triangle => self.third,
+        }
+    }
+}
+
+/// One material per player in their color, shared by their cursor and the rope hanging from it,
+/// and repainted whenever the palette is switched.
+#[derive(Resource)]
+pub struct PlayerMaterials(Vec<Handle<ColorMaterial>>);
+
+impl PlayerMaterials {
+    pub fn get(&self, slot: CursorSlot) -> Handle<ColorMaterial> {
+        self.0[slot.0 as usize].clone()
+    }
+}
+
+#[derive(Resource)]
+struct PatternMaterials {
+    stripes: Handle<ColorMaterial>,
+    dots: Handle<ColorMaterial>,
+}
+
+impl PatternMaterials {
+    fn get(&self, shape: Shape) -> Option<Handle<ColorMaterial>> {
+        match shape {
+            Shape::Square => Some(self.stripes.clone()),
+            Shape::Circle => Some(self.dots.clone()),
+            Shape::Triangle => None,
+        }
+    }
+}
+
+// On a shape with its pattern drawn over it, even if its kind has no pattern.
+#[derive(Component)]
+struct Patterned;
+
+#[derive(Component)]
+struct PatternOverlay;
+
+fn load_patterns(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let mut pattern = |name: &str| {
+        materials.add(ColorMaterial {
+            color: PATTERN_COLOR,
+            texture: Some(asset_server.load(format!("{}/{}.png", PATTERN_DIRECTORY, name))),
+        })
+    };
+    commands.insert_resource(PatternMaterials {
+        stripes: pattern("stripes"),
+        dots: pattern("dots"),
+    });
+}
+
+fn create_player_materials(
+    mut commands: Commands,
+    palette: Res<Palette>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let player_materials = (0..MAX_PLAYERS)
+        .map(|slot| materials.add(ColorMaterial::from(palette.slot(CursorSlot(slot)))))
+        .collect();
+    commands.insert_resource(PlayerMaterials(player_materials));
+}
+
+fn repaint_player_materials(
+    palette: Res<Palette>,
+    player_materials: Res<PlayerMaterials>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !palette.is_changed() {
+        return;
+    }
+    for slot in (0..MAX_PLAYERS).map(CursorSlot) {
+        if let Some(material) = materials.get_mut(&player_materials.get(slot)) {
+            material.color = palette.slot(slot);
+        }
+    }
+}
+
+fn follow_settings(settings: Res<Settings>, mut palette: ResMut<Palette>) {
+    if settings.is_changed() {
+        palette.set_if_neq(settings.palette.into());
+    }
+}
+
+// The pattern is drawn with the shape's own mesh, so it follows the shape's outline.
+fn apply_shape_patterns(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    patterns: Res<PatternMaterials>,
+    unpatterned: Query<(Entity, &Shape, &Mesh2dHandle), Without<Patterned>>,
+    patterned: Query<Entity, With<Patterned>>,
+    overlays: Query<Entity, With<PatternOverlay>>,
+) {
+    if !settings.shape_patterns {
+        if settings.is_changed() {
+            for entity in overlays.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+            for entity in patterned.iter() {
+                commands.entity(entity).remove::<Patterned>();
+            }
+        }
+        return;
+    }
+    for (entity, shape, mesh) in unpatterned.iter() {
+        commands.entity(entity).insert(Patterned);
+        let Some(material) = patterns.get(*shape) else {
+            continue;
+        };
+        let overlay = commands
+            .spawn((
+                MaterialMesh2dBundle {
+                    transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                    mesh: mesh.clone(),
+                    material,
+                    ..default()
+                },
+                PatternOverlay,
+                Name::new("PatternOverlay"),
+            ))
+            .id();
+        commands.entity(entity).add_child(overlay);
+    }
+}
@@ -0,0 +1,232 @@
+use std::fs;
+
+use bevy::{prelude::*, sprite::Anchor};
+
+use super::{
+    locale::Localizer,
+    palette::Palette,
+    spawn_level::{HEIGHT, WIDTH},
+    stats::RunStats,
+    text_style::TextStyler,
+    AppState, DespawnOnExitGameOver, TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+// Launch with `--party` to rotate a roster of players through the two mice, one round at a time.
+const PARTY_FLAG: &str = "--party";
+// The roster is one player name per line.
+const ROSTER_PATH: &str = "party_players.txt";
+// With only two players there's nothing to rotate.
+const MIN_PARTY_PLAYERS: usize = 3;
+
+// Party mode: every round, the two players who have waited longest take the mice, and each is
+// credited with their own bin's score. Standings and the next pairing are shown between rounds.
+pub struct PartyPlugin;
+
+impl Plugin for PartyPlugin {
+    fn build(&self, app: &mut App) {
+        if !std::env::args().any(|arg| arg == PARTY_FLAG) {
+            return;
+        }
+        let Some(party) = Party::load() else {
+            return;
+        };
+
+        app.insert_resource(party)
+            .add_systems(OnEnter(AppState::Warmup), spawn_player_labels)
+            .add_systems(OnEnter(AppState::Playing), spawn_player_labels)
+            .add_systems(OnExit(AppState::Warmup), cleanup_system::<PlayerLabel>)
+            .add_systems(OnExit(AppState::Playing), cleanup_system::<PlayerLabel>)
+            .add_systems(
+                OnEnter(AppState::GameOver),
+                (finish_round, spawn_standings).chain(),
+            );
+    }
+}
+
+struct PartyPlayer {
+    name: String,
+    rounds: u32,
+    points: i32,
+    last_round: Option<u32>,
+}
+
+#[derive(Resource)]
+struct Party {
+    players: Vec<PartyPlayer>,
+    rounds_played: u32,
+    // Indices into `players` of who holds each mouse this round.
+    left: usize,
+    right: usize,
+}
+
+impl Party {
+    fn load() -> Option<Self> {
+        let roster = match fs::read_to_string(ROSTER_PATH) {
+            Ok(roster) => roster,
+            Err(error) => {
+                println!("Party mode needs a roster in {}: {}", ROSTER_PATH, error);
+                return None;
+            }
+        };
+        let players = roster
+            .lines()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| PartyPlayer {
+                name: name.to_owned(),
+                rounds: 0,
+                points: 0,
+                last_round: None,
+            })
+            .collect::<Vec<_>>();
+        if players.len() < MIN_PARTY_PLAYERS {
+            println!(
+                "Party mode needs at least {} players in {}, found {}",
+                MIN_PARTY_PLAYERS,
+                ROSTER_PATH,
+                players.len()
+            );
+            return None;
+        }
+
+        let mut party = Self {
+            players,
+            rounds_played: 0,
+            left: 0,
+            right: 1,
+        };
+        party.pick_next_pair();
+        Some(party)
+    }
+
+    // The two players with the fewest rounds play next, longest-waiting first. Sides alternate
+    // between rounds so nobody is stuck on one side.
+    fn pick_next_pair(&mut self) {
+        let mut order = (0..self.players.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| {
+            let player = &self.players[i];
+            (
+                player.rounds,
+                player.last_round.map_or(-1, |round| round as i64),
+            )
+        });
+        (self.left, self.right) = match self.rounds_played % 2 {
+            0 => (order[0], order[1]),
+            _ => (order[1], order[0]),
+        };
+    }
+
+    fn record_round(&mut self, left_points: i32, right_points: i32) {
+        for (i, points) in [(self.left, left_points), (self.right, right_points)] {
+            let player = &mut self.players[i];
+            player.rounds += 1;
+            player.points += points;
+            player.last_round = Some(self.rounds_played);
+        }
+        self.rounds_played += 1;
+        self.pick_next_pair();
+    }
+
+    fn standings(&self, localizer: &Localizer) -> String {
+        let mut order = (0..self.players.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| std::cmp::Reverse(self.players[i].points));
+        order
+            .iter()
+            .enumerate()
+            .map(|(place, &i)| {
+                let player = &self.players[i];
+                localizer.format(
+                    "party.standing",
+                    &[
+                        &(place + 1).to_string(),
+                        &player.name,
+                        &player.points.to_string(),
+                        &player.rounds.to_string(),
+                    ],
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[derive(Component)]
+struct PlayerLabel;
+
+fn spawn_player_labels(
+    mut commands: Commands,
+    party: Res<Party>,
+    palette: Res<Palette>,
+    text_styler: TextStyler,
+) {
+    let font = text_styler.font();
+    let labels = [
+        (-1.0, &party.players[party.left].name, palette.left),
+        (1.0, &party.players[party.right].name, palette.right),
+    ];
+    for (side, name, color) in labels {
+        commands.spawn((
+            Text2dBundle {
+                transform: Transform::from_xyz(side * (WIDTH / 2.0 - 1.0), HEIGHT / 2.0 - 1.6, 1.0)
+                    .with_scale(Vec3::splat(0.003)),
+                text: Text::from_section(
+                    name.clone(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 100.0,
+                        color,
+                    },
+                ),
+                ..default()
+            },
+            PlayerLabel,
+            Name::new("PlayerLabel"),
+        ));
+    }
+}
+
+fn finish_round(mut party: ResMut<Party>, run_stats: Res<RunStats>) {
+    let left_points = run_stats.left_correct as i32 - run_stats.left_incorrect as i32;
+    let right_points = run_stats.right_correct as i32 - run_stats.right_incorrect as i32;
+    party.record_round(left_points, right_points);
+}
+
+fn spawn_standings(
+    mut commands: Commands,
+    party: Res<Party>,
+    localizer: Localizer,
+    text_styler: TextStyler,
+) {
+    let text_style = TextStyle {
+        font: text_styler.font(),
+        font_size: 100.0,
+        color: TEXT_COLOR,
+    };
+    let next_up = localizer.format(
+        "party.next_up",
+        &[
+            &party.players[party.left].name,
+            &party.players[party.right].name,
+        ],
+    );
+
+    commands.spawn((
+        Text2dBundle {
+            // Hangs down from below the restart prompt, however many players there are.
+            transform: Transform::from_xyz(0.0, 0.6, 1.0).with_scale(Vec3::splat(0.003)),
+            text_anchor: Anchor::TopCenter,
+            text: Text {
+                sections: vec![
+                    TextSection::new(next_up, text_style.clone()),
+                    TextSection::new(party.standings(&localizer), text_style),
+                ],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        DespawnOnExitGameOver,
+        Name::new("PartyStandings"),
+    ));
+}
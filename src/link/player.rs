@@ -1,38 +1,197 @@
-use bevy::{input::common_conditions::input_toggle_active, prelude::*};
+use std::fs;
+
+use bevy::{
+    input::common_conditions::input_toggle_active, prelude::*, utils::HashSet,
+    window::PrimaryWindow,
+};
 use bevy_xpbd_2d::prelude::*;
 
-use crate::{
-    mischief::{poll_events, MischiefEvent, MischiefEventData, MischiefPlugin},
-    PIXELS_PER_METER,
+use super::{
+    settings::Settings,
+    spawn_level::{HEIGHT, WIDTH},
+    AppState,
+};
+use crate::mischief::{
+    accumulate_motion, poll_events, ManyMouseBackend, MischiefAssignments, MischiefDeviceSettings,
+    MischiefDevices, MischiefEvent, MischiefEventData, MischiefHotplug, MischiefPlugin,
+    MischiefRescan, MouseMotionAccumulator,
 };
 
 #[derive(Component)]
 pub struct Cursor(pub Option<u32>);
 
-#[derive(Component, Default)]
-pub struct LeftCursor;
+/// Which player a cursor belongs to. The left and right cursors are the two ends of the rope, and
+/// any further players' cursors are strung along the rope between them.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CursorSlot(pub u8);
+
+impl CursorSlot {
+    pub const LEFT: Self = Self(0);
+    pub const RIGHT: Self = Self(1);
+
+    pub fn is_end(self) -> bool {
+        self == Self::LEFT || self == Self::RIGHT
+    }
+
+    pub fn label(self) -> &'static str {
+        match self.0 {
+            0 => "left",
+            1 => "right",
+            2 => "third",
+            _ => "fourth",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        (0..MAX_PLAYERS)
+            .map(Self)
+            .find(|slot| slot.label() == label)
+    }
+
+    /// The language file key for `label`, for showing the slot to players.
+    pub fn label_key(self) -> &'static str {
+        match self.0 {
+            0 => "slot.left",
+            1 => "slot.right",
+            2 => "slot.third",
+            _ => "slot.fourth",
+        }
+    }
+}
+
+// Launch with e.g. `--mouse-backend=evdev` to force a particular ManyMouse driver.
+const BACKEND_FLAG: &str = "--mouse-backend=";
+
+fn backend_from_args() -> ManyMouseBackend {
+    let Some(value) =
+        std::env::args().find_map(|arg| arg.strip_prefix(BACKEND_FLAG).map(str::to_owned))
+    else {
+        return ManyMouseBackend::Auto;
+    };
+    match value.parse() {
+        Ok(backend) => backend,
+        Err(error) => {
+            println!("{}, using auto", error);
+            ManyMouseBackend::Auto
+        }
+    }
+}
 
-#[derive(Component, Default)]
-pub struct RightCursor;
+// Launch with e.g. `--players=4` to put more cursors on the rope.
+const PLAYERS_FLAG: &str = "--players=";
+pub const MAX_PLAYERS: u8 = 4;
+
+/// How many cursors, and so how many mice, the rope has.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct PlayerCount(pub u8);
+
+impl PlayerCount {
+    fn from_args() -> Self {
+        let Some(value) =
+            std::env::args().find_map(|arg| arg.strip_prefix(PLAYERS_FLAG).map(str::to_owned))
+        else {
+            return Self(2);
+        };
+        match value.parse() {
+            Ok(count) if (2..=MAX_PLAYERS).contains(&count) => Self(count),
+            _ => {
+                println!(
+                    "Player count must be 2 to {}, got {}; using 2",
+                    MAX_PLAYERS, value
+                );
+                Self(2)
+            }
+        }
+    }
+}
+
+/// The cursor a device should claim: its preferred end of the rope if that's free, otherwise the
+/// first free cursor along the middle of the rope.
+pub fn claimable_slot<'a>(
+    cursors: impl Iterator<Item = (&'a CursorSlot, &'a Cursor)>,
+    preferred: Option<CursorSlot>,
+) -> Option<CursorSlot> {
+    cursors
+        .filter(|(slot, cursor)| {
+            cursor.0.is_none() && (Some(**slot) == preferred || !slot.is_end())
+        })
+        .map(|(slot, _)| *slot)
+        .min_by_key(|slot| (Some(*slot) != preferred, slot.0))
+}
+
+/// Attaches `device` to the cursor from `claimable_slot`, unless it already drives a cursor.
+pub fn claim_cursor(
+    cursors: &mut Query<(&CursorSlot, &mut Cursor)>,
+    preferred: Option<CursorSlot>,
+    device: u32,
+) -> Option<CursorSlot> {
+    if cursors.iter().any(|(_, cursor)| cursor.0 == Some(device)) {
+        return None;
+    }
+    let claimed = claimable_slot(cursors.iter(), preferred)?;
+    for (slot, mut cursor) in cursors.iter_mut() {
+        if *slot == claimed {
+            cursor.0 = Some(device);
+        }
+    }
+    Some(claimed)
+}
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(MischiefPlugin)
+        let mischief = MischiefPlugin {
+            backend: backend_from_args(),
+            ..default()
+        };
+        app.add_plugins(mischief)
+            .insert_resource(PlayerCount::from_args())
             .register_type::<TargetVelocity>()
+            .register_type::<PIDController>()
+            .register_type::<CursorGains>()
+            .insert_resource(CursorGains::load())
             .add_state::<AttachState>()
+            .init_resource::<ReleasedDevices>()
             .add_systems(
                 Update,
                 attach_cursors.run_if(in_state(AttachState::Waiting)),
             )
+            .add_systems(Update, detach_on_middle_click.before(move_cursors))
+            .add_systems(Update, toggle_hotplug)
+            .add_systems(Update, request_rescan.after(poll_events))
             .add_systems(
                 Update,
                 move_cursors
-                    .after(poll_events)
+                    .after(accumulate_motion)
                     .run_if(input_toggle_active(true, KeyCode::Grave)),
             )
-            .add_systems(FixedUpdate, apply_cursor_force.before(PhysicsSet::Prepare));
+            .add_systems(
+                FixedUpdate,
+                (apply_cursor_gains, apply_cursor_force)
+                    .chain()
+                    .before(PhysicsSet::Prepare),
+            )
+            .add_systems(PostUpdate, publish_assignments);
+    }
+}
+
+// Cursors are the source of truth for who plays with which device; this mirrors them into
+// Mischief's resource for anything that only knows about devices.
+fn publish_assignments(
+    cursors: Query<(&CursorSlot, &Cursor)>,
+    changed: Query<(), Changed<Cursor>>,
+    mut removed: RemovedComponents<Cursor>,
+    mut assignments: ResMut<MischiefAssignments>,
+) {
+    if changed.is_empty() && removed.iter().count() == 0 {
+        return;
+    }
+    *assignments = MischiefAssignments::default();
+    for (slot, cursor) in cursors.iter() {
+        if let Some(device) = cursor.0 {
+            assignments.assign(slot.0, device);
+        }
     }
 }
 
@@ -43,75 +202,279 @@ pub enum AttachState {
     Attached,
 }
 
+// Newly plugged in mice can only be claimed while waiting for players on the title screen. Mid-run,
+// each periodic rescan would restart ManyMouse under the players still playing and drop their input,
+// so a player who detached waits for F5 or a disconnect to rescan instead.
+fn toggle_hotplug(
+    attach_state: Res<State<AttachState>>,
+    app_state: Res<State<AppState>>,
+    mut hotplug: ResMut<MischiefHotplug>,
+) {
+    let enabled = *attach_state.get() == AttachState::Waiting && *app_state.get() == AppState::Init;
+    if hotplug.enabled != enabled {
+        hotplug.enabled = enabled;
+    }
+}
+
+// Rescan for mice on demand, or as soon as one goes away.
+fn request_rescan(
+    keys: Res<Input<KeyCode>>,
+    mut mouse_events: EventReader<MischiefEvent>,
+    mut rescan: EventWriter<MischiefRescan>,
+) {
+    let disconnected = mouse_events
+        .iter()
+        .any(|event| matches!(event.event_data, MischiefEventData::Disconnect));
+    if disconnected || keys.just_pressed(KeyCode::F5) {
+        rescan.send(MischiefRescan);
+    }
+}
+
+// The left button claims the left cursor and the right button the right one. With more than two
+// players, a click whose end of the rope is taken claims a cursor in the middle instead. Devices
+// which can't move a cursor, like an absolute device reporting no range, are turned away.
 fn attach_cursors(
     mut mouse_events: EventReader<MischiefEvent>,
-    mut left_cursors: Query<&mut Cursor, (With<LeftCursor>, Without<RightCursor>)>,
-    mut right_cursors: Query<&mut Cursor, (With<RightCursor>, Without<LeftCursor>)>,
+    devices: Res<MischiefDevices>,
+    mut cursors: Query<(&CursorSlot, &mut Cursor)>,
     mut state: ResMut<NextState<AttachState>>,
 ) {
-    let left_cursor_device = left_cursors.single().0;
-    let right_cursor_device = right_cursors.single().0;
-    if left_cursor_device != None && right_cursor_device != None {
+    if !cursors.is_empty() && cursors.iter().all(|(_, cursor)| cursor.0.is_some()) {
         state.set(AttachState::Attached);
         return;
     }
 
     for event in mouse_events.iter() {
-        match event.event_data {
+        let preferred = match event.event_data {
             MischiefEventData::Button {
                 button: 0,
                 pressed: true,
-            } => {
-                if left_cursor_device == None && right_cursor_device != Some(event.device) {
-                    let mut cursor = left_cursors.single_mut();
-                    cursor.0 = Some(event.device);
-                }
-            }
+            } => CursorSlot::LEFT,
             MischiefEventData::Button {
                 button: 1,
                 pressed: true,
-            } => {
-                if right_cursor_device == None && left_cursor_device != Some(event.device) {
-                    let mut cursor = right_cursors.single_mut();
-                    cursor.0 = Some(event.device);
-                }
+            } => CursorSlot::RIGHT,
+            _ => continue,
+        };
+        if devices
+            .capabilities(event.device)
+            .is_some_and(|capabilities| !capabilities.can_point())
+        {
+            println!(
+                "Device {} can't move a cursor, not attaching it",
+                event.device
+            );
+            continue;
+        }
+        claim_cursor(&mut cursors, Some(preferred), event.device);
+    }
+}
+
+/// Devices whose cursors were released with a middle click, which only get a cursor back when
+/// they're used to claim one, not automatically from the saved pairing.
+#[derive(Resource, Default, Debug)]
+pub struct ReleasedDevices(pub HashSet<u32>);
+
+// Middle-clicking releases that mouse's cursor, so it can be handed to someone else without
+// restarting. Mid-run this pauses the game until the cursor is claimed again, as for an unplugged
+// mouse.
+fn detach_on_middle_click(
+    mut mouse_events: EventReader<MischiefEvent>,
+    mut cursors: Query<(&CursorSlot, &mut Cursor, &mut AbsoluteTarget)>,
+    mut released: ResMut<ReleasedDevices>,
+    mut attach_state: ResMut<NextState<AttachState>>,
+) {
+    for event in mouse_events.iter() {
+        let MischiefEventData::Button {
+            button: 2,
+            pressed: true,
+        } = event.event_data
+        else {
+            continue;
+        };
+        for (slot, mut cursor, mut absolute_target) in cursors.iter_mut() {
+            if cursor.0 == Some(event.device) {
+                println!("Detaching the {} cursor", slot.label());
+                cursor.0 = None;
+                absolute_target.0 = None;
+                released.0.insert(event.device);
+                attach_state.set(AttachState::Waiting);
             }
-            _ => {}
         }
     }
 }
 
-fn move_cursors(
+// Cursors driven by absolute devices chase their target at a speed that would reach it in this
+// long, rather than teleporting there.
+const ABSOLUTE_CATCHUP_SECONDS: f32 = 0.1;
+
+pub fn move_cursors(
     mut mouse_events: EventReader<MischiefEvent>,
-    mut cursor_query: Query<(&mut TargetVelocity, &Cursor)>,
+    mut cursor_query: Query<(
+        &mut TargetVelocity,
+        &mut AbsoluteTarget,
+        &mut Cursor,
+        &Transform,
+        &mut SmoothedVelocity,
+    )>,
+    accumulator: Res<MouseMotionAccumulator>,
+    device_settings: Res<MischiefDeviceSettings>,
+    preferences: Res<Settings>,
+    mut attach_state: ResMut<NextState<AttachState>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    projections: Query<&OrthographicProjection>,
     time: Res<Time>,
 ) {
-    for (mut target_velocity, _) in cursor_query.iter_mut() {
-        target_velocity.0 = Vec2::ZERO;
+    // Mouse motion comes in pixels, and the camera scales the arena to fit the window, so a pixel
+    // covers more of the arena in a smaller window.
+    let meters_per_pixel = match (windows.get_single(), projections.get_single()) {
+        (Ok(window), Ok(projection)) if window.width() > 0.0 => {
+            projection.area.width() / window.width()
+        }
+        _ => 0.0,
+    };
+    for (mut target_velocity, _, cursor, _, _) in cursor_query.iter_mut() {
+        target_velocity.0 = match cursor.0 {
+            Some(device) => {
+                let settings = device_settings.get(device);
+                let motion =
+                    settings.apply(accumulator.get(device).as_vec2()) * preferences.sensitivity;
+                let velocity = settings.respond(motion / time.delta_seconds());
+                Vec2::new(velocity.x, -velocity.y) * meters_per_pixel
+            }
+            None => Vec2::ZERO,
+        };
     }
 
     for event in mouse_events.iter() {
-        for (mut target_velocity, cursor) in cursor_query.iter_mut() {
+        for (_, mut absolute_target, mut cursor, _, _) in cursor_query.iter_mut() {
             if cursor.0 == Some(event.device) {
                 match event.event_data {
-                    MischiefEventData::RelMotion { x, y } => {
-                        target_velocity.0 += Vec2::new(x as f32, -y as f32)
-                            / (PIXELS_PER_METER * time.delta_seconds());
+                    MischiefEventData::AbsMotion { x, y } => {
+                        // Map the device's range onto the whole play area, with y pointing down.
+                        let target = absolute_target.0.get_or_insert(Vec2::ZERO);
+                        if let Some(x) = x {
+                            target.x = (x - 0.5) * WIDTH;
+                        }
+                        if let Some(y) = y {
+                            target.y = (0.5 - y) * HEIGHT;
+                        }
                     }
+                    // Release the cursor so it can be claimed again, by the same mouse once it's
+                    // reconnected or by another one.
                     MischiefEventData::Disconnect => {
-                        panic!("Mouse disconnected");
+                        println!("Cursor's mouse disconnected, waiting for a new one");
+                        cursor.0 = None;
+                        absolute_target.0 = None;
+                        attach_state.set(AttachState::Waiting);
                     }
                     _ => {}
                 }
             }
         }
     }
+
+    for (mut target_velocity, absolute_target, cursor, transform, mut smoothed) in
+        cursor_query.iter_mut()
+    {
+        // Only relative motion is filtered; absolute devices already ease towards their target.
+        let settings = cursor.0.map(|device| device_settings.get(device));
+        let velocity = settings.map_or(target_velocity.0, |settings| {
+            settings.smooth(smoothed.0, target_velocity.0, time.delta_seconds())
+        });
+        smoothed.0 = velocity;
+        target_velocity.0 = velocity;
+
+        if let Some(target) = absolute_target.0 {
+            target_velocity.0 +=
+                (target - transform.translation.truncate()) / ABSOLUTE_CATCHUP_SECONDS;
+        }
+    }
 }
 
 #[derive(Component, Reflect, Debug, Default)]
 pub struct TargetVelocity(pub Vec2);
 
-#[derive(Component)]
+/// The output of the cursor's motion filter last frame, see `DeviceSettings::smoothing_seconds`.
+#[derive(Component, Debug, Default)]
+pub struct SmoothedVelocity(pub Vec2);
+
+/// Where an absolute pointing device (e.g. a drawing tablet) wants the cursor to be, once one has
+/// reported a position.
+#[derive(Component, Debug, Default)]
+pub struct AbsoluteTarget(pub Option<Vec2>);
+
+// Overrides for the default gains, as `name=value` lines, e.g. `p=1.5`.
+const CURSOR_GAINS_PATH: &str = "cursor_gains.txt";
+
+/// Gains shared by every cursor's PID controller. Loaded from `cursor_gains.txt` at launch if it
+/// exists, and editable live in the inspector.
+#[derive(Resource, Reflect, Debug, Clone)]
+#[reflect(Resource)]
+pub struct CursorGains {
+    pub p: f32,
+    pub i: f32,
+    pub d: f32,
+    pub max_positional_error: f32,
+    pub max_integral_error: f32,
+}
+
+impl Default for CursorGains {
+    fn default() -> Self {
+        Self {
+            p: 1.0,
+            i: 1.0,
+            d: 0.0,
+            max_positional_error: 3.0,
+            max_integral_error: 0.5,
+        }
+    }
+}
+
+impl CursorGains {
+    fn load() -> Self {
+        let mut gains = Self::default();
+        let Ok(contents) = fs::read_to_string(CURSOR_GAINS_PATH) else {
+            return gains;
+        };
+        for line in contents.lines() {
+            let Some((name, value)) = line.split_once('=') else {
+                println!("Skipping malformed cursor gains line: {}", line);
+                continue;
+            };
+            let Ok(value) = value.trim().parse::<f32>() else {
+                println!("Skipping cursor gain with a non-numeric value: {}", line);
+                continue;
+            };
+            match name.trim() {
+                "p" => gains.p = value,
+                "i" => gains.i = value,
+                "d" => gains.d = value,
+                "max_positional_error" => gains.max_positional_error = value,
+                "max_integral_error" => gains.max_integral_error = value,
+                _ => println!("Skipping unknown cursor gain: {}", line),
+            }
+        }
+        println!(
+            "Loaded cursor gains from {}: {:?}",
+            CURSOR_GAINS_PATH, gains
+        );
+        gains
+    }
+
+    pub fn save(&self) {
+        let contents = format!(
+            "p={}\ni={}\nd={}\nmax_positional_error={}\nmax_integral_error={}\n",
+            self.p, self.i, self.d, self.max_positional_error, self.max_integral_error
+        );
+        if let Err(error) = fs::write(CURSOR_GAINS_PATH, contents) {
+            println!("Failed to save cursor gains: {}", error);
+        }
+    }
+}
+
+/// Gains are copied in from `CursorGains` whenever it changes; only the error terms are per cursor.
+#[derive(Component, Reflect, Debug, Default)]
 pub struct PIDController {
     pub p: f32,
     pub i: f32,
@@ -122,7 +485,24 @@ pub struct PIDController {
     pub prev_error: Vec2,
 }
 
-fn apply_cursor_force(
+fn apply_cursor_gains(
+    gains: Res<CursorGains>,
+    mut controllers: Query<&mut PIDController>,
+    added: Query<(), Added<PIDController>>,
+) {
+    if !gains.is_changed() && added.is_empty() {
+        return;
+    }
+    for mut controller in controllers.iter_mut() {
+        controller.p = gains.p;
+        controller.i = gains.i;
+        controller.d = gains.d;
+        controller.max_positional_error = gains.max_positional_error;
+        controller.max_integral_error = gains.max_integral_error;
+    }
+}
+
+pub fn apply_cursor_force(
     mut cursors: Query<(
         &TargetVelocity,
         &mut PIDController,
@@ -132,6 +512,7 @@ fn apply_cursor_force(
     )>,
     time: Res<FixedTime>,
 ) {
+    let _span = info_span!("apply_cursor_force").entered();
     for (target_velocity, mut pd, mass, velocity, mut force) in cursors.iter_mut() {
         let error = target_velocity.0 - velocity.0;
 
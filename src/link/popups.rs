@@ -0,0 +1,225 @@
+use bevy::prelude::*;
+
+use super::{
+    game_mode::DrainRule,
+    gameplay::{ShapeDrained, ShapeScored},
+    locale::Localizer,
+    palette::Palette,
+    spawn_level::PLAY_REGION,
+    text_style::TextStyler,
+    BAD_COLOR, TEXT_COLOR,
+};
+
+// Popups drift up this far, in meters, as they fade out.
+const POPUP_SECONDS: f32 = 1.0;
+const POPUP_RISE: f32 = 0.6;
+// Popups start this far above where they're raised, so they aren't hidden behind whatever raised
+// them.
+const POPUP_OFFSET: f32 = 0.3;
+const POPUP_SCALE: f32 = 0.004;
+// A scored shape swells to this many times its size and back again.
+const PULSE_SECONDS: f32 = 0.3;
+const PULSE_GROWTH: f32 = 1.35;
+// A drained shape leaves behind a puff this size, which grows to this many times that as it fades.
+const PUFF_SECONDS: f32 = 0.4;
+const PUFF_SIZE: f32 = 0.3;
+const PUFF_GROWTH: f32 = 2.0;
+
+// Short labels that float up from where something happened in the playfield and fade away, raised
+// with a `Popup` event. Shapes don't score or drain silently: a shape landing in a bin pulses, and
+// the points it's worth there float up from it. A shape lost through the drain leaves a fading puff
+// at the bottom of the playfield, along with what it cost under the drain rule.
+pub struct PopupPlugin;
+
+impl Plugin for PopupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Popup>().add_systems(
+            Update,
+            (
+                (popup_scores, popup_drains, spawn_popups).chain(),
+                float_popups,
+                pulse_scored_shapes,
+                fade_puffs,
+            ),
+        );
+    }
+}
+
+/// Raises a label at a point in the playfield, which floats up and fades.
+#[derive(Event, Debug, Clone)]
+pub struct Popup {
+    pub label: String,
+    pub position: Vec2,
+    pub color: Color,
+    /// The scale of the label's text, which is drawn at a font size of 100.
+    pub scale: f32,
+}
+
+impl Popup {
+    /// Negative numbers are shown in `BAD_COLOR`, and anything else in `TEXT_COLOR`.
+    pub fn new(label: impl Into<String>, position: Vec2) -> Self {
+        let label = label.into();
+        let color = match label.starts_with('-') {
+            true => BAD_COLOR,
+            false => TEXT_COLOR,
+        };
+        Self {
+            label,
+            position,
+            color,
+            scale: POPUP_SCALE,
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+#[derive(Component)]
+struct FloatingPopup {
+    start: Vec2,
+    timer: Timer,
+}
+
+#[derive(Component)]
+struct ScorePulse(Timer);
+
+#[derive(Component)]
+struct DrainPuff(Timer);
+
+fn spawn_popups(mut commands: Commands, mut popups: EventReader<Popup>, text_styler: TextStyler) {
+    for popup in popups.iter() {
+        let start = popup.position + Vec2::new(0.0, POPUP_OFFSET);
+        commands.spawn((
+            Text2dBundle {
+                transform: Transform::from_translation(start.extend(3.0))
+                    .with_scale(Vec3::splat(popup.scale)),
+                text: Text::from_section(
+                    popup.label.clone(),
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: popup.color,
+                    },
+                )
+                .with_alignment(TextAlignment::Center),
+                ..default()
+            },
+            FloatingPopup {
+                start,
+                timer: Timer::from_seconds(POPUP_SECONDS, TimerMode::Once),
+            },
+            Name::new("Popup"),
+        ));
+    }
+}
+
+fn popup_scores(
+    mut commands: Commands,
+    mut scored_events: EventReader<ShapeScored>,
+    mut popups: EventWriter<Popup>,
+) {
+    for event in scored_events.iter() {
+        let label = match event.points {
+            points if points > 0 => format!("+{}", points),
+            points => format!("{}", points),
+        };
+        popups.send(Popup::new(label, event.position));
+        if let Some(mut shape) = commands.get_entity(event.entity) {
+            shape.insert(ScorePulse(Timer::from_seconds(
+                PULSE_SECONDS,
+                TimerMode::Once,
+            )));
+        }
+    }
+}
+
+// Shapes drain out of sight, so their puffs and popups are brought up to the edge of the playfield.
+fn popup_drains(
+    mut commands: Commands,
+    mut drained_events: EventReader<ShapeDrained>,
+    mut popups: EventWriter<Popup>,
+    drain_rule: Res<DrainRule>,
+    palette: Res<Palette>,
+    localizer: Localizer,
+) {
+    for event in drained_events.iter() {
+        let position = event.position.clamp(PLAY_REGION.min, PLAY_REGION.max);
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: palette.shape(event.shape).with_a(0.8),
+                    custom_size: Some(Vec2::splat(PUFF_SIZE)),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(3.0)),
+                ..default()
+            },
+            DrainPuff(Timer::from_seconds(PUFF_SECONDS, TimerMode::Once)),
+            Name::new("DrainPuff"),
+        ));
+        let label = match *drain_rule {
+            DrainRule::CostsPoints => format!("-{}", event.value.0),
+            DrainRule::Neutral | DrainRule::RefundsSpawn => localizer.get("popup.miss"),
+        };
+        popups.send(Popup::new(label, position));
+    }
+}
+
+fn float_popups(
+    mut commands: Commands,
+    mut popups: Query<(Entity, &mut FloatingPopup, &mut Text, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut popup, mut text, mut transform) in popups.iter_mut() {
+        if popup.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let progress = popup.timer.percent();
+        transform.translation.y = popup.start.y + POPUP_RISE * progress;
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(1.0 - progress * progress);
+        }
+    }
+}
+
+// Only the mesh grows; the shape's collider stays the same size.
+fn pulse_scored_shapes(
+    mut commands: Commands,
+    mut shapes: Query<(Entity, &mut ScorePulse, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut pulse, mut transform) in shapes.iter_mut() {
+        if pulse.0.tick(time.delta()).finished() {
+            transform.scale = Vec3::ONE;
+            commands.entity(entity).remove::<ScorePulse>();
+            continue;
+        }
+        let swell = (pulse.0.percent() * std::f32::consts::PI).sin();
+        transform.scale = Vec3::splat(1.0 + (PULSE_GROWTH - 1.0) * swell);
+    }
+}
+
+fn fade_puffs(
+    mut commands: Commands,
+    mut puffs: Query<(Entity, &mut DrainPuff, &mut Sprite, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut puff, mut sprite, mut transform) in puffs.iter_mut() {
+        if puff.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let progress = puff.0.percent();
+        sprite.color.set_a(0.8 * (1.0 - progress));
+        transform.scale = Vec3::splat(1.0 + (PUFF_GROWTH - 1.0) * progress);
+    }
+}
@@ -0,0 +1,345 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_xpbd_2d::prelude::*;
+use rand::Rng;
+
+use super::{
+    gameplay::{BinMargin, Shape},
+    locale::Localizer,
+    popups::Popup,
+    spawn_level::{Layer, RopeSegment, HEIGHT},
+    stalls::StallState,
+    text_style::TextStyler,
+    AppState, TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+// A token turns up every so often, somewhere in the middle of the playfield, and disappears if it
+// isn't collected in time.
+const TOKEN_MIN_SECONDS: f32 = 15.0;
+const TOKEN_MAX_SECONDS: f32 = 25.0;
+const TOKEN_LIFETIME_SECONDS: f32 = 8.0;
+const TOKEN_RADIUS: f32 = 0.22;
+const TOKEN_REGION: Rect = Rect {
+    min: Vec2::new(-3.0, 0.5),
+    max: Vec2::new(3.0, 2.5),
+};
+
+// During slow motion, shapes feel this fraction of gravity, and their speed is damped by this much
+// per second.
+const SLOW_MOTION_GRAVITY_SCALE: f32 = 0.4;
+const SLOW_MOTION_DRAG: f32 = 1.5;
+// Wider bins count shapes this many meters outside their walls as in them.
+const WIDER_BINS_MARGIN: f32 = 0.3;
+
+// Occasional tokens which the rope must touch to collect, each turning on a helpful effect for a
+// few seconds: slow motion, wider bins, or a score freeze which makes mistakes free. Effects
+// collected while others are running stack up, each with its own timer.
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PowerUps>()
+            .add_systems(OnEnter(AppState::Playing), start_tokens)
+            .add_systems(Startup, spawn_power_up_display)
+            .add_systems(
+                Update,
+                (
+                    schedule_tokens,
+                    expire_tokens,
+                    collect_tokens,
+                    tick_power_ups,
+                    widen_bins,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(in_state(StallState::Running)),
+            )
+            .add_systems(
+                FixedUpdate,
+                slow_shapes
+                    .before(PhysicsSet::Prepare)
+                    .run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(Update, display_power_ups)
+            .add_systems(
+                OnExit(AppState::Playing),
+                (cleanup_system::<PowerUpToken>, clear_power_ups),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerUp {
+    SlowMotion,
+    WiderBins,
+    /// Shapes landing in the wrong bin cost nothing, see `update_score`.
+    ScoreFreeze,
+}
+
+impl PowerUp {
+    const ALL: [PowerUp; 3] = [
+        PowerUp::SlowMotion,
+        PowerUp::WiderBins,
+        PowerUp::ScoreFreeze,
+    ];
+
+    fn seconds(self) -> f32 {
+        match self {
+            PowerUp::SlowMotion => 6.0,
+            PowerUp::WiderBins => 8.0,
+            PowerUp::ScoreFreeze => 8.0,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            PowerUp::SlowMotion => Color::rgb(0.5, 0.75, 1.0),
+            PowerUp::WiderBins => Color::rgb(0.55, 0.95, 0.45),
+            PowerUp::ScoreFreeze => Color::rgb(0.85, 0.95, 1.0),
+        }
+    }
+
+    // The key of its name, see `Localizer`.
+    fn label_key(self) -> &'static str {
+        match self {
+            PowerUp::SlowMotion => "power_up.slow_motion",
+            PowerUp::WiderBins => "power_up.wider_bins",
+            PowerUp::ScoreFreeze => "power_up.score_freeze",
+        }
+    }
+}
+
+/// The effects currently running, most recently collected last.
+#[derive(Resource, Default)]
+pub struct PowerUps {
+    active: Vec<ActivePowerUp>,
+}
+
+struct ActivePowerUp {
+    power_up: PowerUp,
+    remaining_seconds: f32,
+}
+
+impl PowerUps {
+    pub fn is_active(&self, power_up: PowerUp) -> bool {
+        self.active.iter().any(|active| active.power_up == power_up)
+    }
+}
+
+#[derive(Resource)]
+struct TokenScheduler {
+    timer: Timer,
+}
+
+#[derive(Component)]
+struct PowerUpToken {
+    power_up: PowerUp,
+    lifetime: Timer,
+}
+
+#[derive(Component)]
+struct PowerUpDisplay;
+
+fn token_delay() -> Duration {
+    let mut rng = rand::thread_rng();
+    Duration::from_secs_f32(rng.gen_range(TOKEN_MIN_SECONDS..TOKEN_MAX_SECONDS))
+}
+
+fn start_tokens(mut commands: Commands) {
+    commands.insert_resource(TokenScheduler {
+        timer: Timer::new(token_delay(), TimerMode::Once),
+    });
+}
+
+fn clear_power_ups(mut power_ups: ResMut<PowerUps>, mut margin: ResMut<BinMargin>) {
+    power_ups.active.clear();
+    margin.set_if_neq(BinMargin(0.0));
+}
+
+fn schedule_tokens(
+    mut commands: Commands,
+    mut scheduler: ResMut<TokenScheduler>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    if !scheduler.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    scheduler.timer.set_duration(token_delay());
+    scheduler.timer.reset();
+
+    let mut rng = rand::thread_rng();
+    let power_up = PowerUp::ALL[rng.gen_range(0..PowerUp::ALL.len())];
+    let x = rng.gen_range(TOKEN_REGION.min.x..TOKEN_REGION.max.x);
+    let y = rng.gen_range(TOKEN_REGION.min.y..TOKEN_REGION.max.y);
+    commands.spawn((
+        MaterialMesh2dBundle {
+            transform: Transform::from_xyz(x, y, 0.5),
+            // A diamond, to tell it apart from the round bin magnet pickup.
+            mesh: meshes
+                .add(shape::RegularPolygon::new(TOKEN_RADIUS, 4).into())
+                .into(),
+            material: materials.add(ColorMaterial::from(power_up.color())),
+            ..default()
+        },
+        RigidBody::Static,
+        Collider::ball(TOKEN_RADIUS),
+        // Touched rather than pushed, and by the rope alone.
+        Sensor,
+        CollisionLayers::new([Layer::Hazards], [Layer::Rope]),
+        PowerUpToken {
+            power_up,
+            lifetime: Timer::from_seconds(TOKEN_LIFETIME_SECONDS, TimerMode::Once),
+        },
+        Name::new(format!("{:?} token", power_up)),
+    ));
+}
+
+// Pulses as it waits, faster as it's about to disappear.
+fn expire_tokens(
+    mut commands: Commands,
+    mut tokens: Query<(Entity, &mut PowerUpToken, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut token, mut transform) in tokens.iter_mut() {
+        if token.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let elapsed = token.lifetime.elapsed_secs();
+        let rate = 4.0 + 8.0 * token.lifetime.percent();
+        transform.scale = Vec3::splat(1.0 + 0.15 * (elapsed * rate).sin());
+    }
+}
+
+fn collect_tokens(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionStarted>,
+    mut popups: EventWriter<Popup>,
+    tokens: Query<(&PowerUpToken, &Transform)>,
+    segments: Query<(), With<RopeSegment>>,
+    mut power_ups: ResMut<PowerUps>,
+    localizer: Localizer,
+) {
+    // A token can touch several segments at once, but is only collected once.
+    let mut collected = Vec::new();
+    for CollisionStarted(a, b) in collisions.iter() {
+        let (token, segment) = match tokens.contains(*a) {
+            true => (*a, *b),
+            false => (*b, *a),
+        };
+        let Ok((PowerUpToken { power_up, .. }, transform)) = tokens.get(token) else {
+            continue;
+        };
+        if !segments.contains(segment) || collected.contains(&token) {
+            continue;
+        }
+        collected.push(token);
+        let label = localizer.get(power_up.label_key());
+        println!("{} for {}s", label, power_up.seconds());
+        popups
+            .send(Popup::new(label, transform.translation.truncate()).with_color(power_up.color()));
+        commands.entity(token).despawn_recursive();
+        power_ups.active.push(ActivePowerUp {
+            power_up: *power_up,
+            remaining_seconds: power_up.seconds(),
+        });
+    }
+}
+
+fn tick_power_ups(mut power_ups: ResMut<PowerUps>, time: Res<Time>) {
+    if power_ups.active.is_empty() {
+        return;
+    }
+    for active in power_ups.active.iter_mut() {
+        active.remaining_seconds -= time.delta_seconds();
+    }
+    power_ups
+        .active
+        .retain(|active| active.remaining_seconds > 0.0);
+}
+
+fn widen_bins(power_ups: Res<PowerUps>, mut margin: ResMut<BinMargin>) {
+    margin.set_if_neq(BinMargin(match power_ups.is_active(PowerUp::WiderBins) {
+        true => WIDER_BINS_MARGIN,
+        false => 0.0,
+    }));
+}
+
+// Holds shapes up against gravity and damps their speed, so they drift down slowly.
+fn slow_shapes(
+    power_ups: Res<PowerUps>,
+    gravity: Res<Gravity>,
+    mut shapes: Query<
+        (&Mass, &LinearVelocity, &mut ExternalForce),
+        (With<Shape>, Without<Sleeping>),
+    >,
+) {
+    if !power_ups.is_active(PowerUp::SlowMotion) {
+        return;
+    }
+    for (mass, velocity, mut force) in shapes.iter_mut() {
+        let lift = -gravity.0 * (1.0 - SLOW_MOTION_GRAVITY_SCALE);
+        let drag = -velocity.0 * SLOW_MOTION_DRAG;
+        force.apply_force((lift + drag) * mass.0);
+    }
+}
+
+fn spawn_power_up_display(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, HEIGHT / 2.0 - 3.0, 1.0)
+                .with_scale(Vec3::splat(0.003)),
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: TEXT_COLOR,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        PowerUpDisplay,
+        Name::new("PowerUpDisplay"),
+    ));
+}
+
+// Lists each running effect once, with the longest time it has left.
+fn display_power_ups(
+    power_ups: Res<PowerUps>,
+    localizer: Localizer,
+    mut displays: Query<&mut Text, With<PowerUpDisplay>>,
+) {
+    if !power_ups.is_changed() {
+        return;
+    }
+    let value = PowerUp::ALL
+        .into_iter()
+        .filter_map(|power_up| {
+            power_ups
+                .active
+                .iter()
+                .filter(|active| active.power_up == power_up)
+                .map(|active| active.remaining_seconds)
+                .reduce(f32::max)
+                .map(|seconds| {
+                    localizer.format(
+                        "power_up.remaining",
+                        &[
+                            &localizer.get(power_up.label_key()),
+                            &format!("{:.0}", seconds.ceil()),
+                        ],
+                    )
+                })
+        })
+        .collect::<Vec<_>>()
+        .join("    ");
+    for mut text in displays.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
@@ -0,0 +1,138 @@
+use bevy::{
+    app::AppExit, input::common_conditions::input_just_pressed, prelude::*,
+    sprite::MaterialMesh2dBundle, window::WindowCloseRequested,
+};
+
+use super::{locale::TextKey, text_style::TextStyler, AppState, BACKGROUND_COLOR, TEXT_COLOR};
+use crate::util::cleanup_system;
+
+// Quitting from a menu is instant, but quitting mid-run asks for confirmation first so a stray
+// Escape press (or window close) doesn't throw away a good run.
+pub struct QuitPlugin;
+
+impl Plugin for QuitPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<QuitConfirmState>()
+            .add_systems(Update, request_quit)
+            .add_systems(
+                Update,
+                (
+                    confirm_quit.run_if(input_just_pressed(KeyCode::Y)),
+                    cancel_quit.run_if(input_just_pressed(KeyCode::N)),
+                )
+                    .run_if(in_state(QuitConfirmState::Shown)),
+            )
+            .add_systems(OnEnter(QuitConfirmState::Shown), spawn_quit_dialog)
+            .add_systems(
+                OnExit(QuitConfirmState::Shown),
+                cleanup_system::<QuitDialog>,
+            )
+            .add_systems(OnExit(AppState::Playing), cancel_quit);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
+pub enum QuitConfirmState {
+    #[default]
+    Hidden,
+    Shown,
+}
+
+fn request_quit(
+    keys: Res<Input<KeyCode>>,
+    mut close_requests: EventReader<WindowCloseRequested>,
+    app_state: Res<State<AppState>>,
+    quit_state: Res<State<QuitConfirmState>>,
+    mut next_quit_state: ResMut<NextState<QuitConfirmState>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let close_requested = close_requests.iter().count() > 0;
+    let escape_pressed = keys.just_pressed(KeyCode::Escape);
+    if !close_requested && !escape_pressed {
+        return;
+    }
+
+    if app_state.get() != &AppState::Playing {
+        exit.send(AppExit);
+        return;
+    }
+
+    match quit_state.get() {
+        QuitConfirmState::Hidden => next_quit_state.set(QuitConfirmState::Shown),
+        // Pressing Escape again backs out of the dialog; closing the window again means it.
+        QuitConfirmState::Shown if close_requested => exit.send(AppExit),
+        QuitConfirmState::Shown => next_quit_state.set(QuitConfirmState::Hidden),
+    }
+}
+
+fn confirm_quit(mut exit: EventWriter<AppExit>) {
+    exit.send(AppExit);
+}
+
+fn cancel_quit(mut next_quit_state: ResMut<NextState<QuitConfirmState>>) {
+    next_quit_state.set(QuitConfirmState::Hidden);
+}
+
+#[derive(Component)]
+struct QuitDialog;
+
+fn spawn_quit_dialog(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    text_styler: TextStyler,
+) {
+    let text_style = TextStyle {
+        font: text_styler.font(),
+        font_size: 100.0,
+        color: TEXT_COLOR,
+    };
+
+    commands
+        .spawn((
+            MaterialMesh2dBundle {
+                transform: Transform::from_xyz(0.0, 2.0, 3.0),
+                mesh: meshes
+                    .add(
+                        shape::Quad {
+                            size: Vec2::new(5.0, 1.6),
+                            ..default()
+                        }
+                        .into(),
+                    )
+                    .into(),
+                material: materials.add(ColorMaterial::from(BACKGROUND_COLOR)),
+                ..default()
+            },
+            Name::new("QuitDialog"),
+            QuitDialog,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, 0.2, 0.1).with_scale(Vec3::splat(0.006)),
+                    text: Text {
+                        sections: vec![TextSection::new("", text_style.clone())],
+                        alignment: TextAlignment::Center,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    ..default()
+                },
+                TextKey("quit.prompt"),
+                Name::new("QuitPrompt"),
+            ));
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, -0.4, 0.1).with_scale(Vec3::splat(0.003)),
+                    text: Text {
+                        sections: vec![TextSection::new("", text_style.clone())],
+                        alignment: TextAlignment::Center,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    ..default()
+                },
+                TextKey("quit.instructions"),
+                Name::new("QuitInstructions"),
+            ));
+        });
+}
@@ -0,0 +1,175 @@
+use bevy::{ecs::query::WorldQuery, prelude::*};
+use bevy_xpbd_2d::prelude::*;
+
+use super::{
+    hazards::SawBlade,
+    spawn_level::{RopeJoint, RopeJointBuilder, RopeSegment},
+};
+
+// Loose ends held within this distance of each other, in meters, for this long are tied back
+// together.
+const RETIE_DISTANCE: f32 = 0.3;
+const RETIE_SECONDS: f32 = 1.0;
+
+// The rope can be cut, by saw blades or by pulling it too hard, splitting it into two chains.
+// Players tie it back together by holding the loose ends against each other for a moment.
+pub struct RopeCuttingPlugin;
+
+impl Plugin for RopeCuttingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SeveredJoints>()
+            .add_systems(Update, (cut_with_saws, retie_rope).chain());
+    }
+}
+
+/// The two bodies a rope joint held together, and where on each.
+#[derive(Debug, Clone, Copy)]
+pub struct JointEnds {
+    bodies: [Entity; 2],
+    anchors: [Vec2; 2],
+}
+
+impl From<&RevoluteJoint> for JointEnds {
+    fn from(joint: &RevoluteJoint) -> Self {
+        Self {
+            bodies: [joint.entity1, joint.entity2],
+            anchors: [joint.local_anchor1, joint.local_anchor2],
+        }
+    }
+}
+
+impl From<&PrismaticJoint> for JointEnds {
+    fn from(joint: &PrismaticJoint) -> Self {
+        Self {
+            bodies: [joint.entity1, joint.entity2],
+            anchors: [joint.local_anchor1, joint.local_anchor2],
+        }
+    }
+}
+
+/// A rope joint of whichever kind it was built from, see `RopeJointBuilder`.
+#[derive(WorldQuery)]
+pub struct AnyRopeJoint {
+    revolute: Option<&'static RevoluteJoint>,
+    prismatic: Option<&'static PrismaticJoint>,
+}
+
+impl AnyRopeJointItem<'_> {
+    pub fn ends(&self) -> Option<JointEnds> {
+        self.revolute
+            .map(JointEnds::from)
+            .or_else(|| self.prismatic.map(JointEnds::from))
+    }
+
+    /// In newtons, as of the latest physics step.
+    pub fn force(&self) -> f32 {
+        self.revolute
+            .map(|joint| joint.force)
+            .or_else(|| self.prismatic.map(|joint| joint.force))
+            .map_or(0.0, Vec2::length)
+    }
+}
+
+/// Every cut in the rope that hasn't been tied back together yet.
+#[derive(Resource, Default)]
+pub struct SeveredJoints(Vec<SeveredJoint>);
+
+struct SeveredJoint {
+    ends: JointEnds,
+    // How long the loose ends have been held together.
+    touching_seconds: f32,
+}
+
+impl SeveredJoints {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Removes a rope joint, remembering it so it can be tied again.
+    pub fn sever(&mut self, commands: &mut Commands, joint: Entity, ends: JointEnds) {
+        commands.entity(joint).despawn_recursive();
+        self.0.push(SeveredJoint {
+            ends,
+            touching_seconds: 0.0,
+        });
+    }
+}
+
+// Each saw blade cuts the rope once, at the first segment it touches.
+fn cut_with_saws(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionStarted>,
+    mut saws: Query<&mut SawBlade>,
+    segments: Query<(), With<RopeSegment>>,
+    joints: Query<(Entity, AnyRopeJoint), With<RopeJoint>>,
+    mut severed: ResMut<SeveredJoints>,
+) {
+    for CollisionStarted(a, b) in collisions.iter() {
+        let (saw, segment) = match saws.contains(*a) {
+            true => (*a, *b),
+            false => (*b, *a),
+        };
+        let Ok(mut saw) = saws.get_mut(saw) else {
+            continue;
+        };
+        if saw.has_cut || !segments.contains(segment) {
+            continue;
+        }
+        let Some((joint, ends)) = joints.iter().find_map(|(entity, joint)| {
+            let ends = joint.ends()?;
+            ends.bodies.contains(&segment).then_some((entity, ends))
+        }) else {
+            continue;
+        };
+        println!("A saw blade cut the rope");
+        severed.sever(&mut commands, joint, ends);
+        saw.has_cut = true;
+    }
+}
+
+fn retie_rope(
+    mut commands: Commands,
+    mut severed: ResMut<SeveredJoints>,
+    joint_builder: Res<RopeJointBuilder>,
+    bodies: Query<(&Position, &Rotation, Option<&Parent>)>,
+    time: Res<Time>,
+) {
+    let mut finished = Vec::new();
+    for (index, cut) in severed.0.iter_mut().enumerate() {
+        let [entity1, entity2] = cut.ends.bodies;
+        // The rope went with the level, so there's nothing left to tie.
+        let (Ok(first), Ok(second)) = (bodies.get(entity1), bodies.get(entity2)) else {
+            finished.push(index);
+            continue;
+        };
+        let anchor = |(position, rotation, _): (&Position, &Rotation, Option<&Parent>), local| {
+            position.0 + rotation.rotate(local)
+        };
+        let gap = anchor(first, cut.ends.anchors[0]).distance(anchor(second, cut.ends.anchors[1]));
+        cut.touching_seconds = match gap <= RETIE_DISTANCE {
+            true => cut.touching_seconds + time.delta_seconds(),
+            false => 0.0,
+        };
+        if cut.touching_seconds < RETIE_SECONDS {
+            continue;
+        }
+
+        println!("The rope is tied back together");
+        // Tied with the same kind of joint as the rest of the rope; the elastic rope mutator loosens
+        // it into a spring if it's on.
+        let joint = joint_builder.spawn(
+            &mut commands,
+            cut.ends.bodies,
+            cut.ends.anchors,
+            Name::new("Rope joint (retied)"),
+        );
+        // Alongside the rest of the rope, so it goes when the rope does.
+        if let Some(parent) = first.2 {
+            commands.entity(parent.get()).add_child(joint);
+        }
+        finished.push(index);
+    }
+    for index in finished.into_iter().rev() {
+        severed.0.remove(index);
+    }
+}
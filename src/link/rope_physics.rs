@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+use bevy_xpbd_2d::prelude::*;
+
+use super::spawn_level::{RopeBuild, RopeSegment};
+
+// How the rope's weight is spread along it and how readily it swings, all editable from the
+// inspector. Tapering puts more of the weight in the middle, so the middle sags and swings with
+// some heft while the ends, lighter, are easier for the cursors to whip around.
+pub struct RopePhysicsPlugin;
+
+impl Plugin for RopePhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RopePhysicsConfig>()
+            .init_resource::<RopePhysicsConfig>()
+            .add_systems(Update, apply_rope_damping)
+            // As for the cursors, xpbd works mass out from the collider, so the configured mass is
+            // put back after it does.
+            .add_systems(
+                FixedUpdate,
+                hold_rope_mass
+                    .after(PhysicsSet::Prepare)
+                    .before(PhysicsSet::StepSimulation),
+            );
+    }
+}
+
+/// Physical properties of every rope segment.
+#[derive(Resource, Reflect, Debug, Clone)]
+#[reflect(Resource)]
+pub struct RopePhysicsConfig {
+    /// In kilograms, of a segment at the ends of the rope.
+    pub segment_mass: f32,
+    /// How much heavier the segment in the middle of the rope is than those at the ends, as a
+    /// fraction of `segment_mass`; those in between are somewhere between. 0 for an even rope.
+    pub taper: f32,
+    pub angular_damping: f32,
+    pub friction: f32,
+}
+
+impl Default for RopePhysicsConfig {
+    // What the rope was before it was configurable: 0.15m by 0.05m segments of density 1, undamped,
+    // with xpbd's default friction.
+    fn default() -> Self {
+        Self {
+            segment_mass: 0.0075,
+            taper: 0.0,
+            angular_damping: 0.0,
+            friction: 0.3,
+        }
+    }
+}
+
+impl RopePhysicsConfig {
+    fn mass(&self, segment: &RopeSegment) -> f32 {
+        // 0 at either end of the rope, 1 in the middle.
+        let centrality = 1.0 - (segment.along * 2.0 - 1.0).abs();
+        self.segment_mass * (1.0 + self.taper * centrality)
+    }
+}
+
+fn apply_rope_damping(
+    mut commands: Commands,
+    config: Res<RopePhysicsConfig>,
+    segments: Query<Entity, With<RopeSegment>>,
+    added: Query<(), Added<RopeSegment>>,
+) {
+    if !config.is_changed() && added.is_empty() {
+        return;
+    }
+    for entity in segments.iter() {
+        commands.entity(entity).insert((
+            AngularDamping(config.angular_damping),
+            Friction::new(config.friction),
+        ));
+    }
+}
+
+fn hold_rope_mass(
+    config: Res<RopePhysicsConfig>,
+    build: Res<RopeBuild>,
+    mut segments: Query<(
+        &RopeSegment,
+        &Collider,
+        &mut Mass,
+        &mut InverseMass,
+        &mut Inertia,
+        &mut InverseInertia,
+    )>,
+) {
+    // A massless dynamic body would break the solver.
+    if config.segment_mass <= 0.0 || config.taper < -1.0 {
+        return;
+    }
+    for (segment, collider, mut mass, mut inverse_mass, mut inertia, mut inverse_inertia) in
+        segments.iter_mut()
+    {
+        let target = config.mass(segment) * build.segment_mass_scale;
+        // Only written when it differs, so change detection stays quiet.
+        if mass.0 == target {
+            continue;
+        }
+        // The segment's inertia scales with its mass, keeping the shape's own distribution.
+        let unit = ColliderMassProperties::new_computed(collider, 1.0);
+        let target_inertia = unit.inertia.0 * target / unit.mass.0;
+        mass.0 = target;
+        inverse_mass.0 = 1.0 / target;
+        inertia.0 = target_inertia;
+        inverse_inertia.0 = 1.0 / target_inertia;
+    }
+}
@@ -0,0 +1,265 @@
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use super::{
+    locale::{Localizer, TextKey},
+    spawn_level::RopeSegment,
+    stats::LifetimeStats,
+    text_style::TextStyler,
+    title_hints::TitleHint,
+    AppState, DespawnOnExitInit, TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+// Every rope skin, in the order they're listed. Skins with more than one color repeat them in
+// stripes along the rope.
+const SKINS: [RopeSkin; 5] = [
+    RopeSkin {
+        name_key: "rope_skin.player_colors",
+        colors: &[],
+        unlock: Unlock::Always,
+    },
+    RopeSkin {
+        name_key: "rope_skin.twine",
+        colors: &[Color::rgb(0.72, 0.58, 0.38)],
+        unlock: Unlock::GamesPlayed(5),
+    },
+    RopeSkin {
+        name_key: "rope_skin.candy_cane",
+        colors: &[Color::rgb(0.95, 0.95, 0.95), Color::rgb(0.85, 0.1, 0.15)],
+        unlock: Unlock::ShapesSorted(50),
+    },
+    RopeSkin {
+        name_key: "rope_skin.caution_tape",
+        colors: &[Color::rgb(0.95, 0.8, 0.1), Color::rgb(0.1, 0.1, 0.1)],
+        unlock: Unlock::BestCombo(10),
+    },
+    RopeSkin {
+        name_key: "rope_skin.gold",
+        colors: &[Color::rgb(1.0, 0.78, 0.2)],
+        unlock: Unlock::ShapesSorted(250),
+    },
+];
+
+// Press L on the title screen to choose how the rope looks, from skins unlocked by playing. Purely
+// cosmetic: the chosen skin recolors every segment of the rope, in place.
+pub struct RopeSkinPlugin;
+
+impl Plugin for RopeSkinPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RopeSkinChoice>()
+            .add_systems(OnEnter(AppState::Init), spawn_rope_skins_hint)
+            .add_systems(
+                Update,
+                toggle_rope_skins_menu
+                    .run_if(input_just_pressed(KeyCode::L))
+                    .run_if(in_state(AppState::Init).or_else(in_state(AppState::RopeSkins))),
+            )
+            .add_systems(OnEnter(AppState::RopeSkins), spawn_rope_skins_menu)
+            .add_systems(
+                Update,
+                (choose_rope_skin, display_rope_skins)
+                    .chain()
+                    .run_if(in_state(AppState::RopeSkins)),
+            )
+            .add_systems(OnExit(AppState::RopeSkins), cleanup_system::<RopeSkinsMenu>)
+            .add_systems(Update, apply_rope_skin);
+    }
+}
+
+struct RopeSkin {
+    // The language file key for the skin's name.
+    name_key: &'static str,
+    // No colors means each half of the rope keeps the color of the cursor it hangs from.
+    colors: &'static [Color],
+    unlock: Unlock,
+}
+
+/// What it takes, over every game played, to earn a skin.
+enum Unlock {
+    Always,
+    GamesPlayed(u32),
+    ShapesSorted(u32),
+    BestCombo(u32),
+}
+
+impl Unlock {
+    fn is_met(&self, stats: &LifetimeStats) -> bool {
+        match *self {
+            Unlock::Always => true,
+            Unlock::GamesPlayed(games) => stats.games_played >= games,
+            Unlock::ShapesSorted(shapes) => stats.shapes_sorted() >= shapes,
+            Unlock::BestCombo(combo) => stats.best_combo >= combo,
+        }
+    }
+
+    fn describe(&self, localizer: &Localizer) -> String {
+        match *self {
+            Unlock::Always => String::new(),
+            Unlock::GamesPlayed(games) => {
+                localizer.format("rope_skin.unlock.games", &[&games.to_string()])
+            }
+            Unlock::ShapesSorted(shapes) => {
+                localizer.format("rope_skin.unlock.shapes", &[&shapes.to_string()])
+            }
+            Unlock::BestCombo(combo) => {
+                localizer.format("rope_skin.unlock.combo", &[&combo.to_string()])
+            }
+        }
+    }
+}
+
+/// Which of `SKINS` the rope wears.
+#[derive(Resource, Debug, Default)]
+pub struct RopeSkinChoice(usize);
+
+// The material a segment was spawned with, to go back to when the skin is taken off.
+#[derive(Component)]
+struct PlayerColorMaterial(Handle<ColorMaterial>);
+
+#[derive(Component)]
+struct RopeSkinsMenu;
+
+#[derive(Component)]
+struct RopeSkinsList;
+
+fn toggle_rope_skins_menu(
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    next_state.set(match state.get() {
+        AppState::RopeSkins => AppState::Init,
+        _ => AppState::RopeSkins,
+    });
+}
+
+fn spawn_rope_skins_hint(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: TitleHint::RopeSkins.transform(),
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: TEXT_COLOR,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        DespawnOnExitInit,
+        TextKey("title.rope_skins_hint"),
+        Name::new("RopeSkinsHint"),
+    ));
+}
+
+fn spawn_rope_skins_menu(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 3.0, 1.0).with_scale(Vec3::splat(0.003)),
+            text_anchor: bevy::sprite::Anchor::TopCenter,
+            text: Text {
+                sections: vec![TextSection::new(
+                    "",
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: TEXT_COLOR,
+                    },
+                )],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        RopeSkinsMenu,
+        RopeSkinsList,
+        Name::new("RopeSkinsMenu"),
+    ));
+}
+
+// The number keys pick a skin by its place in the list.
+fn choose_rope_skin(
+    keys: Res<Input<KeyCode>>,
+    stats: Res<LifetimeStats>,
+    mut choice: ResMut<RopeSkinChoice>,
+) {
+    const NUMBER_KEYS: [KeyCode; 9] = [
+        KeyCode::Key1,
+        KeyCode::Key2,
+        KeyCode::Key3,
+        KeyCode::Key4,
+        KeyCode::Key5,
+        KeyCode::Key6,
+        KeyCode::Key7,
+        KeyCode::Key8,
+        KeyCode::Key9,
+    ];
+    for (index, (key, skin)) in NUMBER_KEYS.iter().zip(SKINS.iter()).enumerate() {
+        if keys.just_pressed(*key) && skin.unlock.is_met(&stats) && choice.0 != index {
+            choice.0 = index;
+        }
+    }
+}
+
+fn display_rope_skins(
+    choice: Res<RopeSkinChoice>,
+    stats: Res<LifetimeStats>,
+    localizer: Localizer,
+    mut lists: Query<&mut Text, With<RopeSkinsList>>,
+) {
+    let mut lines = vec![localizer.get("rope_skin.title"), String::new()];
+    lines.extend(SKINS.iter().enumerate().map(|(index, skin)| {
+        let status = match (index == choice.0, skin.unlock.is_met(&stats)) {
+            (true, _) => localizer.get("rope_skin.wearing"),
+            (false, true) => String::new(),
+            (false, false) => {
+                localizer.format("rope_skin.locked", &[&skin.unlock.describe(&localizer)])
+            }
+        };
+        format!("{}: {}{}", index + 1, localizer.get(skin.name_key), status)
+    }));
+    lines.extend([String::new(), localizer.get("rope_skin.back")]);
+    for mut text in lists.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+// Reapplied to the whole rope when the choice changes, and to segments as they spawn.
+fn apply_rope_skin(
+    mut commands: Commands,
+    choice: Res<RopeSkinChoice>,
+    segments: Query<(
+        Entity,
+        &RopeSegment,
+        &Handle<ColorMaterial>,
+        Option<&PlayerColorMaterial>,
+    )>,
+    added: Query<(), Added<RopeSegment>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !choice.is_changed() && added.is_empty() {
+        return;
+    }
+    let stripes = SKINS[choice.0]
+        .colors
+        .iter()
+        .map(|color| materials.add(ColorMaterial::from(*color)))
+        .collect::<Vec<_>>();
+    for (entity, segment, material, player_color) in segments.iter() {
+        let player_color = match player_color {
+            Some(player_color) => player_color.0.clone(),
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(PlayerColorMaterial(material.clone()));
+                material.clone()
+            }
+        };
+        let skinned = match stripes.is_empty() {
+            true => player_color,
+            false => stripes[segment.index as usize % stripes.len()].clone(),
+        };
+        commands.entity(entity).insert(skinned);
+    }
+}
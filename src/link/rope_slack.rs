@@ -0,0 +1,166 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_xpbd_2d::prelude::*;
+
+use super::{
+    locale::Localizer,
+    player::CursorSlot,
+    spawn_level::{Layer, RopeBuild, RopeSegment},
+    text_style::TextStyler,
+    AppState, TEXT_COLOR,
+};
+
+// With less slack than this, as a fraction of the rope's length, the rope reads as taut.
+const TAUT_SLACK: f32 = 0.05;
+// The readout sits this far below the middle of the rope, out of the way of shapes landing on it.
+const READOUT_OFFSET: f32 = -0.25;
+
+// A small readout under the middle of each rope during play, showing how much slack it has: how
+// much shorter the straight line between its cursors is than the rope itself. Helps players judge
+// when the rope is taut enough to catch a shape. A rope that's taut all along its length joins
+// `Layer::TautRope`, so it can carry boss shapes.
+pub struct RopeSlackPlugin;
+
+impl Plugin for RopeSlackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (mark_taut_ropes, display_rope_slack));
+    }
+}
+
+// Each rope's spans between neighbouring cursors, in order from its left end to its right, by the
+// rope's entity. Cursors on the same rope share a parent.
+fn rope_spans<'a>(
+    cursors: impl Iterator<Item = (&'a CursorSlot, &'a Transform, &'a Parent)>,
+) -> HashMap<Entity, Vec<(Vec2, Vec2)>> {
+    let mut chains = HashMap::<Entity, Vec<(CursorSlot, Vec2)>>::new();
+    for (slot, transform, parent) in cursors {
+        chains
+            .entry(parent.get())
+            .or_default()
+            .push((*slot, transform.translation.truncate()));
+    }
+    chains
+        .into_iter()
+        .map(|(rope, mut chain)| {
+            chain.sort_by_key(|(slot, _)| match *slot {
+                CursorSlot::LEFT => 0,
+                CursorSlot::RIGHT => u8::MAX,
+                CursorSlot(index) => index,
+            });
+            let spans = chain
+                .windows(2)
+                .map(|pair| (pair[0].1, pair[1].1))
+                .collect();
+            (rope, spans)
+        })
+        .collect()
+}
+
+// How much shorter the straight line between two cursors is than the rope between them, as a
+// fraction of its length.
+fn slack((start, end): (Vec2, Vec2), build: &RopeBuild) -> f32 {
+    (1.0 - start.distance(end) / build.length).max(0.0)
+}
+
+fn mark_taut_ropes(
+    cursors: Query<(&CursorSlot, &Transform, &Parent)>,
+    mut segments: Query<(&Parent, &mut CollisionLayers), With<RopeSegment>>,
+    build: Res<RopeBuild>,
+) {
+    let taut = rope_spans(cursors.iter())
+        .into_iter()
+        .map(|(rope, spans)| {
+            let taut = spans.iter().all(|span| slack(*span, &build) < TAUT_SLACK);
+            (rope, taut)
+        })
+        .collect::<HashMap<_, _>>();
+    for (parent, mut layers) in segments.iter_mut() {
+        let taut = taut.get(&parent.get()).copied().unwrap_or(false);
+        if layers.contains_group(Layer::TautRope) == taut {
+            continue;
+        }
+        *layers = match taut {
+            true => layers.add_group(Layer::TautRope),
+            false => layers.remove_group(Layer::TautRope),
+        };
+    }
+}
+
+#[derive(Component)]
+struct SlackReadout;
+
+fn display_rope_slack(
+    mut commands: Commands,
+    state: Res<State<AppState>>,
+    cursors: Query<(&CursorSlot, &Transform, &Parent)>,
+    segments: Query<(&RopeSegment, &Transform)>,
+    build: Res<RopeBuild>,
+    mut readouts: Query<
+        (Entity, &mut Text, &mut Transform),
+        (
+            With<SlackReadout>,
+            Without<CursorSlot>,
+            Without<RopeSegment>,
+        ),
+    >,
+    localizer: Localizer,
+    text_styler: TextStyler,
+) {
+    let shown = matches!(state.get(), AppState::Playing | AppState::Warmup);
+    let ropes = rope_spans(cursors.iter().filter(|_| shown))
+        .into_values()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    let mut readouts = readouts.iter_mut().collect::<Vec<_>>();
+    // One readout per rope, spawning or despawning them to match.
+    let kept = ropes.len().min(readouts.len());
+    for (entity, _, _) in readouts.drain(kept..) {
+        commands.entity(entity).despawn();
+    }
+    for (i, (start, end)) in ropes.into_iter().enumerate() {
+        let slack = slack((start, end), &build);
+        let label = match slack < TAUT_SLACK {
+            true => localizer.get("rope_slack.taut"),
+            false => localizer.format(
+                "rope_slack.slack",
+                &[&format!("{:.1}", slack * build.length)],
+            ),
+        };
+        // The middle of this rope is the middle segment nearest the midpoint between its cursors.
+        let between = (start + end) / 2.0;
+        let middle = segments
+            .iter()
+            .filter(|(segment, _)| (segment.along - 0.5).abs() < 0.1)
+            .map(|(_, transform)| transform.translation.truncate())
+            .min_by(|a, b| a.distance(between).total_cmp(&b.distance(between)))
+            .unwrap_or(between);
+        let translation = (middle + Vec2::new(0.0, READOUT_OFFSET)).extend(1.0);
+
+        match readouts.get_mut(i) {
+            Some((_, text, transform)) => {
+                text.sections[0].value = label;
+                transform.translation = translation;
+            }
+            None => {
+                commands.spawn((
+                    Text2dBundle {
+                        transform: Transform::from_translation(translation)
+                            .with_scale(Vec3::splat(0.002)),
+                        text: Text::from_section(
+                            label,
+                            TextStyle {
+                                font: text_styler.font(),
+                                font_size: 100.0,
+                                color: TEXT_COLOR.with_a(0.5),
+                            },
+                        )
+                        .with_alignment(TextAlignment::Center),
+                        ..default()
+                    },
+                    SlackReadout,
+                    Name::new("SlackReadout"),
+                ));
+            }
+        }
+    }
+}
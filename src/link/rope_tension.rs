@@ -0,0 +1,238 @@
+use bevy::prelude::*;
+use bevy_xpbd_2d::prelude::*;
+
+use super::{
+    elastic_rope::ElasticJoint,
+    gameplay::Score,
+    locale::TextKey,
+    rope_cutting::{AnyRopeJoint, SeveredJoints},
+    spawn_level::{RopeJoint, HEIGHT},
+    text_style::TextStyler,
+    AppState, BAD_COLOR, TEXT_COLOR,
+};
+
+// Launch with this flag to let the rope snap when it's pulled too hard.
+const BREAKABLE_FLAG: &str = "--breakable-rope";
+// The meter along the bottom of the screen, full when the rope is at its breaking point.
+const METER_SIZE: Vec2 = Vec2::new(3.0, 0.12);
+const METER_Y: f32 = -HEIGHT / 2.0 + 0.15;
+// A snap costs this many points, and so does every further SNAPPED_PENALTY_SECONDS the rope is
+// left in two.
+const SNAP_PENALTY: i32 = 5;
+const SNAPPED_PENALTY_SECONDS: f32 = 5.0;
+
+// Measures how hard the rope is being pulled, from the forces in the joints between its segments,
+// and shows it on a meter during play. With `--breakable-rope`, a joint pulled past
+// `RopeBreaking::breaking_force` snaps, leaving the players to tie it back together, see
+// `RopeCuttingPlugin`. Snapping costs `SNAP_PENALTY` points, and the same again every
+// `SNAPPED_PENALTY_SECONDS` until they've tied it.
+pub struct RopeTensionPlugin;
+
+impl Plugin for RopeTensionPlugin {
+    fn build(&self, app: &mut App) {
+        let breaking = RopeBreaking {
+            enabled: std::env::args().any(|arg| arg == BREAKABLE_FLAG),
+            ..default()
+        };
+        app.register_type::<RopeTension>()
+            .register_type::<RopeBreaking>()
+            .init_resource::<RopeTension>()
+            .insert_resource(breaking)
+            .add_systems(Startup, spawn_tension_meter)
+            .add_systems(
+                FixedUpdate,
+                (
+                    measure_rope_tension,
+                    snap_rope.run_if(in_state(AppState::Playing)),
+                )
+                    .chain()
+                    .after(PhysicsSet::StepSimulation),
+            )
+            .add_systems(
+                Update,
+                (
+                    penalize_snapped_rope.run_if(in_state(AppState::Playing)),
+                    display_tension_meter,
+                ),
+            );
+    }
+}
+
+/// The strongest pull on any joint along the rope in the latest physics step.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+pub struct RopeTension {
+    /// In newtons.
+    pub force: f32,
+    /// `force` as a fraction of `RopeBreaking::breaking_force`.
+    pub strain: f32,
+}
+
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct RopeBreaking {
+    pub enabled: bool,
+    /// In newtons. A gentle swing pulls a few, a violent flick a few dozen.
+    pub breaking_force: f32,
+    /// How long since the rope snapped, while it's still in two.
+    snapped_seconds: Option<f32>,
+}
+
+impl Default for RopeBreaking {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            breaking_force: 40.0,
+            snapped_seconds: None,
+        }
+    }
+}
+
+#[derive(Component)]
+struct TensionMeter;
+
+#[derive(Component)]
+struct TensionMeterFill;
+
+fn spawn_tension_meter(mut commands: Commands, text_styler: TextStyler) {
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: TEXT_COLOR.with_a(0.2),
+                    custom_size: Some(METER_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, METER_Y, 1.0),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            TensionMeter,
+            Name::new("TensionMeter"),
+        ))
+        .with_children(|meter| {
+            meter.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: TEXT_COLOR,
+                        custom_size: Some(METER_SIZE),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                    ..default()
+                },
+                TensionMeterFill,
+            ));
+            meter.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(-METER_SIZE.x / 2.0 - 0.1, 0.0, 0.1)
+                        .with_scale(Vec3::splat(0.002)),
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: text_styler.font(),
+                            font_size: 100.0,
+                            color: TEXT_COLOR,
+                        },
+                    )
+                    .with_alignment(TextAlignment::Right),
+                    text_anchor: bevy::sprite::Anchor::CenterRight,
+                    ..default()
+                },
+                TextKey("hud.tension"),
+            ));
+        });
+}
+
+fn measure_rope_tension(
+    mut tension: ResMut<RopeTension>,
+    breaking: Res<RopeBreaking>,
+    joints: Query<AnyRopeJoint, With<RopeJoint>>,
+) {
+    tension.force = joints.iter().map(|joint| joint.force()).fold(0.0, f32::max);
+    tension.strain = tension.force / breaking.breaking_force;
+}
+
+fn snap_rope(
+    mut commands: Commands,
+    tension: Res<RopeTension>,
+    mut breaking: ResMut<RopeBreaking>,
+    mut severed: ResMut<SeveredJoints>,
+    score: Option<ResMut<Score>>,
+    // An elastic rope stretches rather than snapping.
+    joints: Query<(Entity, AnyRopeJoint), (With<RopeJoint>, Without<ElasticJoint>)>,
+) {
+    // One break at a time, so a single yank doesn't shred the rope.
+    if !breaking.enabled || tension.strain < 1.0 || !severed.is_empty() {
+        return;
+    }
+    let Some((entity, ends)) = joints
+        .iter()
+        .max_by(|(_, a), (_, b)| a.force().total_cmp(&b.force()))
+        .and_then(|(entity, joint)| Some((entity, joint.ends()?)))
+    else {
+        return;
+    };
+    println!("The rope snapped under {:.1}N", tension.force);
+    severed.sever(&mut commands, entity, ends);
+    breaking.snapped_seconds = Some(0.0);
+    if let Some(mut score) = score {
+        score.snapped += SNAP_PENALTY;
+    }
+}
+
+fn penalize_snapped_rope(
+    mut breaking: ResMut<RopeBreaking>,
+    severed: Res<SeveredJoints>,
+    mut score: ResMut<Score>,
+    time: Res<Time>,
+) {
+    let Some(seconds) = breaking.snapped_seconds else {
+        return;
+    };
+    if severed.is_empty() {
+        breaking.snapped_seconds = None;
+        return;
+    }
+    let later = seconds + time.delta_seconds();
+    if (later / SNAPPED_PENALTY_SECONDS).floor() > (seconds / SNAPPED_PENALTY_SECONDS).floor() {
+        println!("The rope is still snapped, losing {} points", SNAP_PENALTY);
+        score.snapped += SNAP_PENALTY;
+    }
+    breaking.snapped_seconds = Some(later);
+}
+
+fn display_tension_meter(
+    tension: Res<RopeTension>,
+    breaking: Res<RopeBreaking>,
+    state: Res<State<AppState>>,
+    mut meters: Query<&mut Visibility, With<TensionMeter>>,
+    mut fills: Query<(&mut Sprite, &mut Transform), With<TensionMeterFill>>,
+) {
+    let shown = matches!(state.get(), AppState::Playing | AppState::Warmup);
+    for mut visibility in meters.iter_mut() {
+        *visibility = match shown {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+    }
+
+    let strain = tension.strain.clamp(0.0, 1.0);
+    // Only turns red where it means something: when the rope can actually snap.
+    let danger = match breaking.enabled {
+        true => strain,
+        false => 0.0,
+    };
+    for (mut sprite, mut transform) in fills.iter_mut() {
+        let [r, g, b, _] = TEXT_COLOR.as_rgba_f32();
+        let [bad_r, bad_g, bad_b, _] = BAD_COLOR.as_rgba_f32();
+        sprite.color = Color::rgb(
+            r + (bad_r - r) * danger,
+            g + (bad_g - g) * danger,
+            b + (bad_b - b) * danger,
+        );
+        transform.scale.x = strain;
+        // Grows from the left edge.
+        transform.translation.x = -METER_SIZE.x / 2.0 * (1.0 - strain);
+    }
+}
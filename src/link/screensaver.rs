@@ -0,0 +1,139 @@
+use bevy::{
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+use bevy_xpbd_2d::prelude::*;
+
+use super::{
+    spawn_level::{Layer, HEIGHT, WIDTH},
+    AppState, DespawnOnExitInit, TEXT_COLOR,
+};
+
+const SEGMENT_COUNT: u32 = 16;
+const SEGMENT_LENGTH: f32 = 0.25;
+const SEGMENT_GAP: f32 = 0.05;
+const SEGMENT_THICKNESS: f32 = 0.05;
+// The rope hangs from just above the top of the screen, behind the title.
+const ANCHOR: Vec2 = Vec2::new(0.0, HEIGHT / 2.0 + 0.2);
+// Wind is a slow swell plus faster gusts, as horizontal acceleration in meters per second squared.
+const WIND_SWELL: f32 = 4.0;
+const WIND_GUSTS: f32 = 2.5;
+
+// A purely decorative rope swaying in the wind behind the title text. It collides with nothing and
+// takes no input. Since it's simulated from the moment the game starts, it's also a quick visual
+// check that the physics is behaving.
+pub struct ScreensaverPlugin;
+
+impl Plugin for ScreensaverPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Init), spawn_screensaver_rope)
+            .add_systems(
+                FixedUpdate,
+                blow_wind
+                    .before(PhysicsSet::Prepare)
+                    .run_if(in_state(AppState::Init)),
+            )
+            .add_systems(
+                Update,
+                check_rope_stability.run_if(in_state(AppState::Init)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct ScreensaverSegment {
+    index: u32,
+}
+
+fn spawn_screensaver_rope(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let mesh: Mesh2dHandle = meshes
+        .add(
+            shape::Quad {
+                size: Vec2::new(SEGMENT_THICKNESS, SEGMENT_LENGTH),
+                ..default()
+            }
+            .into(),
+        )
+        .into();
+    let material = materials.add(ColorMaterial::from(TEXT_COLOR.with_a(0.3)));
+
+    let anchor_id = commands
+        .spawn((
+            TransformBundle::from_transform(Transform::from_xyz(ANCHOR.x, ANCHOR.y, 0.0)),
+            RigidBody::Static,
+            DespawnOnExitInit,
+            Name::new("ScreensaverAnchor"),
+        ))
+        .id();
+
+    let mut prev_id = anchor_id;
+    let mut prev_anchor = Vec2::ZERO;
+    let pitch = SEGMENT_LENGTH + SEGMENT_GAP;
+    for i in 0..SEGMENT_COUNT {
+        let center = ANCHOR - Vec2::new(0.0, pitch * (i as f32 + 0.5));
+        let segment_id = commands
+            .spawn((
+                MaterialMesh2dBundle {
+                    // Behind the title text and the level.
+                    transform: Transform::from_xyz(center.x, center.y, -0.5),
+                    mesh: mesh.clone(),
+                    material: material.clone(),
+                    ..default()
+                },
+                RigidBody::Dynamic,
+                Collider::cuboid(SEGMENT_THICKNESS, SEGMENT_LENGTH),
+                CollisionLayers::new([Layer::Decoration], []),
+                ExternalForce::default().with_persistence(false),
+                ScreensaverSegment { index: i },
+                DespawnOnExitInit,
+                Name::new(format!("Screensaver segment {}", i)),
+            ))
+            .id();
+        commands.spawn((
+            RevoluteJoint::new(prev_id, segment_id)
+                .with_local_anchor_1(prev_anchor)
+                .with_local_anchor_2(Vec2::new(0.0, pitch / 2.0)),
+            DespawnOnExitInit,
+            Name::new(format!("Screensaver joint {}", i)),
+        ));
+
+        prev_anchor = Vec2::new(0.0, -pitch / 2.0);
+        prev_id = segment_id;
+    }
+}
+
+fn blow_wind(
+    mut segments: Query<(&ScreensaverSegment, &Mass, &mut ExternalForce)>,
+    time: Res<Time>,
+) {
+    let t = time.elapsed_seconds();
+    let swell = WIND_SWELL * (t * 0.4).sin();
+    for (segment, mass, mut force) in segments.iter_mut() {
+        // Gusts travel down the rope, so it ripples rather than swinging stiffly.
+        let gust = WIND_GUSTS * (t * 1.7 - segment.index as f32 * 0.3).sin();
+        force.apply_force(Vec2::new(swell + gust, 0.0) * mass.0);
+    }
+}
+
+// The rope should never leave the screen. If it does, something is wrong with the simulation.
+fn check_rope_stability(
+    segments: Query<&Transform, With<ScreensaverSegment>>,
+    mut reported: Local<bool>,
+) {
+    if *reported {
+        return;
+    }
+    let bounds = Rect::new(-WIDTH, -HEIGHT, WIDTH, HEIGHT);
+    let unstable = segments.iter().any(|transform| {
+        let position = transform.translation.truncate();
+        !position.is_finite() || !bounds.contains(position)
+    });
+    if unstable {
+        println!("Screensaver rope left the screen, the physics simulation may be unstable");
+        *reported = true;
+    }
+}
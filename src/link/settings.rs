@@ -0,0 +1,654 @@
+use std::{f32::consts::TAU, fs};
+
+use bevy::{
+    audio::Volume,
+    input::common_conditions::input_just_pressed,
+    prelude::*,
+    window::{PrimaryWindow, WindowMode},
+};
+
+use super::{
+    aiming::AimingGuide,
+    locale::{Localizer, TextKey},
+    palette::PaletteChoice,
+    rope_physics::RopePhysicsConfig,
+    stalls::StallState,
+    text_style::TextStyler,
+    title_hints::TitleHint,
+    AppState, DespawnOnExitInit, BACKGROUND_COLOR, TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+const SETTINGS_PATH: &str = "settings.txt";
+
+const VOLUME_STEP: f32 = 0.1;
+// Changing a volume plays a short tone on its channel, so the new level can be heard.
+const PREVIEW_TONE_HZ: f32 = 440.0;
+const PREVIEW_TONE_SECONDS: f32 = 0.15;
+const PREVIEW_SAMPLE_RATE: u32 = 22050;
+const SENSITIVITY_STEP: f32 = 0.1;
+const MIN_SENSITIVITY: f32 = 0.2;
+const MAX_SENSITIVITY: f32 = 3.0;
+// With the heavier middle turned on, the rope's middle segment weighs this much more than its ends,
+// as a fraction of their mass, see `RopePhysicsConfig::taper`.
+const HEAVY_MIDDLE_TAPER: f32 = 2.0;
+// The window sizes to pick from, in pixels. Any other size can still be set in the settings file.
+const RESOLUTIONS: [UVec2; 5] = [
+    UVec2::new(1280, 720),
+    UVec2::new(1600, 900),
+    UVec2::new(1920, 1080),
+    UVec2::new(2560, 1440),
+    UVec2::new(3840, 2160),
+];
+const MIN_RESOLUTION: UVec2 = UVec2::new(640, 360);
+
+// Player preferences, kept across launches. Press F1 on the title screen to change them, or
+// mid-run to pause and change them, after which play resumes with the usual stall countdown.
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Settings>()
+            .insert_resource(Settings::load())
+            .add_systems(Startup, build_preview_tone)
+            .add_systems(Update, (apply_settings, apply_sound_volumes))
+            .add_systems(OnEnter(AppState::Init), spawn_settings_hint)
+            .add_systems(
+                Update,
+                toggle_settings_menu
+                    .run_if(input_just_pressed(KeyCode::F1))
+                    .run_if(in_state(AppState::Init).or_else(in_state(AppState::Settings))),
+            )
+            .add_systems(
+                Update,
+                toggle_pause_menu
+                    .run_if(input_just_pressed(KeyCode::F1))
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(in_state(StallState::Running).or_else(in_state(StallState::Settings))),
+            )
+            .add_systems(OnEnter(AppState::Settings), spawn_settings_menu)
+            .add_systems(
+                OnEnter(StallState::Settings),
+                (bevy_xpbd_2d::pause, spawn_settings_menu),
+            )
+            .add_systems(
+                Update,
+                (edit_settings, display_settings)
+                    .chain()
+                    .run_if(in_state(AppState::Settings).or_else(in_state(StallState::Settings))),
+            )
+            .add_systems(
+                OnExit(AppState::Settings),
+                (cleanup_system::<SettingsMenu>, save_settings),
+            )
+            .add_systems(
+                OnExit(StallState::Settings),
+                (
+                    bevy_xpbd_2d::resume,
+                    cleanup_system::<SettingsMenu>,
+                    save_settings,
+                ),
+            );
+    }
+}
+
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource)]
+pub struct Settings {
+    /// From 0 to 1, scaling every sound.
+    pub master_volume: f32,
+    /// From 0 to 1, scaling sound effects on top of `master_volume`.
+    pub effects_volume: f32,
+    /// From 0 to 1, scaling music on top of `master_volume`.
+    pub music_volume: f32,
+    /// Scales every mouse's motion, on top of its calibration.
+    pub sensitivity: f32,
+    pub window_mode: WindowChoice,
+    /// The size of the window in pixels, or of the screen in exclusive fullscreen. Borderless
+    /// fullscreen always covers the whole monitor. The arena is scaled to fit either way.
+    pub resolution: UVec2,
+    pub palette: PaletteChoice,
+    /// Draw patterns over the shapes, see `PalettePlugin`.
+    pub shape_patterns: bool,
+    /// Show the aiming guide, see `AimingGuidePlugin`.
+    pub aiming_guide: bool,
+    /// Weight the middle of the rope, see `HEAVY_MIDDLE_TAPER`.
+    pub heavy_rope_middle: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            effects_volume: 1.0,
+            music_volume: 1.0,
+            sensitivity: 1.0,
+            window_mode: WindowChoice::Windowed,
+            resolution: UVec2::new(1600, 900),
+            palette: PaletteChoice::Standard,
+            shape_patterns: false,
+            aiming_guide: true,
+            heavy_rope_middle: false,
+        }
+    }
+}
+
+impl Settings {
+    fn load() -> Self {
+        let mut settings = Self::default();
+        let Ok(contents) = fs::read_to_string(SETTINGS_PATH) else {
+            return settings;
+        };
+        for line in contents.lines() {
+            let applied = line
+                .split_once('=')
+                .is_some_and(|(key, value)| settings.set(key, value));
+            if !applied {
+                println!("Skipping malformed settings line: {}", line);
+            }
+        }
+        settings
+    }
+
+    /// How loud a sound on the channel plays, on top of `master_volume`.
+    pub fn channel_volume(&self, channel: SoundChannel) -> f32 {
+        match channel {
+            SoundChannel::Effects => self.effects_volume,
+            SoundChannel::Music => self.music_volume,
+        }
+    }
+
+    /// Playback settings for a new sound on the channel, at that channel's volume. `GlobalVolume`
+    /// takes care of the master volume.
+    pub fn playback(&self, channel: SoundChannel, playback: PlaybackSettings) -> PlaybackSettings {
+        playback.with_volume(Volume::new_relative(self.channel_volume(channel)))
+    }
+
+    // Sets one of the settings from its saved form, and says whether it was understood.
+    fn set(&mut self, key: &str, value: &str) -> bool {
+        let volume = || {
+            value
+                .parse::<f32>()
+                .ok()
+                .map(|volume| volume.clamp(0.0, 1.0))
+        };
+        let flag = || value.parse::<bool>().ok();
+        let applied = match key {
+            "master_volume" => volume().map(|volume| self.master_volume = volume),
+            "effects_volume" => volume().map(|volume| self.effects_volume = volume),
+            "music_volume" => volume().map(|volume| self.music_volume = volume),
+            "sensitivity" => value.parse::<f32>().ok().map(|sensitivity| {
+                self.sensitivity = sensitivity.clamp(MIN_SENSITIVITY, MAX_SENSITIVITY)
+            }),
+            "window_mode" => WindowChoice::ALL
+                .into_iter()
+                .find(|choice| choice.key() == value)
+                .map(|choice| self.window_mode = choice),
+            "resolution" => value
+                .split_once('x')
+                .and_then(|(width, height)| {
+                    Some(UVec2::new(width.parse().ok()?, height.parse().ok()?))
+                })
+                .map(|resolution| self.resolution = resolution.max(MIN_RESOLUTION)),
+            "palette" => PaletteChoice::ALL
+                .into_iter()
+                .find(|choice| choice.key() == value)
+                .map(|choice| self.palette = choice),
+            "shape_patterns" => flag().map(|on| self.shape_patterns = on),
+            "aiming_guide" => flag().map(|on| self.aiming_guide = on),
+            "heavy_rope_middle" => flag().map(|on| self.heavy_rope_middle = on),
+            _ => None,
+        };
+        applied.is_some()
+    }
+
+    fn save(&self) {
+        let contents = [
+            format!("master_volume={}", self.master_volume),
+            format!("effects_volume={}", self.effects_volume),
+            format!("music_volume={}", self.music_volume),
+            format!("sensitivity={}", self.sensitivity),
+            format!("window_mode={}", self.window_mode.key()),
+            format!("resolution={}x{}", self.resolution.x, self.resolution.y),
+            format!("palette={}", self.palette.key()),
+            format!("shape_patterns={}", self.shape_patterns),
+            format!("aiming_guide={}", self.aiming_guide),
+            format!("heavy_rope_middle={}", self.heavy_rope_middle),
+        ]
+        .join("\n");
+        if let Err(error) = fs::write(SETTINGS_PATH, contents + "\n") {
+            println!("Failed to save settings: {}", error);
+        }
+    }
+}
+
+/// Which volume setting a sound follows, on top of the master volume. Spawn every sound with one,
+/// and its `PlaybackSettings` from `Settings::playback`, so it keeps following the volume while
+/// it plays.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundChannel {
+    Effects,
+    Music,
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowChoice {
+    Windowed,
+    /// A window without decorations covering the whole monitor, at the monitor's own resolution.
+    Borderless,
+    /// Takes over the monitor, switching it to the chosen resolution.
+    Fullscreen,
+}
+
+impl WindowChoice {
+    const ALL: [WindowChoice; 3] = [
+        WindowChoice::Windowed,
+        WindowChoice::Borderless,
+        WindowChoice::Fullscreen,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            WindowChoice::Windowed => "windowed",
+            WindowChoice::Borderless => "borderless",
+            WindowChoice::Fullscreen => "fullscreen",
+        }
+    }
+
+    // The key of its name, see `Localizer`.
+    fn label_key(self) -> &'static str {
+        match self {
+            WindowChoice::Windowed => "settings.window.windowed",
+            WindowChoice::Borderless => "settings.window.borderless",
+            WindowChoice::Fullscreen => "settings.window.fullscreen",
+        }
+    }
+
+    fn mode(self) -> WindowMode {
+        match self {
+            WindowChoice::Windowed => WindowMode::Windowed,
+            WindowChoice::Borderless => WindowMode::BorderlessFullscreen,
+            WindowChoice::Fullscreen => WindowMode::SizedFullscreen,
+        }
+    }
+
+    fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|choice| *choice == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+// The next preset resolution up or down from the given one, which needn't be a preset itself.
+// Stops at the smallest and largest.
+fn step_resolution(resolution: UVec2, up: bool) -> UVec2 {
+    let pixels = |size: UVec2| size.x * size.y;
+    let stepped = match up {
+        true => RESOLUTIONS
+            .into_iter()
+            .find(|preset| pixels(*preset) > pixels(resolution)),
+        false => RESOLUTIONS
+            .into_iter()
+            .rev()
+            .find(|preset| pixels(*preset) < pixels(resolution)),
+    };
+    stepped.unwrap_or(resolution)
+}
+
+// The setting each row of the menu edits, in the order they're listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingsRow {
+    MasterVolume,
+    EffectsVolume,
+    MusicVolume,
+    Sensitivity,
+    WindowMode,
+    Resolution,
+    Palette,
+    ShapePatterns,
+    AimingGuide,
+    HeavyRopeMiddle,
+}
+
+impl SettingsRow {
+    const ALL: [SettingsRow; 10] = [
+        SettingsRow::MasterVolume,
+        SettingsRow::EffectsVolume,
+        SettingsRow::MusicVolume,
+        SettingsRow::Sensitivity,
+        SettingsRow::WindowMode,
+        SettingsRow::Resolution,
+        SettingsRow::Palette,
+        SettingsRow::ShapePatterns,
+        SettingsRow::AimingGuide,
+        SettingsRow::HeavyRopeMiddle,
+    ];
+    // The tenth row is on 0, as on the keyboard.
+    const KEYS: [KeyCode; 10] = [
+        KeyCode::Key1,
+        KeyCode::Key2,
+        KeyCode::Key3,
+        KeyCode::Key4,
+        KeyCode::Key5,
+        KeyCode::Key6,
+        KeyCode::Key7,
+        KeyCode::Key8,
+        KeyCode::Key9,
+        KeyCode::Key0,
+    ];
+
+    fn describe(self, settings: &Settings, localizer: &Localizer) -> String {
+        let on_off = |on: bool| match on {
+            true => localizer.get("settings.on"),
+            false => localizer.get("settings.off"),
+        };
+        let percent = |volume: f32| format!("{:.0}", volume * 100.0);
+        let (key, value) = match self {
+            SettingsRow::MasterVolume => {
+                ("settings.master_volume", percent(settings.master_volume))
+            }
+            SettingsRow::EffectsVolume => {
+                ("settings.effects_volume", percent(settings.effects_volume))
+            }
+            SettingsRow::MusicVolume => ("settings.music_volume", percent(settings.music_volume)),
+            SettingsRow::Sensitivity => (
+                "settings.sensitivity",
+                format!("{:.1}", settings.sensitivity),
+            ),
+            SettingsRow::WindowMode => (
+                "settings.window_mode",
+                localizer.get(settings.window_mode.label_key()),
+            ),
+            SettingsRow::Resolution => (
+                "settings.resolution",
+                format!("{}x{}", settings.resolution.x, settings.resolution.y),
+            ),
+            SettingsRow::Palette => (
+                "settings.palette",
+                localizer.get(settings.palette.label_key()),
+            ),
+            SettingsRow::ShapePatterns => {
+                ("settings.shape_patterns", on_off(settings.shape_patterns))
+            }
+            SettingsRow::AimingGuide => ("settings.aiming_guide", on_off(settings.aiming_guide)),
+            SettingsRow::HeavyRopeMiddle => (
+                "settings.heavy_rope_middle",
+                on_off(settings.heavy_rope_middle),
+            ),
+        };
+        localizer.format(key, &[&value])
+    }
+
+    // The channel a change to this row is previewed on, if it's a volume.
+    fn channel(self) -> Option<SoundChannel> {
+        match self {
+            SettingsRow::MasterVolume | SettingsRow::EffectsVolume => Some(SoundChannel::Effects),
+            SettingsRow::MusicVolume => Some(SoundChannel::Music),
+            _ => None,
+        }
+    }
+
+    // Steps the setting down or up; anything with only a few choices just moves to the next one.
+    fn adjust(self, settings: &mut Settings, up: bool) {
+        let step = |value: f32, by: f32| match up {
+            true => value + by,
+            false => value - by,
+        };
+        match self {
+            SettingsRow::MasterVolume => {
+                settings.master_volume = step(settings.master_volume, VOLUME_STEP).clamp(0.0, 1.0)
+            }
+            SettingsRow::EffectsVolume => {
+                settings.effects_volume = step(settings.effects_volume, VOLUME_STEP).clamp(0.0, 1.0)
+            }
+            SettingsRow::MusicVolume => {
+                settings.music_volume = step(settings.music_volume, VOLUME_STEP).clamp(0.0, 1.0)
+            }
+            SettingsRow::Sensitivity => {
+                settings.sensitivity = step(settings.sensitivity, SENSITIVITY_STEP)
+                    .clamp(MIN_SENSITIVITY, MAX_SENSITIVITY)
+            }
+            SettingsRow::WindowMode => settings.window_mode = settings.window_mode.next(),
+            SettingsRow::Resolution => {
+                settings.resolution = step_resolution(settings.resolution, up)
+            }
+            SettingsRow::Palette => settings.palette = settings.palette.next(),
+            SettingsRow::ShapePatterns => settings.shape_patterns = !settings.shape_patterns,
+            SettingsRow::AimingGuide => settings.aiming_guide = !settings.aiming_guide,
+            SettingsRow::HeavyRopeMiddle => {
+                settings.heavy_rope_middle = !settings.heavy_rope_middle
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct SettingsMenu;
+
+#[derive(Component)]
+struct SettingsList {
+    selected: SettingsRow,
+}
+
+fn toggle_settings_menu(state: Res<State<AppState>>, mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(match state.get() {
+        AppState::Settings => AppState::Init,
+        _ => AppState::Settings,
+    });
+}
+
+fn toggle_pause_menu(state: Res<State<StallState>>, mut next_state: ResMut<NextState<StallState>>) {
+    next_state.set(match state.get() {
+        StallState::Settings => StallState::Resuming,
+        _ => StallState::Settings,
+    });
+}
+
+fn spawn_settings_hint(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: TitleHint::Settings.transform(),
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: TEXT_COLOR,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        DespawnOnExitInit,
+        TextKey("title.settings_hint"),
+        Name::new("SettingsHint"),
+    ));
+}
+
+// Mid-run the menu is drawn over the playfield, so it gets a backdrop.
+fn spawn_settings_menu(mut commands: Commands, text_styler: TextStyler) {
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: BACKGROUND_COLOR.with_a(0.95),
+                    custom_size: Some(Vec2::new(9.0, 6.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.3, 4.0),
+                ..default()
+            },
+            SettingsMenu,
+            Name::new("SettingsMenu"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, 2.7, 0.1).with_scale(Vec3::splat(0.003)),
+                    text_anchor: bevy::sprite::Anchor::TopCenter,
+                    text: Text {
+                        sections: vec![TextSection::new(
+                            "",
+                            TextStyle {
+                                font: text_styler.font(),
+                                font_size: 100.0,
+                                color: TEXT_COLOR,
+                            },
+                        )],
+                        alignment: TextAlignment::Left,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    ..default()
+                },
+                SettingsList {
+                    selected: SettingsRow::MasterVolume,
+                },
+                Name::new("SettingsList"),
+            ));
+        });
+}
+
+fn edit_settings(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    mut settings: ResMut<Settings>,
+    preview_tone: Res<PreviewTone>,
+    mut lists: Query<&mut SettingsList>,
+) {
+    for mut list in lists.iter_mut() {
+        let picked = SettingsRow::KEYS
+            .into_iter()
+            .zip(SettingsRow::ALL)
+            .find(|(key, _)| keys.just_pressed(*key));
+        if let Some((_, row)) = picked {
+            list.selected = row;
+        }
+        if keys.just_pressed(KeyCode::BracketLeft) {
+            list.selected.adjust(&mut settings, false);
+        }
+        if keys.just_pressed(KeyCode::BracketRight) {
+            list.selected.adjust(&mut settings, true);
+        }
+        let adjusted = keys.any_just_pressed([KeyCode::BracketLeft, KeyCode::BracketRight]);
+        if let Some(channel) = list.selected.channel().filter(|_| adjusted) {
+            commands.spawn((
+                AudioBundle {
+                    source: preview_tone.0.clone(),
+                    settings: settings.playback(channel, PlaybackSettings::DESPAWN),
+                },
+                channel,
+                Name::new("PreviewTone"),
+            ));
+        }
+    }
+}
+
+fn display_settings(
+    settings: Res<Settings>,
+    localizer: Localizer,
+    mut lists: Query<(&SettingsList, &mut Text)>,
+) {
+    for (list, mut text) in lists.iter_mut() {
+        let mut lines = vec![localizer.get("settings.title"), String::new()];
+        for (index, row) in SettingsRow::ALL.into_iter().enumerate() {
+            let marker = match row == list.selected {
+                true => ">",
+                false => " ",
+            };
+            lines.push(format!(
+                "{} {}: {}",
+                marker,
+                (index + 1) % 10,
+                row.describe(&settings, &localizer)
+            ));
+        }
+        lines.push(String::new());
+        lines.push(localizer.get("settings.instructions"));
+        lines.push(localizer.get("settings.back"));
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+fn save_settings(settings: Res<Settings>) {
+    settings.save();
+}
+
+#[derive(Resource)]
+struct PreviewTone(Handle<AudioSource>);
+
+// A sine wave fading out over the tone's length, as a 16 bit mono wav.
+fn build_preview_tone(mut commands: Commands, mut sources: ResMut<Assets<AudioSource>>) {
+    let samples = (PREVIEW_TONE_SECONDS * PREVIEW_SAMPLE_RATE as f32) as u32;
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + samples * 2).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    // Uncompressed, one channel.
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&PREVIEW_SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(PREVIEW_SAMPLE_RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(samples * 2).to_le_bytes());
+    for sample in 0..samples {
+        let seconds = sample as f32 / PREVIEW_SAMPLE_RATE as f32;
+        let fade = 1.0 - sample as f32 / samples as f32;
+        let level = (seconds * PREVIEW_TONE_HZ * TAU).sin() * fade * 0.5;
+        wav.extend_from_slice(&((level * i16::MAX as f32) as i16).to_le_bytes());
+    }
+    let tone = sources.add(AudioSource { bytes: wav.into() });
+    commands.insert_resource(PreviewTone(tone));
+}
+
+// New sounds pick up their volume from `Settings::playback`; sounds already playing are turned up
+// or down here. A sink's volume ignores `GlobalVolume`, so the master volume is applied too.
+fn apply_sound_volumes(settings: Res<Settings>, sinks: Query<(&SoundChannel, &AudioSink)>) {
+    if !settings.is_changed() {
+        return;
+    }
+    for (channel, sink) in sinks.iter() {
+        sink.set_volume(settings.master_volume * settings.channel_volume(*channel));
+    }
+}
+
+// Runs once at startup too, when the loaded settings are first added, which sizes the window. The
+// guide can still be toggled on its own with G, until the settings next change.
+fn apply_settings(
+    settings: Res<Settings>,
+    mut global_volume: ResMut<GlobalVolume>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut guide: ResMut<AimingGuide>,
+    mut rope_config: ResMut<RopePhysicsConfig>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    *global_volume = GlobalVolume::new(settings.master_volume);
+    for mut window in windows.iter_mut() {
+        let mode = settings.window_mode.mode();
+        if window.mode != mode {
+            window.mode = mode;
+        }
+        // Borderless fullscreen takes the monitor's size, which resizing would fight with.
+        let resolution = settings.resolution;
+        let current = UVec2::new(window.physical_width(), window.physical_height());
+        if mode != WindowMode::BorderlessFullscreen && current != resolution {
+            window
+                .resolution
+                .set_physical_resolution(resolution.x, resolution.y);
+            if mode == WindowMode::Windowed {
+                window.position.center(MonitorSelection::Current);
+            }
+        }
+    }
+    guide.enabled = settings.aiming_guide;
+    rope_config.taper = match settings.heavy_rope_middle {
+        true => HEAVY_MIDDLE_TAPER,
+        false => 0.0,
+    };
+}
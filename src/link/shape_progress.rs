@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+
+use super::{
+    game_mode::shape_limited,
+    gameplay::LevelState,
+    locale::Localizer,
+    spawn_level::{HEIGHT, OUTER_WALL_THICKNESS, WIDTH},
+    text_style::TextStyler,
+    AppState, TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+// The bar runs along the top wall, stopping short of the corners.
+const BAR_SIZE: Vec2 = Vec2::new(WIDTH - 1.0, 0.08);
+const BAR_Y: f32 = HEIGHT / 2.0 - OUTER_WALL_THICKNESS / 2.0;
+// The count sits just under the wall, in the middle of the inlet.
+const COUNT_Y: f32 = HEIGHT / 2.0 - OUTER_WALL_THICKNESS - 0.12;
+
+// Shows how many of the level's shapes are still to come, as a number and as a bar along the top
+// wall which empties as they spawn, so players can pace themselves. Only levels with a fixed number
+// of shapes have one; timed and endless runs have their clock and lives instead.
+pub struct ShapeProgressPlugin;
+
+impl Plugin for ShapeProgressPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::Playing),
+            spawn_shape_progress.run_if(shape_limited),
+        )
+        .add_systems(
+            Update,
+            display_shape_progress
+                .run_if(in_state(AppState::Playing))
+                .run_if(resource_exists::<LevelState>()),
+        )
+        .add_systems(OnExit(AppState::Playing), cleanup_system::<ShapeProgress>);
+    }
+}
+
+#[derive(Component)]
+struct ShapeProgress;
+
+#[derive(Component)]
+struct ShapeProgressFill;
+
+#[derive(Component)]
+struct ShapeCount;
+
+fn spawn_shape_progress(mut commands: Commands, text_styler: TextStyler) {
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: TEXT_COLOR.with_a(0.2),
+                    custom_size: Some(BAR_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, BAR_Y, 1.0),
+                ..default()
+            },
+            ShapeProgress,
+            Name::new("ShapeProgress"),
+        ))
+        .with_children(|bar| {
+            bar.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: TEXT_COLOR,
+                        custom_size: Some(BAR_SIZE),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                    ..default()
+                },
+                ShapeProgressFill,
+            ));
+            bar.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, COUNT_Y - BAR_Y, 0.1)
+                        .with_scale(Vec3::splat(0.0018)),
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: text_styler.font(),
+                            font_size: 100.0,
+                            color: TEXT_COLOR,
+                        },
+                    )
+                    .with_alignment(TextAlignment::Center),
+                    ..default()
+                },
+                ShapeCount,
+            ));
+        });
+}
+
+fn display_shape_progress(
+    level_state: Res<LevelState>,
+    localizer: Localizer,
+    mut fills: Query<&mut Transform, With<ShapeProgressFill>>,
+    mut counts: Query<&mut Text, With<ShapeCount>>,
+) {
+    let remaining = level_state.num_shapes_remaining();
+    let fullness = match level_state.num_shapes() {
+        0 => 0.0,
+        num_shapes => (remaining as f32 / num_shapes as f32).min(1.0),
+    };
+    for mut transform in fills.iter_mut() {
+        transform.scale.x = fullness;
+        // Empties towards the left edge.
+        transform.translation.x = -BAR_SIZE.x / 2.0 * (1.0 - fullness);
+    }
+    let label = match remaining {
+        1 => localizer.get("hud.one_shape_left"),
+        remaining => localizer.format("hud.shapes_left", &[&remaining.to_string()]),
+    };
+    for mut text in counts.iter_mut() {
+        if text.sections[0].value != label {
+            text.sections[0].value = label.clone();
+        }
+    }
+}
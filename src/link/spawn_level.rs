@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use bevy::{
     prelude::*,
@@ -7,23 +7,58 @@ use bevy::{
 use bevy_xpbd_2d::prelude::*;
 
 use super::{
-    gameplay::ScoreDisplay,
-    player::{Cursor, LeftCursor, PIDController, RightCursor, TargetVelocity},
-    AppState, DespawnOnExitGameOver, DespawnOnExitInit, BAD_COLOR, LEFT_COLOR, RIGHT_COLOR,
-    TEXT_COLOR,
+    gameplay::{Bin, Score, ScoreDisplay},
+    leaderboard::Leaderboard,
+    levels::{CurrentLevel, LevelDefinition},
+    locale::TextKey,
+    mutators::Mutators,
+    palette::{Palette, PlayerMaterials},
+    player::{
+        AbsoluteTarget, Cursor, CursorSlot, PIDController, PlayerCount, SmoothedVelocity,
+        TargetVelocity,
+    },
+    stats::RunStats,
+    text_style::TextStyler,
+    title_hints::TitleHint,
+    upgrades::RopeUpgrades,
+    AppState, DespawnOnExitGameOver, DespawnOnExitInit, BACKGROUND_COLOR, BAD_COLOR, TEXT_COLOR,
 };
 use crate::util::path::{Path, WindDirection};
 
+// Launch with e.g. `--rope-joint=distance` to build the rope from a different kind of joint, see
+// `RopeJointBuilder`.
+const ROPE_JOINT_FLAG: &str = "--rope-joint=";
+
 pub struct SpawnPlugin;
 
 impl Plugin for SpawnPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(SpawnState::Settling), spawn_level)
+        app.insert_resource(RopeJointBuilder::from_args())
+            .init_resource::<RopeLayout>()
+            .init_resource::<RopeBuild>()
+            .add_systems(OnEnter(SpawnState::Settling), spawn_level)
             .add_state::<SpawnState>()
             .insert_resource(SettleTimer(Timer::from_seconds(0.05, TimerMode::Once)))
             .add_systems(Startup, bevy_xpbd_2d::pause)
             .add_systems(OnExit(SpawnState::Settling), bevy_xpbd_2d::resume)
             .add_systems(Update, exit_spawning.run_if(in_state(SpawnState::Settling)))
+            .add_systems(
+                Update,
+                (
+                    update_rope_build,
+                    respawn_cursors.run_if(in_state(SpawnState::Done)).run_if(
+                        in_state(AppState::Init)
+                            .or_else(in_state(AppState::Mutators))
+                            .or_else(in_state(AppState::GameOver)),
+                    ),
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                respawn_walls.run_if(not(in_state(AppState::GameOver))),
+            )
+            .add_systems(OnEnter(AppState::Init), spawn_title_screen)
             .add_systems(OnEnter(AppState::GameOver), spawn_game_over_screen);
     }
 }
@@ -60,59 +95,55 @@ const TOP: f32 = HEIGHT / 2.0;
 const LEFT: f32 = -WIDTH / 2.0;
 const RIGHT: f32 = WIDTH / 2.0;
 
-pub const SHAPE_SPAWN_REGION: Rect = Rect {
-    min: Vec2::new(-3.0, 5.0),
-    max: Vec2::new(3.0, 6.0),
-};
 pub const PLAY_REGION: Rect = Rect {
     min: Vec2::new(LEFT, BOTTOM - 1.0),
     max: Vec2::new(RIGHT, TOP),
 };
-pub const SHAPE_ALIVE_REGION: Rect = Rect {
-    min: Vec2::new(SHAPE_SPAWN_REGION.min.x, PLAY_REGION.max.y),
-    max: SHAPE_SPAWN_REGION.max,
-};
 
-const OUTER_WALL_THICKNESS: f32 = 0.25;
+// Neighbouring cursors are joined by a rope of this length, in meters, before any upgrades.
+const ROPE_LENGTH: f32 = 4.0;
 
-const BIN_WIDTH: f32 = 1.35;
-const BIN_BOTTOM: f32 = BOTTOM + 0.4;
-const BIN_TOP: f32 = 0.0;
-pub const LEFT_SCORE_REGION: Rect = Rect {
-    min: Vec2::new(LEFT + OUTER_WALL_THICKNESS, BIN_BOTTOM),
-    max: Vec2::new(LEFT + OUTER_WALL_THICKNESS + BIN_WIDTH, BIN_TOP),
-};
+pub const OUTER_WALL_THICKNESS: f32 = 0.25;
 
-pub const RIGHT_SCORE_REGION: Rect = Rect {
-    min: Vec2::new(RIGHT - OUTER_WALL_THICKNESS - BIN_WIDTH, BIN_BOTTOM),
-    max: Vec2::new(RIGHT - OUTER_WALL_THICKNESS, BIN_TOP),
-};
+// The bins' floors are the same on every level; the rest of their layout comes from the level, see
+// `LevelDefinition`. The center bin is a cup standing in the middle of the drain, with a gap down
+// either side of it for shapes that miss.
+pub const BIN_BOTTOM: f32 = BOTTOM + 0.4;
+pub const CENTER_BIN_WALL_THICKNESS: f32 = 0.15;
+const PADDLE_THICKNESS: f32 = 0.15;
 
 pub fn spawn_level(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    asset_server: Res<AssetServer>,
+    palette: Res<Palette>,
+    player_materials: Res<PlayerMaterials>,
+    text_styler: TextStyler,
+    player_count: Res<PlayerCount>,
+    layout: Res<RopeLayout>,
+    build: Res<RopeBuild>,
+    joint_builder: Res<RopeJointBuilder>,
+    current_level: CurrentLevel,
 ) {
-    let left_color = materials.add(ColorMaterial::from(LEFT_COLOR));
-    let right_color = materials.add(ColorMaterial::from(RIGHT_COLOR));
-    let bad_color = materials.add(ColorMaterial::from(BAD_COLOR));
-
+    let _span = info_span!("spawn_level").entered();
     spawn_cursors(
         &mut commands,
         &mut meshes,
-        left_color.clone(),
-        right_color.clone(),
+        &player_materials,
+        player_count.0,
+        *layout,
+        *joint_builder,
+        &build,
+        &HashMap::new(),
     );
     spawn_walls(
         &mut commands,
         &mut meshes,
-        left_color,
-        right_color,
-        bad_color,
+        &mut materials,
+        &palette,
+        current_level.get(),
     );
-    spawn_score_displays(&mut commands, &asset_server);
-    spawn_title_screen(&mut commands, &asset_server);
+    spawn_score_displays(&mut commands, &text_styler);
 }
 
 #[derive(PhysicsLayer)]
@@ -121,102 +152,272 @@ pub enum Layer {
     Level,
     Shapes,
     PlayerBlocker,
+    Hazards,
+    // Scenery which is simulated but touches nothing, like the title screen's rope.
+    Decoration,
+    // Joined by the segments of a rope pulled taut, the only rope a boss shape collides with, see
+    // `RopeSlackPlugin`.
+    TautRope,
+}
+
+/// What the rope is built with, gathered from the mutators and the run's upgrades. The rope is
+/// respawned whenever it changes.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct RopeBuild {
+    /// Between neighbouring cursors, in meters.
+    pub length: f32,
+    pub net: bool,
+    /// Scales `RopePhysicsConfig::segment_mass`.
+    pub segment_mass_scale: f32,
 }
 
+impl Default for RopeBuild {
+    fn default() -> Self {
+        Self {
+            length: ROPE_LENGTH,
+            net: false,
+            segment_mass_scale: 1.0,
+        }
+    }
+}
+
+fn update_rope_build(
+    mutators: Res<Mutators>,
+    upgrades: Res<RopeUpgrades>,
+    mut build: ResMut<RopeBuild>,
+) {
+    if !mutators.is_changed() && !upgrades.is_changed() {
+        return;
+    }
+    build.set_if_neq(RopeBuild {
+        length: ROPE_LENGTH + upgrades.extra_length(),
+        net: mutators.rope_net || upgrades.mid_net,
+        segment_mass_scale: upgrades.segment_mass_scale(),
+    });
+}
+
+// Respawns the cursors and rope when what they're built with changes, each cursor still held by
+// whoever held it before.
+fn respawn_cursors(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    player_materials: Res<PlayerMaterials>,
+    player_count: Res<PlayerCount>,
+    layout: Res<RopeLayout>,
+    build: Res<RopeBuild>,
+    joint_builder: Res<RopeJointBuilder>,
+    players: Query<Entity, With<Player>>,
+    cursors: Query<(&CursorSlot, &Cursor)>,
+) {
+    if !build.is_changed() || build.is_added() {
+        return;
+    }
+    let devices = cursors
+        .iter()
+        .filter_map(|(slot, cursor)| Some((*slot, cursor.0?)))
+        .collect();
+    for player in players.iter() {
+        commands.entity(player).despawn_recursive();
+    }
+    spawn_cursors(
+        &mut commands,
+        &mut meshes,
+        &player_materials,
+        player_count.0,
+        *layout,
+        *joint_builder,
+        &build,
+        &devices,
+    );
+}
+
+#[derive(Component)]
+struct Player;
+
+/// Which rope a player's cursors and rope belong to, on the entity they're all children of. There's
+/// only one rope, team 0, except in the double rope layout.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Team(pub u8);
+
+/// How the cursors are strung together.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RopeLayout {
+    /// Every cursor on one rope.
+    #[default]
+    Single,
+    /// Two ropes of two cursors each, one per team, see `DoubleRopePlugin`.
+    Double,
+}
+
+/// The optional net hanging from the middle of each rope, see `RopeBuild::net`.
+#[derive(Component)]
+pub struct RopeNet;
+
 fn spawn_cursors(
-    mut commands: &mut Commands,
-    mut meshes: &mut ResMut<Assets<Mesh>>,
-    left_color: Handle<ColorMaterial>,
-    right_color: Handle<ColorMaterial>,
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    player_materials: &PlayerMaterials,
+    player_count: u8,
+    layout: RopeLayout,
+    joint_builder: RopeJointBuilder,
+    build: &RopeBuild,
+    devices: &HashMap<CursorSlot, u32>,
 ) {
-    // Spawns a rope of this length between two cursor-controlled objects.
-    const ROPE_LENGTH: f32 = 4.0;
-    // The rope is spawned in a shallow V shape, with this angle to the horizontal.
+    // Each rope is spawned in a shallow V shape, with this angle to the horizontal.
     // Horizontal is a physically impossible configuration.
     const RELAX_ANGLE_RAD: f32 = 0.4;
 
-    let width = ROPE_LENGTH * RELAX_ANGLE_RAD.cos();
-    let left_pos = Vec2::new(-width / 2.0, 0.0);
-    let right_pos = Vec2::new(width / 2.0, 0.0);
-    let v_bottom = Vec2::new(0.0, -ROPE_LENGTH * RELAX_ANGLE_RAD.sin() / 2.0);
+    let link_width = build.length * RELAX_ANGLE_RAD.cos();
+    let v_depth = Vec2::new(0.0, -build.length * RELAX_ANGLE_RAD.sin() / 2.0);
 
-    let player_id = commands
-        .spawn((Name::new("Player"), SpatialBundle::default()))
-        .id();
+    // Each rope's cursors from left to right, and where the rope is centered.
+    let ropes = match layout {
+        // The left and right cursors hold the ends of the rope; any others hold it in between.
+        RopeLayout::Single => {
+            let mut slots = vec![CursorSlot::LEFT];
+            slots.extend((2..player_count).map(CursorSlot));
+            slots.push(CursorSlot::RIGHT);
+            vec![(slots, 0.0)]
+        }
+        // One rope on each side of the playfield, each held by a left and a middle cursor.
+        RopeLayout::Double => vec![
+            (vec![CursorSlot::LEFT, CursorSlot(2)], -WIDTH / 4.0),
+            (vec![CursorSlot(3), CursorSlot::RIGHT], WIDTH / 4.0),
+        ],
+    };
 
-    let cursor_size = 0.3;
-    let left_cursor_mesh: Mesh2dHandle = meshes
-        .add(
-            shape::Quad {
-                size: Vec2::new(cursor_size, cursor_size),
-                ..default()
-            }
-            .into(),
-        )
-        .into();
-    let right_cursor_mesh: Mesh2dHandle = meshes
-        .add(
-            shape::Circle {
-                radius: cursor_size / 2.0,
-                ..default()
+    for (team, (slots, center_x)) in ropes.into_iter().enumerate() {
+        let player_id = commands
+            .spawn((
+                Name::new("Player"),
+                Player,
+                Team(team as u8),
+                SpatialBundle::default(),
+            ))
+            .id();
+        let left_x = center_x - link_width * (slots.len() - 1) as f32 / 2.0;
+        spawn_chain(
+            commands,
+            meshes,
+            player_materials,
+            player_id,
+            joint_builder,
+            build.net,
+            devices,
+            slots
+                .into_iter()
+                .enumerate()
+                .map(|(i, slot)| (slot, Vec2::new(left_x + i as f32 * link_width, 0.0))),
+            v_depth,
+        );
+    }
+}
+
+// Spawns cursors at the given positions, each joined to the next by a rope hanging in a V.
+fn spawn_chain(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    player_materials: &PlayerMaterials,
+    player_id: Entity,
+    joint_builder: RopeJointBuilder,
+    net: bool,
+    devices: &HashMap<CursorSlot, u32>,
+    cursors: impl Iterator<Item = (CursorSlot, Vec2)>,
+    v_depth: Vec2,
+) {
+    let mut previous: Option<(Entity, Vec2, Handle<ColorMaterial>)> = None;
+    for (slot, pos) in cursors {
+        let color = player_materials.get(slot);
+        // Each half of a rope takes the color of the cursor it hangs from.
+        let connect_to = match previous {
+            Some((previous_cursor, previous_pos, previous_color)) => {
+                let v_bottom = (previous_pos + pos) / 2.0 + v_depth;
+                let middle_rope = spawn_rope(
+                    commands,
+                    meshes,
+                    player_id,
+                    joint_builder,
+                    previous_color.clone(),
+                    previous_pos,
+                    v_bottom,
+                    (0.0, 0.5),
+                    10,
+                    previous_cursor,
+                    Vec2::ZERO,
+                );
+                if net {
+                    spawn_rope_net(
+                        commands,
+                        meshes,
+                        player_id,
+                        previous_color,
+                        v_bottom,
+                        middle_rope,
+                    );
+                }
+                Some(spawn_rope(
+                    commands,
+                    meshes,
+                    player_id,
+                    joint_builder,
+                    color.clone(),
+                    v_bottom,
+                    pos,
+                    (0.5, 1.0),
+                    10,
+                    middle_rope.0,
+                    middle_rope.1,
+                ))
             }
-            .into(),
-        )
-        .into();
-    let left_cursor = spawn_cursor::<LeftCursor>(
-        &mut commands,
-        left_cursor_mesh,
-        player_id,
-        left_color.clone(),
-        left_pos,
-        None,
-        "Left Cursor",
-    );
-    let middle_rope = spawn_rope(
-        &mut commands,
-        &mut meshes,
-        player_id,
-        left_color,
-        left_pos,
-        v_bottom,
-        10,
-        left_cursor,
-        Vec2::ZERO,
-    );
-    let last_rope = spawn_rope(
-        &mut commands,
-        &mut meshes,
-        player_id,
-        right_color.clone(),
-        v_bottom,
-        right_pos,
-        10,
-        middle_rope.0,
-        middle_rope.1,
-    );
-    spawn_cursor::<RightCursor>(
-        &mut commands,
-        right_cursor_mesh,
-        player_id,
-        right_color,
-        right_pos,
-        Some(last_rope),
-        "Right Cursor",
-    );
+            None => None,
+        };
+        let mesh = cursor_mesh(meshes, slot);
+        let cursor = spawn_cursor(
+            commands,
+            mesh,
+            player_id,
+            joint_builder,
+            color.clone(),
+            pos,
+            connect_to,
+            slot,
+            devices.get(&slot).copied(),
+        );
+        previous = Some((cursor, pos, color));
+    }
 }
 
-fn spawn_cursor<T>(
+// Every cursor has its own shape as well as its own color.
+fn cursor_mesh(meshes: &mut ResMut<Assets<Mesh>>, slot: CursorSlot) -> Mesh2dHandle {
+    const CURSOR_SIZE: f32 = 0.3;
+    let mesh: Mesh = match slot.0 {
+        0 => shape::Quad {
+            size: Vec2::new(CURSOR_SIZE, CURSOR_SIZE),
+            ..default()
+        }
+        .into(),
+        1 => shape::Circle {
+            radius: CURSOR_SIZE / 2.0,
+            ..default()
+        }
+        .into(),
+        2 => shape::RegularPolygon::new(CURSOR_SIZE / 2.0, 3).into(),
+        _ => shape::RegularPolygon::new(CURSOR_SIZE / 2.0, 6).into(),
+    };
+    meshes.add(mesh).into()
+}
+
+fn spawn_cursor(
     commands: &mut Commands,
     mesh: Mesh2dHandle,
     player_id: Entity,
+    joint_builder: RopeJointBuilder,
     color: Handle<ColorMaterial>,
     start_pos: Vec2,
     connect_to: Option<(Entity, Vec2)>,
-    name: &str,
-) -> Entity
-where
-    T: Component + Default,
-{
+    slot: CursorSlot,
+    device: Option<u32>,
+) -> Entity {
     let cursor_size = 0.3;
     let cursor_id = commands
         .spawn((
@@ -228,53 +429,142 @@ where
             },
             RigidBody::Dynamic,
             TargetVelocity(Vec2::ZERO),
-            PIDController {
-                p: 1.0,
-                i: 1.0,
-                d: 0.0,
-                max_positional_error: 3.0,
-                max_integral_error: 0.5,
-                prev_error: Vec2::ZERO,
-                integral_error: Vec2::ZERO,
-            },
+            SmoothedVelocity::default(),
+            AbsoluteTarget::default(),
+            // Gains are filled in from `CursorGains`.
+            PIDController::default(),
             LinearVelocity::default(),
             ExternalForce::default().with_persistence(false),
             LockedAxes::ROTATION_LOCKED,
             Collider::cuboid(cursor_size, cursor_size),
             CollisionLayers::new(
                 [Layer::Rope],
-                [Layer::Level, Layer::Shapes, Layer::PlayerBlocker],
+                [
+                    Layer::Level,
+                    Layer::Shapes,
+                    Layer::PlayerBlocker,
+                    Layer::Hazards,
+                ],
             ),
-            Cursor(None),
-            T::default(),
-            Name::new(name.to_owned()),
+            Cursor(device),
+            slot,
+            Name::new(format!("Cursor ({})", slot.label())),
         ))
         .id();
 
     commands.entity(player_id).push_children(&[cursor_id]);
 
     if let Some((entity, prev_anchor)) = connect_to {
-        let joint_id = commands
-            .spawn((
-                RevoluteJoint::new(entity, cursor_id)
-                    .with_local_anchor_1(prev_anchor)
-                    .with_local_anchor_2(Vec2::new(0.0, 0.0)),
-                Name::new("Rope joint final"),
-            ))
-            .id();
+        let joint_id = joint_builder.spawn(
+            commands,
+            [entity, cursor_id],
+            [prev_anchor, Vec2::ZERO],
+            Name::new("Rope joint final"),
+        );
         commands.entity(player_id).push_children(&[joint_id]);
     };
 
     return cursor_id;
 }
 
+/// One link of the rope, numbered from the end it hangs from.
+#[derive(Component)]
+pub struct RopeSegment {
+    pub index: u32,
+    /// How far along the rope between two cursors the segment is, from 0 at one to 1 at the other.
+    pub along: f32,
+}
+
+/// Marks the joints holding the rope together, as opposed to anything else joined to it.
+#[derive(Component)]
+pub struct RopeJoint;
+
+/// How the rope's links are joined to each other and to the cursors. Rope feel experiments swap
+/// this rather than the code that lays the rope out.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub enum RopeJointBuilder {
+    /// Pinned end to end, free to bend but not to stretch.
+    #[default]
+    Revolute,
+    /// Pinned end to end through a spring with this compliance, so the distance between links
+    /// stretches under load and pulls back.
+    Distance { compliance: f32 },
+    /// Free to slide up to `max_stretch` apart along the rope, but held in line.
+    Prismatic { max_stretch: f32 },
+}
+
+impl RopeJointBuilder {
+    fn from_args() -> Self {
+        let Some(value) =
+            std::env::args().find_map(|arg| arg.strip_prefix(ROPE_JOINT_FLAG).map(str::to_owned))
+        else {
+            return Self::default();
+        };
+        match value.as_str() {
+            "revolute" => Self::Revolute,
+            "distance" => Self::Distance { compliance: 0.001 },
+            "prismatic" => Self::Prismatic { max_stretch: 0.05 },
+            _ => {
+                println!(
+                    "Unknown rope joint '{}', expected revolute, distance or prismatic",
+                    value
+                );
+                Self::default()
+            }
+        }
+    }
+
+    /// The compliance of the joints this builds, for putting it back after it's been changed.
+    pub fn compliance(&self) -> f32 {
+        match *self {
+            Self::Distance { compliance } => compliance,
+            Self::Revolute | Self::Prismatic { .. } => 0.0,
+        }
+    }
+
+    /// Spawns a rope joint between the two bodies, at the given anchors on each.
+    pub fn spawn(
+        &self,
+        commands: &mut Commands,
+        [entity1, entity2]: [Entity; 2],
+        [anchor1, anchor2]: [Vec2; 2],
+        name: Name,
+    ) -> Entity {
+        let mut joint = commands.spawn((RopeJoint, name));
+        match *self {
+            Self::Revolute => joint.insert(
+                RevoluteJoint::new(entity1, entity2)
+                    .with_local_anchor_1(anchor1)
+                    .with_local_anchor_2(anchor2),
+            ),
+            Self::Distance { compliance } => joint.insert(
+                RevoluteJoint::new(entity1, entity2)
+                    .with_local_anchor_1(anchor1)
+                    .with_local_anchor_2(anchor2)
+                    .with_compliance(compliance),
+            ),
+            Self::Prismatic { max_stretch } => joint.insert(
+                PrismaticJoint::new(entity1, entity2)
+                    .with_local_anchor_1(anchor1)
+                    .with_local_anchor_2(anchor2)
+                    .with_free_axis(Vec2::X)
+                    .with_limits(0.0, max_stretch),
+            ),
+        };
+        joint.id()
+    }
+}
+
 fn spawn_rope(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     player_id: Entity,
+    joint_builder: RopeJointBuilder,
     color: Handle<ColorMaterial>,
     start_pos: Vec2,
     end_pos: Vec2,
+    // The part of the rope between two cursors this spans, see `RopeSegment::along`.
+    (along_start, along_end): (f32, f32),
     num_segments: u32,
     parent_id: Entity,
     parent_anchor: Vec2,
@@ -313,21 +603,29 @@ fn spawn_rope(
                 Collider::cuboid(body_length, THICKNESS),
                 CollisionLayers::new(
                     [Layer::Rope],
-                    [Layer::Level, Layer::Shapes, Layer::PlayerBlocker],
+                    [
+                        Layer::Level,
+                        Layer::Shapes,
+                        Layer::PlayerBlocker,
+                        Layer::Hazards,
+                    ],
                 ),
+                RopeSegment {
+                    index: i,
+                    along: along_start
+                        + (along_end - along_start) * (i as f32 + 0.5) / num_segments as f32,
+                },
                 Name::new(format!("Rope segment {}", i)),
             ))
             .id();
         commands.entity(player_id).push_children(&[current_id]);
 
-        let joint_id = commands
-            .spawn((
-                RevoluteJoint::new(prev_id, current_id)
-                    .with_local_anchor_1(prev_anchor)
-                    .with_local_anchor_2(Vec2::new(-(body_length + GAP) / 2.0, 0.0)),
-                Name::new(format!("Rope joint {}", i)),
-            ))
-            .id();
+        let joint_id = joint_builder.spawn(
+            commands,
+            [prev_id, current_id],
+            [prev_anchor, Vec2::new(-(body_length + GAP) / 2.0, 0.0)],
+            Name::new(format!("Rope joint {}", i)),
+        );
         commands.entity(player_id).push_children(&[joint_id]);
 
         prev_anchor = Vec2::new((body_length + GAP) / 2.0, 0.0);
@@ -336,28 +634,143 @@ fn spawn_rope(
     return (prev_id, prev_anchor);
 }
 
+// A small open-topped basket, sitting on the rope's midpoint with its floor level with the rope,
+// just big enough to cradle one shape.
+fn spawn_rope_net(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    player_id: Entity,
+    color: Handle<ColorMaterial>,
+    pos: Vec2,
+    (rope_segment, rope_anchor): (Entity, Vec2),
+) {
+    const NET_WIDTH: f32 = 0.6;
+    const NET_DEPTH: f32 = 0.3;
+    const NET_THICKNESS: f32 = 0.04;
+
+    let mut outline = Path::new();
+    outline.move_to(Vec2::new(-NET_WIDTH / 2.0, NET_DEPTH));
+    outline.line_to(Vec2::new(-NET_WIDTH / 2.0, 0.0));
+    outline.line_to(Vec2::new(NET_WIDTH / 2.0, 0.0));
+    outline.line_to(Vec2::new(NET_WIDTH / 2.0, NET_DEPTH));
+    outline.line_to(Vec2::new(NET_WIDTH / 2.0 - NET_THICKNESS, NET_DEPTH));
+    outline.line_to(Vec2::new(NET_WIDTH / 2.0 - NET_THICKNESS, NET_THICKNESS));
+    outline.line_to(Vec2::new(-NET_WIDTH / 2.0 + NET_THICKNESS, NET_THICKNESS));
+    outline.line_to(Vec2::new(-NET_WIDTH / 2.0 + NET_THICKNESS, NET_DEPTH));
+    outline.close();
+
+    let net_id = commands
+        .spawn((
+            MaterialMesh2dBundle {
+                transform: Transform::from_xyz(pos.x, pos.y, 0.0),
+                mesh: meshes.add(outline.build_triangle_mesh()).into(),
+                material: color,
+                ..default()
+            },
+            RigidBody::Dynamic,
+            outline.build_collider(),
+            CollisionLayers::new(
+                [Layer::Rope],
+                [
+                    Layer::Level,
+                    Layer::Shapes,
+                    Layer::PlayerBlocker,
+                    Layer::Hazards,
+                ],
+            ),
+            RopeNet,
+            Name::new("Rope net"),
+        ))
+        .id();
+    commands.entity(player_id).push_children(&[net_id]);
+
+    // Held upright by the rope, so a cradled shape stays put until the rope tips it out. Not a
+    // `RopeJoint`: the net isn't part of the rope, so it neither stretches nor snaps.
+    let joint_id = commands
+        .spawn((
+            FixedJoint::new(rope_segment, net_id)
+                .with_local_anchor_1(rope_anchor)
+                .with_local_anchor_2(Vec2::ZERO),
+            Name::new("Rope net joint"),
+        ))
+        .id();
+    commands.entity(player_id).push_children(&[joint_id]);
+}
+
+// Marks the walls and bins, which are rebuilt whenever the level changes.
+#[derive(Component)]
+struct LevelGeometry;
+
+/// The wall whose color shows which shape a bin accepts, recolored when the bins are swapped, see
+/// `BinSwapPlugin`.
+#[derive(Component)]
+pub struct BinWall(pub Bin);
+
+/// Covers the inside of a bin, so shapes passing in and out of it can be told apart by their
+/// collisions. Resized to take in any margin around the bin, see `BinMargin`.
+#[derive(Component)]
+pub struct BinSensor(pub Bin);
+
+// Between games, the walls are swapped out for the next level's. They're also rebuilt once the
+// level's file has loaded, replacing the built-in layout used until then.
+fn respawn_walls(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    palette: Res<Palette>,
+    current_level: CurrentLevel,
+    mut level_events: EventReader<AssetEvent<LevelDefinition>>,
+    geometry: Query<Entity, With<LevelGeometry>>,
+) {
+    let loaded = level_events
+        .iter()
+        .filter(|event| current_level.is_loaded_by(event))
+        .count()
+        > 0;
+    if !current_level.is_changed() && !loaded {
+        return;
+    }
+    for entity in geometry.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_walls(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &palette,
+        current_level.get(),
+    );
+}
+
 fn spawn_walls(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
-    left_color: Handle<ColorMaterial>,
-    right_color: Handle<ColorMaterial>,
-    bad_color: Handle<ColorMaterial>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    palette: &Palette,
+    level: &LevelDefinition,
 ) {
-    let drain_width: f32 = 2.0;
-    let inlet_width: f32 = 8.0;
+    let left_color = materials.add(ColorMaterial::from(palette.left));
+    let right_color = materials.add(ColorMaterial::from(palette.right));
+    let center_color = materials.add(ColorMaterial::from(palette.third));
+    let bad_color = materials.add(ColorMaterial::from(BAD_COLOR));
+
+    let bin_width = level.bin_width;
+    let bin_top = level.bin_top;
+    let drain_width = level.drain_width;
+    let inlet_width = level.inlet_width;
     let playfield_wall_thickness: f32 = 0.4;
     let playfield_width: f32 =
-        WIDTH - (OUTER_WALL_THICKNESS + playfield_wall_thickness + BIN_WIDTH) * 2.0;
+        WIDTH - (OUTER_WALL_THICKNESS + playfield_wall_thickness + bin_width) * 2.0;
 
     let mut left_side = Path::new();
     left_side.move_to(Vec2::new(LEFT, BOTTOM));
     left_side.line_to(Vec2::new(-drain_width / 2.0, BOTTOM));
     left_side.line_to(Vec2::new(-drain_width / 2.0, BOTTOM + OUTER_WALL_THICKNESS));
     left_side.line_to(Vec2::new(-playfield_width / 2.0, BOTTOM + 1.0));
-    left_side.line_to(Vec2::new(-playfield_width / 2.0, BIN_TOP));
+    left_side.line_to(Vec2::new(-playfield_width / 2.0, bin_top));
     left_side.line_to(Vec2::new(
         -playfield_width / 2.0 - playfield_wall_thickness,
-        BIN_TOP,
+        bin_top,
     ));
     left_side.line_to(Vec2::new(
         -playfield_width / 2.0 - playfield_wall_thickness,
@@ -380,6 +793,8 @@ fn spawn_walls(
             material: left_color,
             ..default()
         },
+        LevelGeometry,
+        BinWall(Bin::Left),
         CollisionLayers::new([Layer::Level], [Layer::Rope, Layer::Shapes]),
     ));
 
@@ -390,29 +805,29 @@ fn spawn_walls(
     right_side.line_to(Vec2::new(playfield_width / 2.0, BOTTOM + 1.0));
     right_side.line_to(Vec2::new(
         playfield_width / 2.0,
-        BIN_TOP - playfield_wall_thickness / 2.0,
+        bin_top - playfield_wall_thickness / 2.0,
     ));
     right_side.arc_to(
         Vec2::new(
             playfield_width / 2.0 + playfield_wall_thickness,
-            BIN_TOP - playfield_wall_thickness / 2.0,
+            bin_top - playfield_wall_thickness / 2.0,
         ),
         Vec2::new(
             playfield_width / 2.0 + playfield_wall_thickness / 2.0,
-            BIN_TOP - playfield_wall_thickness / 2.0,
+            bin_top - playfield_wall_thickness / 2.0,
         ),
         10,
         WindDirection::Clockwise,
     );
     right_side.line_to(Vec2::new(
         playfield_width / 2.0 + playfield_wall_thickness,
-        BIN_BOTTOM + BIN_WIDTH / 2.0,
+        BIN_BOTTOM + bin_width / 2.0,
     ));
     right_side.arc_to(
-        Vec2::new(RIGHT - OUTER_WALL_THICKNESS, BIN_BOTTOM + BIN_WIDTH / 2.0),
+        Vec2::new(RIGHT - OUTER_WALL_THICKNESS, BIN_BOTTOM + bin_width / 2.0),
         Vec2::new(
-            playfield_width / 2.0 + playfield_wall_thickness + BIN_WIDTH / 2.0,
-            BIN_BOTTOM + BIN_WIDTH / 2.0,
+            playfield_width / 2.0 + playfield_wall_thickness + bin_width / 2.0,
+            BIN_BOTTOM + bin_width / 2.0,
         ),
         10,
         WindDirection::CounterClockwise,
@@ -434,6 +849,8 @@ fn spawn_walls(
             material: right_color,
             ..default()
         },
+        LevelGeometry,
+        BinWall(Bin::Right),
         CollisionLayers::new([Layer::Level], [Layer::Rope, Layer::Shapes]),
     ));
 
@@ -456,9 +873,40 @@ fn spawn_walls(
             material: bad_color.clone(),
             ..default()
         },
+        LevelGeometry,
         CollisionLayers::new([Layer::PlayerBlocker], [Layer::Rope]),
     ));
 
+    let center_outer = level.center_bin_width / 2.0 + CENTER_BIN_WALL_THICKNESS;
+    let center_inner = level.center_bin_width / 2.0;
+    let center_floor = BIN_BOTTOM + CENTER_BIN_WALL_THICKNESS;
+    let center_top = BIN_BOTTOM + level.center_bin_height;
+    let mut center_bin = Path::new();
+    center_bin.move_to(Vec2::new(-center_outer, center_top));
+    center_bin.line_to(Vec2::new(-center_outer, BIN_BOTTOM));
+    center_bin.line_to(Vec2::new(center_outer, BIN_BOTTOM));
+    center_bin.line_to(Vec2::new(center_outer, center_top));
+    center_bin.line_to(Vec2::new(center_inner, center_top));
+    center_bin.line_to(Vec2::new(center_inner, center_floor));
+    center_bin.line_to(Vec2::new(-center_inner, center_floor));
+    center_bin.line_to(Vec2::new(-center_inner, center_top));
+    center_bin.close();
+
+    commands.spawn((
+        Name::new("CenterBin"),
+        RigidBody::Static,
+        center_bin.build_collider(),
+        MaterialMesh2dBundle {
+            transform: Transform::from_xyz(0.0, 0.0, 0.0),
+            mesh: meshes.add(center_bin.build_triangle_mesh()).into(),
+            material: center_color,
+            ..default()
+        },
+        LevelGeometry,
+        BinWall(Bin::Center),
+        CollisionLayers::new([Layer::Level], [Layer::Rope, Layer::Shapes]),
+    ));
+
     // Prevent the player from passing through the drain.
     commands.spawn((
         Name::new("DrainBlock"),
@@ -478,13 +926,56 @@ fn spawn_walls(
             material: bad_color,
             ..default()
         },
+        LevelGeometry,
         CollisionLayers::new([Layer::PlayerBlocker], [Layer::Rope]),
     ));
+
+    for bin in Bin::ALL {
+        let region = bin.region(level);
+        commands.spawn((
+            Name::new(format!("{:?}BinSensor", bin)),
+            RigidBody::Static,
+            Collider::cuboid(region.width(), region.height()),
+            Sensor,
+            TransformBundle::from_transform(Transform::from_translation(
+                region.center().extend(0.0),
+            )),
+            LevelGeometry,
+            BinSensor(bin),
+            CollisionLayers::new([Layer::Level], [Layer::Shapes]),
+        ));
+    }
+
+    let paddle_color = materials.add(ColorMaterial::from(TEXT_COLOR.with_a(0.6)));
+    for paddle in level.paddles.iter() {
+        commands.spawn((
+            Name::new("Paddle"),
+            RigidBody::Kinematic,
+            AngularVelocity(paddle.angular_speed),
+            Collider::cuboid(paddle.length, PADDLE_THICKNESS),
+            MaterialMesh2dBundle {
+                transform: Transform::from_translation(paddle.position.extend(0.0)),
+                mesh: meshes
+                    .add(
+                        shape::Quad {
+                            size: Vec2::new(paddle.length, PADDLE_THICKNESS),
+                            ..default()
+                        }
+                        .into(),
+                    )
+                    .into(),
+                material: paddle_color.clone(),
+                ..default()
+            },
+            LevelGeometry,
+            CollisionLayers::new([Layer::Level], [Layer::Shapes]),
+        ));
+    }
 }
 
-fn spawn_score_displays(commands: &mut Commands, asset_server: &Res<AssetServer>) {
+fn spawn_score_displays(commands: &mut Commands, text_styler: &TextStyler) {
     let text_style = TextStyle {
-        font: asset_server.load("fonts/Roboto-Regular.ttf"),
+        font: text_styler.font(),
         font_size: 100.0,
         color: TEXT_COLOR,
     };
@@ -509,7 +1000,7 @@ fn spawn_score_displays(commands: &mut Commands, asset_server: &Res<AssetServer>
             transform: Transform::from_xyz(RIGHT - 1.0, TOP - 1.0, 1.0)
                 .with_scale(Vec3::splat(0.01)),
             text: Text {
-                sections: vec![TextSection::new("0", text_style)],
+                sections: vec![TextSection::new("0", text_style.clone())],
                 alignment: TextAlignment::Right,
                 linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
             },
@@ -518,11 +1009,27 @@ fn spawn_score_displays(commands: &mut Commands, asset_server: &Res<AssetServer>
         ScoreDisplay::Right,
         Name::new("RightScoreDisplay"),
     ));
+
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, TOP - 1.0, 1.0).with_scale(Vec3::splat(0.01)),
+            text: Text {
+                sections: vec![TextSection::new("0", text_style)],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        ScoreDisplay::Center,
+        Name::new("CenterScoreDisplay"),
+    ));
 }
 
-fn spawn_title_screen(commands: &mut Commands, asset_server: &Res<AssetServer>) {
+// Spawned each time the game returns to the title screen, not just at startup. The instructions
+// below the title come from the tutorial, see `TutorialPlugin`.
+fn spawn_title_screen(mut commands: Commands, text_styler: TextStyler) {
     let text_style = TextStyle {
-        font: asset_server.load("fonts/Roboto-Regular.ttf"),
+        font: text_styler.font(),
         font_size: 100.0,
         color: TEXT_COLOR,
     };
@@ -541,77 +1048,183 @@ fn spawn_title_screen(commands: &mut Commands, asset_server: &Res<AssetServer>)
                 Text2dBundle {
                     transform: Transform::from_xyz(0.0, 3.0, 1.0).with_scale(Vec3::splat(0.01)),
                     text: Text {
-                        sections: vec![TextSection::new("Mischief Link", text_style.clone())],
+                        sections: vec![TextSection::new("", text_style.clone())],
                         alignment: TextAlignment::Center,
                         linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
                     },
                     ..default()
                 },
+                TextKey("title"),
                 Name::new("Title"),
             ));
             parent.spawn((
                 Text2dBundle {
-                    transform: Transform::from_xyz(0.0, 2.0, 1.0).with_scale(Vec3::splat(0.005)),
+                    transform: TitleHint::Stats.transform(),
                     text: Text {
-                        sections: vec![TextSection::new(
-                            "Click outer mouse buttons to start",
-                            text_style.clone(),
-                        )],
+                        sections: vec![TextSection::new("", text_style.clone())],
                         alignment: TextAlignment::Center,
                         linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
                     },
                     ..default()
                 },
-                Name::new("Instructions"),
+                TextKey("title.stats_hint"),
+                Name::new("StatsHint"),
             ));
         });
 }
 
-fn spawn_game_over_screen(mut commands: Commands, asset_server: Res<AssetServer>) {
+// The results panel, in the middle of the screen between the leaderboard and the run breakdown.
+const RESULTS_PANEL_SIZE: Vec2 = Vec2::new(4.6, 3.4);
+const RESULTS_PANEL_CENTER: Vec2 = Vec2::new(0.0, 2.3);
+const RESULTS_ROW_HEIGHT: f32 = 0.3;
+
+fn spawn_game_over_screen(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    palette: Res<Palette>,
+    score: Res<Score>,
+    run_stats: Res<RunStats>,
+    leaderboard: Res<Leaderboard>,
+    text_styler: TextStyler,
+) {
     let text_style = TextStyle {
-        font: asset_server.load("fonts/Roboto-Regular.ttf"),
+        font: text_styler.font(),
         font_size: 100.0,
         color: TEXT_COLOR,
     };
+    // Beating every score on the board, rather than just making it.
+    let high_score = leaderboard
+        .best()
+        .map_or(score.total() > 0, |best| score.total() > best);
+    let half_width = RESULTS_PANEL_SIZE.x / 2.0 - 0.3;
+    let rows = [
+        (
+            "game_over.left_bin",
+            palette.left,
+            Some(ScoreDisplay::Left),
+            String::new(),
+        ),
+        (
+            "game_over.right_bin",
+            palette.right,
+            Some(ScoreDisplay::Right),
+            String::new(),
+        ),
+        (
+            "game_over.center_bin",
+            palette.third,
+            Some(ScoreDisplay::Center),
+            String::new(),
+        ),
+        (
+            "game_over.drops",
+            TEXT_COLOR,
+            None,
+            run_stats.drained.to_string(),
+        ),
+        (
+            "game_over.accuracy",
+            TEXT_COLOR,
+            None,
+            format!("{:.0}%", run_stats.accuracy() * 100.0),
+        ),
+        (
+            "game_over.best_combo",
+            TEXT_COLOR,
+            None,
+            run_stats.best_combo.to_string(),
+        ),
+    ];
+    let (total_label, total_color) = match high_score {
+        true => ("game_over.new_high_score", palette.third),
+        false => ("game_over.total", TEXT_COLOR),
+    };
 
     commands
         .spawn((
-            SpatialBundle {
-                transform: Transform::from_xyz(0.0, 0.0, 0.0),
+            MaterialMesh2dBundle {
+                transform: Transform::from_translation(RESULTS_PANEL_CENTER.extend(0.9)),
+                mesh: meshes
+                    .add(
+                        shape::Quad {
+                            size: RESULTS_PANEL_SIZE,
+                            ..default()
+                        }
+                        .into(),
+                    )
+                    .into(),
+                material: materials.add(ColorMaterial::from(BACKGROUND_COLOR)),
                 ..default()
             },
             DespawnOnExitGameOver,
+            Name::new("ResultsPanel"),
         ))
         .with_children(|parent| {
-            parent.spawn((Text2dBundle {
-                transform: Transform::from_xyz(0.0, 3.0, 1.0).with_scale(Vec3::splat(0.01)),
-                text: Text {
-                    sections: vec![TextSection::new("Game Over", text_style.clone())],
-                    alignment: TextAlignment::Center,
-                    linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
-                },
-                ..default()
-            },));
+            let top = RESULTS_PANEL_SIZE.y / 2.0;
             parent.spawn((
                 Text2dBundle {
-                    transform: Transform::from_xyz(0.0, 2.0, 1.0).with_scale(Vec3::splat(0.01)),
-                    text: Text {
-                        sections: vec![TextSection::new("", text_style.clone())],
-                        alignment: TextAlignment::Center,
-                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
-                    },
+                    transform: Transform::from_xyz(0.0, top - 0.45, 0.1)
+                        .with_scale(Vec3::splat(0.006)),
+                    text: Text::from_section("", text_style.clone())
+                        .with_alignment(TextAlignment::Center),
                     ..default()
                 },
-                ScoreDisplay::Sum,
+                TextKey("game_over.title"),
+                Name::new("GameOverTitle"),
             ));
-            parent.spawn((Text2dBundle {
-                transform: Transform::from_xyz(0.0, 1.0, 1.0).with_scale(Vec3::splat(0.005)),
-                text: Text {
-                    sections: vec![TextSection::new("Click to restart", text_style.clone())],
-                    alignment: TextAlignment::Center,
-                    linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+
+            let total_row = (
+                total_label,
+                total_color,
+                Some(ScoreDisplay::Sum),
+                String::new(),
+            );
+            let total_y = top - 1.3 - RESULTS_ROW_HEIGHT * rows.len() as f32;
+            let placed_rows = rows
+                .into_iter()
+                .enumerate()
+                .map(|(index, row)| (top - 1.1 - RESULTS_ROW_HEIGHT * index as f32, 0.0025, row))
+                .chain([(total_y, 0.004, total_row)]);
+            for (y, scale, (label_key, color, display, value)) in placed_rows {
+                let style = TextStyle {
+                    color,
+                    ..text_style.clone()
+                };
+                parent.spawn((
+                    Text2dBundle {
+                        transform: Transform::from_xyz(-half_width, y, 0.1)
+                            .with_scale(Vec3::splat(scale)),
+                        text: Text::from_section("", style.clone())
+                            .with_alignment(TextAlignment::Left),
+                        text_anchor: bevy::sprite::Anchor::CenterLeft,
+                        ..default()
+                    },
+                    TextKey(label_key),
+                ));
+                let mut value_text = parent.spawn(Text2dBundle {
+                    transform: Transform::from_xyz(half_width, y, 0.1)
+                        .with_scale(Vec3::splat(scale)),
+                    text: Text::from_section(value, style).with_alignment(TextAlignment::Right),
+                    text_anchor: bevy::sprite::Anchor::CenterRight,
+                    ..default()
+                });
+                // Scores are kept up to date by `display_score`.
+                if let Some(display) = display {
+                    value_text.insert(display);
+                }
+            }
+
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, -top + 0.25, 0.1)
+                        .with_scale(Vec3::splat(0.0025)),
+                    text: Text::from_section("", text_style.clone())
+                        .with_alignment(TextAlignment::Center),
+                    ..default()
                 },
-                ..default()
-            },));
+                TextKey("game_over.restart"),
+                Name::new("RestartHint"),
+            ));
         });
 }
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_xpbd_2d::prelude::*;
+
+use super::{locale::Localizer, text_style::TextStyler, AppState, TEXT_COLOR};
+use crate::util::cleanup_system;
+
+// A frame longer than this is an OS stall (window drag, sleep/wake) rather than ordinary lag.
+const STALL_SECONDS: f32 = 0.25;
+// At most this much fixed-update time is caught up in a single frame; the rest is dropped.
+const MAX_CATCH_UP_SECONDS: f32 = 0.1;
+const RESUME_COUNTDOWN_SECONDS: f32 = 3.0;
+
+// Keeps long stalls from fast-forwarding the simulation. Fixed-update catch-up is always clamped,
+// and a stall mid-run pauses physics and spawning until a short countdown has finished.
+pub struct StallPlugin;
+
+impl Plugin for StallPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<StallState>()
+            .add_systems(FixedUpdate, limit_catch_up.before(PhysicsSet::Prepare))
+            .add_systems(
+                PreUpdate,
+                detect_stall
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(in_state(StallState::Running)),
+            )
+            .add_systems(
+                OnEnter(StallState::Resuming),
+                (bevy_xpbd_2d::pause, start_countdown),
+            )
+            .add_systems(Update, count_down.run_if(in_state(StallState::Resuming)))
+            .add_systems(
+                OnExit(StallState::Resuming),
+                (bevy_xpbd_2d::resume, cleanup_system::<ResumeCountdown>),
+            )
+            .add_systems(OnExit(AppState::Playing), end_stall);
+    }
+}
+
+/// Timed gameplay (spawning, hazards, lids) only advances while `Running`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
+pub enum StallState {
+    #[default]
+    Running,
+    Resuming,
+    /// Paused until a disconnected cursor is claimed again, see `DisconnectPlugin`.
+    Disconnected,
+    /// Paused while the players change their settings, see `SettingsPlugin`.
+    Settings,
+}
+
+#[derive(Component)]
+struct ResumeCountdown {
+    timer: Timer,
+}
+
+fn limit_catch_up(mut fixed_time: ResMut<FixedTime>) {
+    let max_catch_up = Duration::from_secs_f32(MAX_CATCH_UP_SECONDS);
+    while fixed_time.accumulated() > max_catch_up && fixed_time.expend().is_ok() {}
+}
+
+fn detect_stall(time: Res<Time>, mut next_state: ResMut<NextState<StallState>>) {
+    if time.delta_seconds() > STALL_SECONDS {
+        println!("Stalled for {:.2}s, pausing", time.delta_seconds());
+        next_state.set(StallState::Resuming);
+    }
+}
+
+fn start_countdown(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 2.0, 3.0).with_scale(Vec3::splat(0.006)),
+            text: Text {
+                sections: vec![TextSection::new(
+                    "",
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: TEXT_COLOR,
+                    },
+                )],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        ResumeCountdown {
+            timer: Timer::from_seconds(RESUME_COUNTDOWN_SECONDS, TimerMode::Once),
+        },
+        Name::new("ResumeCountdown"),
+    ));
+}
+
+fn count_down(
+    mut countdowns: Query<(&mut ResumeCountdown, &mut Text)>,
+    mut next_state: ResMut<NextState<StallState>>,
+    localizer: Localizer,
+    time: Res<Time>,
+) {
+    for (mut countdown, mut text) in countdowns.iter_mut() {
+        // Another stall during the countdown (including the one that started it) starts it over.
+        match time.delta_seconds() > STALL_SECONDS {
+            true => countdown.timer.reset(),
+            false => {
+                countdown.timer.tick(time.delta());
+            }
+        }
+        if countdown.timer.finished() {
+            next_state.set(StallState::Running);
+        }
+        let remaining = countdown.timer.remaining_secs().ceil();
+        text.sections[0].value = localizer.format("stall.resuming", &[&remaining.to_string()]);
+    }
+}
+
+fn end_stall(mut next_state: ResMut<NextState<StallState>>) {
+    next_state.set(StallState::Running);
+}
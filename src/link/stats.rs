@@ -0,0 +1,529 @@
+use std::{fs, io::Write};
+
+use bevy::{
+    input::common_conditions::input_just_pressed,
+    prelude::*,
+    sprite::{Anchor, MaterialMesh2dBundle},
+};
+use bevy_xpbd_2d::prelude::*;
+
+use super::{
+    gameplay::{update_score, Bin, ShapeDrained, ShapeScored},
+    locale::{Localizer, TextKey},
+    player::Cursor,
+    text_style::TextStyler,
+    AppState, DespawnOnExitGameOver, BACKGROUND_COLOR, TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+// Every finished run is appended to this file as one line of whitespace-separated counts, followed
+// by the run's simulation checksum in hex, then the center bin's counts. Older lines without a
+// checksum or center counts are still read.
+const RUN_HISTORY_PATH: &str = "run_history.txt";
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<StatsScreenState>()
+            .insert_resource(LifetimeStats::load())
+            .add_event::<ShapeSorted>()
+            .add_systems(OnEnter(AppState::Playing), start_run)
+            .add_systems(
+                Update,
+                (
+                    track_sorts.after(update_score),
+                    track_drains,
+                    track_cursor_distance,
+                )
+                    .run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(OnEnter(AppState::GameOver), (save_run, spawn_run_breakdown))
+            .add_systems(
+                Update,
+                toggle_stats_screen
+                    .run_if(in_state(AppState::Init))
+                    .run_if(input_just_pressed(KeyCode::Tab)),
+            )
+            .add_systems(
+                Update,
+                (
+                    reset_lifetime_stats.run_if(input_just_pressed(KeyCode::R)),
+                    display_lifetime_stats,
+                )
+                    .chain()
+                    .run_if(in_state(StatsScreenState::Shown)),
+            )
+            .add_systems(OnEnter(StatsScreenState::Shown), spawn_stats_screen)
+            .add_systems(
+                OnExit(StatsScreenState::Shown),
+                cleanup_system::<StatsScreen>,
+            )
+            .add_systems(OnExit(AppState::Init), hide_stats_screen);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
+pub enum StatsScreenState {
+    #[default]
+    Hidden,
+    Shown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+    Center,
+}
+
+/// Sent once for each shape the first time it lands in a bin.
+#[derive(Event, Debug)]
+pub struct ShapeSorted {
+    pub shape: Entity,
+    pub correct: bool,
+    /// Consecutive correct sorts, including this one.
+    pub combo: u32,
+}
+
+/// Tallies for the run in progress.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct RunStats {
+    pub left_correct: u32,
+    pub left_incorrect: u32,
+    pub right_correct: u32,
+    pub right_incorrect: u32,
+    pub center_correct: u32,
+    pub center_incorrect: u32,
+    pub combo: u32,
+    pub best_combo: u32,
+    /// Shapes which fell out through the drain without being sorted.
+    pub drained: u32,
+    /// Meters moved by all the cursors together.
+    pub cursor_distance: f32,
+    /// Rolling checksum of the simulation state, see `ChecksumPlugin`.
+    pub checksum: u64,
+}
+
+impl RunStats {
+    pub fn record_sort(&mut self, side: Side, correct: bool) {
+        match (side, correct) {
+            (Side::Left, true) => self.left_correct += 1,
+            (Side::Left, false) => self.left_incorrect += 1,
+            (Side::Right, true) => self.right_correct += 1,
+            (Side::Right, false) => self.right_incorrect += 1,
+            (Side::Center, true) => self.center_correct += 1,
+            (Side::Center, false) => self.center_incorrect += 1,
+        }
+        self.combo = if correct { self.combo + 1 } else { 0 };
+        self.best_combo = u32::max(self.best_combo, self.combo);
+    }
+
+    fn correct(&self) -> u32 {
+        self.left_correct + self.right_correct + self.center_correct
+    }
+
+    fn incorrect(&self) -> u32 {
+        self.left_incorrect + self.right_incorrect + self.center_incorrect
+    }
+
+    /// The fraction of sorted shapes which went in the right bin, or 0 if none were sorted.
+    pub fn accuracy(&self) -> f32 {
+        match self.correct() + self.incorrect() {
+            0 => 0.0,
+            sorted => self.correct() as f32 / sorted as f32,
+        }
+    }
+
+    // The game over screen's breakdown of the run, one line per stat. The results panel already
+    // covers drains, accuracy and the best combo.
+    fn breakdown(&self, localizer: &Localizer) -> String {
+        let bins = [
+            ("game_over.left_bin", self.left_correct, self.left_incorrect),
+            (
+                "game_over.right_bin",
+                self.right_correct,
+                self.right_incorrect,
+            ),
+            (
+                "game_over.center_bin",
+                self.center_correct,
+                self.center_incorrect,
+            ),
+        ];
+        let mut lines = vec![localizer.get("stats.this_run"), String::new()];
+        lines.extend(bins.iter().map(|(name, correct, incorrect)| {
+            localizer.format(
+                "stats.bin_sorts",
+                &[
+                    &localizer.get(name),
+                    &correct.to_string(),
+                    &incorrect.to_string(),
+                ],
+            )
+        }));
+        lines.push(localizer.format(
+            "stats.cursor_travel",
+            &[&format!("{:.0}", self.cursor_distance)],
+        ));
+        lines.join("\n")
+    }
+
+    // Only the sorting counts are saved; drains and cursor travel are just for the game over screen.
+    fn to_line(&self) -> String {
+        format!(
+            "{} {} {} {} {} {:016x} {} {}",
+            self.left_correct,
+            self.left_incorrect,
+            self.right_correct,
+            self.right_incorrect,
+            self.best_combo,
+            self.checksum,
+            self.center_correct,
+            self.center_incorrect
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        let (counts, checksum, center_counts) = match fields.len() {
+            5 => (&fields[..], 0, &[][..]),
+            6 => (
+                &fields[..5],
+                u64::from_str_radix(fields[5], 16).ok()?,
+                &[][..],
+            ),
+            8 => (
+                &fields[..5],
+                u64::from_str_radix(fields[5], 16).ok()?,
+                &fields[6..],
+            ),
+            _ => return None,
+        };
+        let parse = |fields: &[&str]| {
+            fields
+                .iter()
+                .map(|value| value.parse::<u32>().ok())
+                .collect::<Option<Vec<_>>>()
+        };
+        let (values, center_values) = (parse(counts)?, parse(center_counts)?);
+        let [center_correct, center_incorrect] = match center_values[..] {
+            [correct, incorrect] => [correct, incorrect],
+            _ => [0, 0],
+        };
+        match values[..] {
+            [left_correct, left_incorrect, right_correct, right_incorrect, best_combo] => {
+                Some(Self {
+                    left_correct,
+                    left_incorrect,
+                    right_correct,
+                    right_incorrect,
+                    center_correct,
+                    center_incorrect,
+                    combo: 0,
+                    best_combo,
+                    checksum,
+                    ..default()
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Totals aggregated over every run saved in the run history.
+#[derive(Resource, Default, Debug)]
+pub struct LifetimeStats {
+    pub games_played: u32,
+    pub left_correct: u32,
+    pub right_correct: u32,
+    pub center_correct: u32,
+    pub incorrect: u32,
+    pub best_combo: u32,
+}
+
+impl LifetimeStats {
+    fn load() -> Self {
+        let mut stats = Self::default();
+        let history = match fs::read_to_string(RUN_HISTORY_PATH) {
+            Ok(history) => history,
+            Err(_) => return stats,
+        };
+        for line in history.lines().filter(|line| !line.trim().is_empty()) {
+            match RunStats::from_line(line) {
+                Some(run) => stats.add_run(&run),
+                None => println!("Skipping malformed run history line: {}", line),
+            }
+        }
+        stats
+    }
+
+    fn add_run(&mut self, run: &RunStats) {
+        self.games_played += 1;
+        self.left_correct += run.left_correct;
+        self.right_correct += run.right_correct;
+        self.center_correct += run.center_correct;
+        self.incorrect += run.left_incorrect + run.right_incorrect + run.center_incorrect;
+        self.best_combo = u32::max(self.best_combo, run.best_combo);
+    }
+
+    pub fn shapes_sorted(&self) -> u32 {
+        self.left_correct + self.right_correct + self.center_correct
+    }
+
+    fn accuracy(&self) -> f32 {
+        let total = self.shapes_sorted() + self.incorrect;
+        match total {
+            0 => 0.0,
+            _ => self.shapes_sorted() as f32 / total as f32,
+        }
+    }
+
+    // The key of the side's name.
+    fn favorite_side(&self) -> &'static str {
+        let sides = [
+            ("stats.side.left", self.left_correct),
+            ("stats.side.right", self.right_correct),
+            ("stats.side.center", self.center_correct),
+        ];
+        let best = sides.iter().map(|(_, count)| *count).max().unwrap_or(0);
+        let mut favorites = sides.iter().filter(|(_, count)| *count == best);
+        match (favorites.next(), favorites.next()) {
+            (Some((side, _)), None) => side,
+            _ => "stats.side.none",
+        }
+    }
+
+    fn summary(&self, localizer: &Localizer) -> String {
+        localizer.format(
+            "stats.summary",
+            &[
+                &self.games_played.to_string(),
+                &self.shapes_sorted().to_string(),
+                &format!("{:.0}", self.accuracy() * 100.0),
+                &self.best_combo.to_string(),
+                &localizer.get(self.favorite_side()),
+            ],
+        )
+    }
+}
+
+fn start_run(mut commands: Commands) {
+    commands.insert_resource(RunStats::default());
+}
+
+pub fn track_sorts(
+    mut run_stats: ResMut<RunStats>,
+    mut scored_events: EventReader<ShapeScored>,
+    mut sorted_events: EventWriter<ShapeSorted>,
+) {
+    let _span = info_span!("track_sorts").entered();
+    for event in scored_events.iter() {
+        let side = match event.bin {
+            Bin::Left => Side::Left,
+            Bin::Right => Side::Right,
+            Bin::Center => Side::Center,
+        };
+        let correct = event.points > 0;
+        run_stats.record_sort(side, correct);
+        sorted_events.send(ShapeSorted {
+            shape: event.entity,
+            correct,
+            combo: run_stats.combo,
+        });
+    }
+}
+
+fn track_drains(mut run_stats: ResMut<RunStats>, mut drained_events: EventReader<ShapeDrained>) {
+    run_stats.drained += drained_events.iter().count() as u32;
+}
+
+fn track_cursor_distance(
+    mut run_stats: ResMut<RunStats>,
+    cursors: Query<&LinearVelocity, With<Cursor>>,
+    time: Res<Time>,
+) {
+    let speed = cursors
+        .iter()
+        .map(|velocity| velocity.length())
+        .sum::<f32>();
+    run_stats.cursor_distance += speed * time.delta_seconds();
+}
+
+fn save_run(run_stats: Res<RunStats>, mut lifetime_stats: ResMut<LifetimeStats>) {
+    lifetime_stats.add_run(&run_stats);
+
+    let result = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(RUN_HISTORY_PATH)
+        .and_then(|mut file| writeln!(file, "{}", run_stats.to_line()));
+    if let Err(error) = result {
+        println!("Failed to save run history: {}", error);
+    }
+}
+
+// Stands opposite the leaderboard.
+fn spawn_run_breakdown(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    run_stats: Res<RunStats>,
+    text_styler: TextStyler,
+    localizer: Localizer,
+) {
+    commands
+        .spawn((
+            MaterialMesh2dBundle {
+                transform: Transform::from_xyz(4.5, 1.2, 0.9),
+                mesh: meshes
+                    .add(
+                        shape::Quad {
+                            size: Vec2::new(4.0, 3.8),
+                            ..default()
+                        }
+                        .into(),
+                    )
+                    .into(),
+                material: materials.add(ColorMaterial::from(BACKGROUND_COLOR)),
+                ..default()
+            },
+            DespawnOnExitGameOver,
+            Name::new("RunBreakdown"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, 1.75, 0.1).with_scale(Vec3::splat(0.0025)),
+                    text_anchor: Anchor::TopCenter,
+                    text: Text {
+                        sections: vec![TextSection::new(
+                            run_stats.breakdown(&localizer),
+                            TextStyle {
+                                font: text_styler.font(),
+                                font_size: 100.0,
+                                color: TEXT_COLOR,
+                            },
+                        )],
+                        alignment: TextAlignment::Center,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    ..default()
+                },
+                Name::new("RunBreakdownText"),
+            ));
+        });
+}
+
+fn reset_lifetime_stats(mut lifetime_stats: ResMut<LifetimeStats>) {
+    *lifetime_stats = LifetimeStats::default();
+    if let Err(error) = fs::remove_file(RUN_HISTORY_PATH) {
+        if error.kind() != std::io::ErrorKind::NotFound {
+            println!("Failed to reset run history: {}", error);
+        }
+    }
+}
+
+fn toggle_stats_screen(
+    state: Res<State<StatsScreenState>>,
+    mut next_state: ResMut<NextState<StatsScreenState>>,
+) {
+    next_state.set(match state.get() {
+        StatsScreenState::Hidden => StatsScreenState::Shown,
+        StatsScreenState::Shown => StatsScreenState::Hidden,
+    });
+}
+
+fn hide_stats_screen(mut next_state: ResMut<NextState<StatsScreenState>>) {
+    next_state.set(StatsScreenState::Hidden);
+}
+
+#[derive(Component)]
+struct StatsScreen;
+
+#[derive(Component)]
+struct LifetimeStatsDisplay;
+
+fn spawn_stats_screen(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    text_styler: TextStyler,
+) {
+    let text_style = TextStyle {
+        font: text_styler.font(),
+        font_size: 100.0,
+        color: TEXT_COLOR,
+    };
+
+    commands
+        .spawn((
+            MaterialMesh2dBundle {
+                transform: Transform::from_xyz(0.0, -0.5, 2.0),
+                mesh: meshes
+                    .add(
+                        shape::Quad {
+                            size: Vec2::new(7.0, 4.5),
+                            ..default()
+                        }
+                        .into(),
+                    )
+                    .into(),
+                material: materials.add(ColorMaterial::from(BACKGROUND_COLOR)),
+                ..default()
+            },
+            Name::new("StatsScreen"),
+            StatsScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, 1.6, 0.1).with_scale(Vec3::splat(0.006)),
+                    text: Text {
+                        sections: vec![TextSection::new("", text_style.clone())],
+                        alignment: TextAlignment::Center,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    ..default()
+                },
+                TextKey("stats.title"),
+                Name::new("StatsTitle"),
+            ));
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, -0.1, 0.1).with_scale(Vec3::splat(0.004)),
+                    text: Text {
+                        sections: vec![TextSection::new("", text_style.clone())],
+                        alignment: TextAlignment::Center,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    ..default()
+                },
+                LifetimeStatsDisplay,
+                Name::new("StatsSummary"),
+            ));
+            parent.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(0.0, -1.8, 0.1).with_scale(Vec3::splat(0.003)),
+                    text: Text {
+                        sections: vec![TextSection::new("", text_style.clone())],
+                        alignment: TextAlignment::Center,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    ..default()
+                },
+                TextKey("stats.instructions"),
+                Name::new("StatsInstructions"),
+            ));
+        });
+}
+
+fn display_lifetime_stats(
+    lifetime_stats: Res<LifetimeStats>,
+    localizer: Localizer,
+    mut displays: Query<&mut Text, With<LifetimeStatsDisplay>>,
+) {
+    for mut text in displays.iter_mut() {
+        text.sections[0].value = lifetime_stats.summary(&localizer);
+    }
+}
@@ -0,0 +1,124 @@
+use std::f32::consts::PI;
+
+use bevy::{prelude::*, utils::HashSet};
+use bevy_xpbd_2d::prelude::*;
+
+use super::{
+    elastic_rope::ElasticJoint,
+    mutators::Mutators,
+    player::Cursor,
+    spawn_level::{RopeJoint, RopeJointBuilder},
+    AppState,
+};
+use crate::mischief::{MischiefEvent, MischiefEventData};
+
+// While every player holds their right button during play, the rope locks into whatever shape
+// it's in, a rigid bar the pair can swing like a bat to flick shapes. Letting go, or the game
+// ending, makes it a rope again.
+pub struct StiffenPlugin;
+
+impl Plugin for StiffenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Stiffening>()
+            .add_systems(
+                Update,
+                (track_stiffen_buttons, stiffen_rope)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(OnExit(AppState::Playing), relax_rope);
+    }
+}
+
+#[derive(Resource, Default)]
+struct Stiffening {
+    // The devices holding their right button down.
+    held: HashSet<u32>,
+    stiff: bool,
+}
+
+fn track_stiffen_buttons(
+    mut mouse_events: EventReader<MischiefEvent>,
+    mut stiffening: ResMut<Stiffening>,
+) {
+    for event in mouse_events.iter() {
+        let MischiefEventData::Button { button: 1, pressed } = event.event_data else {
+            continue;
+        };
+        match pressed {
+            true => stiffening.held.insert(event.device),
+            false => stiffening.held.remove(&event.device),
+        };
+    }
+}
+
+fn stiffen_rope(
+    mut stiffening: ResMut<Stiffening>,
+    cursors: Query<&Cursor>,
+    mutators: Res<Mutators>,
+    joint_builder: Res<RopeJointBuilder>,
+    mut joints: Query<(&mut RevoluteJoint, Option<&ElasticJoint>), With<RopeJoint>>,
+    rotations: Query<&Rotation>,
+) {
+    let all_held = !cursors.is_empty()
+        && cursors.iter().all(|cursor| {
+            cursor
+                .0
+                .is_some_and(|device| stiffening.held.contains(&device))
+        });
+    if all_held == stiffening.stiff {
+        return;
+    }
+    stiffening.stiff = all_held;
+
+    match all_held {
+        true => {
+            println!("Stiffening the rope");
+            for (mut joint, _) in joints.iter_mut() {
+                let Ok([rotation1, rotation2]) = rotations.get_many([joint.entity1, joint.entity2])
+                else {
+                    continue;
+                };
+                // Pinned at the angle it's bent to now, so the rope doesn't jerk straight.
+                let angle = (rotation2.as_radians() - rotation1.as_radians() + PI)
+                    .rem_euclid(2.0 * PI)
+                    - PI;
+                *joint = joint.with_angle_limits(angle, angle);
+                // An elastic rope's links stop stretching too.
+                joint.compliance = 0.0;
+            }
+        }
+        false => {
+            println!("Relaxing the rope");
+            relax_joints(&mutators, &joint_builder, &mut joints);
+        }
+    }
+}
+
+// Buttons held when play ends don't leave the rope locked up on the menus.
+fn relax_rope(
+    mut stiffening: ResMut<Stiffening>,
+    mutators: Res<Mutators>,
+    joint_builder: Res<RopeJointBuilder>,
+    mut joints: Query<(&mut RevoluteJoint, Option<&ElasticJoint>), With<RopeJoint>>,
+) {
+    stiffening.held.clear();
+    if stiffening.stiff {
+        stiffening.stiff = false;
+        relax_joints(&mutators, &joint_builder, &mut joints);
+    }
+}
+
+fn relax_joints(
+    mutators: &Mutators,
+    joint_builder: &RopeJointBuilder,
+    joints: &mut Query<(&mut RevoluteJoint, Option<&ElasticJoint>), With<RopeJoint>>,
+) {
+    for (mut joint, elastic) in joints.iter_mut() {
+        joint.angle_limit = None;
+        joint.compliance = match elastic {
+            Some(_) => 1.0 / mutators.rope_stiffness,
+            None => joint_builder.compliance(),
+        };
+    }
+}
@@ -0,0 +1,71 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{
+    player::{AbsoluteTarget, Cursor, CursorSlot},
+    AppState,
+};
+use crate::mischief::{MischiefEvent, MischiefEventData};
+
+// Players often grab the cursors with the wrong hands. Pressing X swaps which devices drive the
+// left and right cursors, at any time. Outside of play, so can pressing both buttons of either
+// mouse at once; during play that's gripping a shape while stiffening the rope, see `StiffenPlugin`.
+pub struct SwapPlugin;
+
+impl Plugin for SwapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HeldButtons>()
+            .add_systems(Update, swap_on_request);
+    }
+}
+
+// The left and right buttons each device is holding down.
+#[derive(Resource, Default)]
+struct HeldButtons(HashMap<u32, [bool; 2]>);
+
+fn swap_on_request(
+    keys: Res<Input<KeyCode>>,
+    state: Res<State<AppState>>,
+    mut mouse_events: EventReader<MischiefEvent>,
+    mut held: ResMut<HeldButtons>,
+    mut cursors: Query<(&CursorSlot, &mut Cursor, &mut AbsoluteTarget)>,
+) {
+    let mut requested = keys.just_pressed(KeyCode::X);
+    let playing = *state.get() == AppState::Playing;
+    for event in mouse_events.iter() {
+        let MischiefEventData::Button { button, pressed } = event.event_data else {
+            continue;
+        };
+        let Some(index) = [0, 1].into_iter().position(|index| index == button) else {
+            continue;
+        };
+        let buttons = held.0.entry(event.device).or_default();
+        // Only the press completing the pair counts, so holding both doesn't swap back and forth.
+        if pressed && !buttons[index] && buttons[1 - index] && !playing {
+            requested = true;
+        }
+        buttons[index] = pressed;
+    }
+    if !requested {
+        return;
+    }
+
+    let device = |slot: CursorSlot| {
+        cursors
+            .iter()
+            .find(|(cursor_slot, _, _)| **cursor_slot == slot)
+            .map(|(_, cursor, _)| cursor.0)
+    };
+    let (Some(left), Some(right)) = (device(CursorSlot::LEFT), device(CursorSlot::RIGHT)) else {
+        return;
+    };
+    println!("Swapping the left and right cursors' devices");
+    for (slot, mut cursor, mut absolute_target) in cursors.iter_mut() {
+        cursor.0 = match *slot {
+            CursorSlot::LEFT => right,
+            CursorSlot::RIGHT => left,
+            _ => continue,
+        };
+        // The new device reports its own position the next time it moves.
+        absolute_target.0 = None;
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::{ecs::system::SystemParam, prelude::*, sprite::Anchor};
+
+const FONT: &str = "fonts/Roboto-Regular.ttf";
+
+// Outlines are drawn as copies of the text nudged this many text pixels in each direction.
+const OUTLINE_WIDTH: f32 = 4.0;
+const OUTLINE_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.85);
+
+// All game text gets its font through `TextStyler`, so the accessibility settings here apply to
+// every piece of text, including text which is already on screen when a setting changes.
+pub struct TextStylePlugin;
+
+impl Plugin for TextStylePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TextSettings>()
+            .init_resource::<TextSettings>()
+            .init_resource::<CurrentFont>()
+            .add_systems(
+                PostUpdate,
+                (apply_text_settings, add_outlines, sync_outlines).chain(),
+            );
+    }
+}
+
+/// Accessibility options for game text, editable from the inspector.
+#[derive(Resource, Reflect, Debug, Default, Clone, PartialEq)]
+#[reflect(Resource)]
+pub struct TextSettings {
+    /// Draw a dark outline around text, so it stays readable over busy backgrounds.
+    pub outline: bool,
+}
+
+#[derive(Resource)]
+struct CurrentFont(Handle<Font>);
+
+impl FromWorld for CurrentFont {
+    fn from_world(world: &mut World) -> Self {
+        Self(world.resource::<AssetServer>().load(FONT))
+    }
+}
+
+/// Hands out the font for newly spawned text.
+#[derive(SystemParam)]
+pub struct TextStyler<'w> {
+    font: Res<'w, CurrentFont>,
+}
+
+impl<'w> TextStyler<'w> {
+    pub fn font(&self) -> Handle<Font> {
+        self.font.0.clone()
+    }
+}
+
+// Marks the copies of a text entity which make up its outline.
+#[derive(Component)]
+struct TextOutline;
+
+fn apply_text_settings(
+    mut commands: Commands,
+    settings: Res<TextSettings>,
+    outlines: Query<Entity, With<TextOutline>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    // Outlines are rebuilt from scratch by `add_outlines`.
+    for entity in outlines.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn outline_text(text: &Text) -> Text {
+    let mut outline = text.clone();
+    for section in outline.sections.iter_mut() {
+        section.style.color = OUTLINE_COLOR;
+    }
+    outline
+}
+
+fn add_outlines(
+    mut commands: Commands,
+    settings: Res<TextSettings>,
+    texts: Query<(Entity, Ref<Text>, &Anchor), Without<TextOutline>>,
+) {
+    if !settings.outline {
+        return;
+    }
+
+    for (entity, text, anchor) in texts.iter() {
+        // After a settings change every outline was just removed, so all text needs new ones.
+        if !text.is_added() && !settings.is_changed() {
+            continue;
+        }
+        commands.entity(entity).with_children(|parent| {
+            for offset in [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y] {
+                let offset = offset * OUTLINE_WIDTH;
+                parent.spawn((
+                    Text2dBundle {
+                        // Just behind the text itself.
+                        transform: Transform::from_xyz(offset.x, offset.y, -1.0),
+                        text: outline_text(&text),
+                        text_anchor: anchor.clone(),
+                        ..default()
+                    },
+                    TextOutline,
+                    Name::new("TextOutline"),
+                ));
+            }
+        });
+    }
+}
+
+fn sync_outlines(
+    texts: Query<Ref<Text>, Without<TextOutline>>,
+    mut outlines: Query<(&Parent, &mut Text), With<TextOutline>>,
+) {
+    for (parent, mut outline) in outlines.iter_mut() {
+        let Ok(text) = texts.get(parent.get()) else {
+            continue;
+        };
+        if text.is_changed() {
+            *outline = outline_text(&text);
+        }
+    }
+}
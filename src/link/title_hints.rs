@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+// The hints above the main menu are stacked upwards from just over its buttons, and the ones below
+// it downwards from under the device list, this far apart.
+const ABOVE_MENU_Y: f32 = 0.4;
+const BELOW_MENU_Y: f32 = -2.7;
+const HINT_SPACING: f32 = 0.4;
+const HINT_SCALE: f32 = 0.003;
+
+/// Every line of help text on the title screen. They're laid out here, all together, rather than
+/// each module picking its own spot, so adding one can't land it on top of another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleHint {
+    Settings,
+    RopeSkins,
+    Mutators,
+    Stats,
+    Mirror,
+    Keyboard,
+    Gamepad,
+    Calibration,
+    GainTuning,
+}
+
+impl TitleHint {
+    const ABOVE_MENU: [TitleHint; 4] = [
+        TitleHint::Settings,
+        TitleHint::RopeSkins,
+        TitleHint::Mutators,
+        TitleHint::Stats,
+    ];
+    const BELOW_MENU: [TitleHint; 5] = [
+        TitleHint::Mirror,
+        TitleHint::Keyboard,
+        TitleHint::Gamepad,
+        TitleHint::Calibration,
+        TitleHint::GainTuning,
+    ];
+
+    /// Where the hint's text goes, at the size all the hints share.
+    pub fn transform(self) -> Transform {
+        let row = |hints: &[TitleHint]| hints.iter().position(|hint| *hint == self);
+        let y = match (row(&Self::ABOVE_MENU), row(&Self::BELOW_MENU)) {
+            (Some(row), _) => ABOVE_MENU_Y + HINT_SPACING * row as f32,
+            (None, row) => BELOW_MENU_Y - HINT_SPACING * row.unwrap_or(0) as f32,
+        };
+        Transform::from_xyz(0.0, y, 1.0).with_scale(Vec3::splat(HINT_SCALE))
+    }
+}
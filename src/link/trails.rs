@@ -0,0 +1,149 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    prelude::*,
+    render::render_resource::PrimitiveTopology,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+
+use super::{palette::Palette, player::CursorSlot};
+
+// How long a point stays in a cursor's trail, in seconds.
+const TRAIL_SECONDS: f32 = 0.25;
+// The trail is this wide right behind the cursor, tapering to nothing at its tail.
+const TRAIL_WIDTH: f32 = 0.2;
+const TRAIL_ALPHA: f32 = 0.5;
+// Cursors moving less than this far between frames don't add a point, so resting cursors have no
+// trail.
+const MIN_POINT_DISTANCE: f32 = 0.01;
+
+// Draws a fading ribbon behind each cursor in its player's color, so fast hand motion is easier to
+// follow.
+pub struct TrailPlugin;
+
+impl Plugin for TrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (spawn_trails, update_trails).chain());
+    }
+}
+
+#[derive(Component)]
+struct Trail {
+    cursor: Entity,
+    color: Color,
+    // Recent cursor positions, newest first, with how long ago each was recorded.
+    points: VecDeque<(Vec2, f32)>,
+}
+
+fn spawn_trails(
+    mut commands: Commands,
+    cursors: Query<(Entity, &CursorSlot), Added<CursorSlot>>,
+    palette: Res<Palette>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (cursor, slot) in cursors.iter() {
+        let trail = Trail {
+            cursor,
+            color: palette.slot(*slot),
+            points: VecDeque::new(),
+        };
+        // Starts out empty, but with its attributes in place so it renders as nothing.
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        build_ribbon(&mut mesh, &trail);
+        commands.spawn((
+            MaterialMesh2dBundle {
+                // Just behind the cursors. Points are in world space, so the trail stays put.
+                transform: Transform::from_xyz(0.0, 0.0, -0.1),
+                mesh: meshes.add(mesh).into(),
+                // White, so the vertex colors come through unchanged.
+                material: materials.add(ColorMaterial::from(Color::WHITE)),
+                ..default()
+            },
+            trail,
+            Name::new(format!("Trail ({})", slot.label())),
+        ));
+    }
+}
+
+fn update_trails(
+    mut commands: Commands,
+    mut trails: Query<(Entity, &mut Trail, &Mesh2dHandle)>,
+    cursors: Query<(&Transform, &CursorSlot)>,
+    palette: Res<Palette>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    time: Res<Time>,
+) {
+    for (entity, mut trail, mesh) in trails.iter_mut() {
+        let Ok((cursor, slot)) = cursors.get(trail.cursor) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        // Kept up with the palette, which can be switched mid-run from the pause menu.
+        trail.color = palette.slot(*slot);
+
+        for (_, age) in trail.points.iter_mut() {
+            *age += time.delta_seconds();
+        }
+        while trail
+            .points
+            .back()
+            .is_some_and(|(_, age)| *age > TRAIL_SECONDS)
+        {
+            trail.points.pop_back();
+        }
+        let position = cursor.translation.truncate();
+        let moved = trail.points.front().map_or(true, |(newest, _)| {
+            newest.distance(position) >= MIN_POINT_DISTANCE
+        });
+        if moved {
+            trail.points.push_front((position, 0.0));
+        }
+
+        if let Some(mesh) = meshes.get_mut(&mesh.0) {
+            build_ribbon(mesh, &trail);
+        }
+    }
+}
+
+// Two triangles per segment between consecutive points, narrowing and fading with age.
+fn build_ribbon(mesh: &mut Mesh, trail: &Trail) {
+    let edge = |index: usize| {
+        let (point, age) = trail.points[index];
+        let ahead = index
+            .checked_sub(1)
+            .and_then(|ahead| trail.points.get(ahead))
+            .map(|(p, _)| *p);
+        let behind = trail.points.get(index + 1).map(|(p, _)| *p);
+        let direction = match (ahead, behind) {
+            (Some(ahead), _) => ahead - point,
+            (None, Some(behind)) => point - behind,
+            (None, None) => Vec2::X,
+        };
+        let life = 1.0 - (age / TRAIL_SECONDS).clamp(0.0, 1.0);
+        let offset = direction.normalize_or_zero().perp() * TRAIL_WIDTH / 2.0 * life;
+        let color = trail.color.with_a(TRAIL_ALPHA * life).as_linear_rgba_f32();
+        ((point + offset, point - offset), color)
+    };
+
+    let mut positions = Vec::new();
+    let mut colors = Vec::new();
+    for index in 1..trail.points.len() {
+        let ((a_left, a_right), a_color) = edge(index - 1);
+        let ((b_left, b_right), b_color) = edge(index);
+        for (vertex, color) in [
+            (a_left, a_color),
+            (a_right, a_color),
+            (b_left, b_color),
+            (b_left, b_color),
+            (a_right, a_color),
+            (b_right, b_color),
+        ] {
+            // Must convert to Vec3 because Mesh::ATTRIBUTE_POSITION is Vec3.
+            positions.push(Vec3::new(vertex.x, vertex.y, 0.0));
+            colors.push(color);
+        }
+    }
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::quick::ResourceInspectorPlugin;
+
+use super::{
+    gameplay::SpawnTuning, locale::TextKey, spawn_level::SpawnState, text_style::TextStyler,
+    AppState, TEXT_COLOR,
+};
+
+// Launch with `--tuning` to skip straight past the title screen into tuning mode.
+const TUNING_FLAG: &str = "--tuning";
+
+// A dev workflow for tuning spawn pacing: shapes spawn endlessly with no scoring, while the spawn
+// region, delays and intensity are scrubbed live in an egui panel.
+pub struct TuningPlugin;
+
+impl Plugin for TuningPlugin {
+    fn build(&self, app: &mut App) {
+        if !std::env::args().any(|arg| arg == TUNING_FLAG) {
+            return;
+        }
+
+        app.insert_resource(TuningMode)
+            .add_plugins(
+                ResourceInspectorPlugin::<SpawnTuning>::default()
+                    .run_if(in_state(AppState::Tuning)),
+            )
+            .add_systems(Update, enter_tuning.run_if(in_state(AppState::Init)))
+            .add_systems(OnEnter(AppState::Tuning), spawn_tuning_banner);
+    }
+}
+
+/// Present when the game was launched in tuning mode.
+#[derive(Resource)]
+pub struct TuningMode;
+
+fn enter_tuning(spawn_state: Res<State<SpawnState>>, mut app_state: ResMut<NextState<AppState>>) {
+    if spawn_state.get() == &SpawnState::Done {
+        app_state.set(AppState::Tuning);
+    }
+}
+
+fn spawn_tuning_banner(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 4.2, 1.0).with_scale(Vec3::splat(0.003)),
+            text: Text {
+                sections: vec![TextSection::new(
+                    "",
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: TEXT_COLOR,
+                    },
+                )],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        TextKey("tuning.banner"),
+        Name::new("TuningBanner"),
+    ));
+}
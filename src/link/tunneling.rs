@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use bevy_xpbd_2d::{parry, prelude::*};
+
+use super::{
+    player::{apply_cursor_force, CursorSlot},
+    spawn_level::{Layer, RopeSegment},
+};
+
+// Contacts are only found between substeps, so anything moving further than this in one can skip
+// over the rope's thinnest part, half its 0.05m thickness, and come out the other side of a wall.
+const MAX_SUBSTEP_TRAVEL: f32 = 0.025;
+// How far past a wall's surface a dynamic cursor must have ended a step to count as having gone
+// through it, rather than just pressing against it or sliding along it.
+const MIN_TUNNEL_DEPTH: f32 = 0.05;
+// Cursors pulled back out of a wall are left this far from its surface.
+const SKIN: f32 = 0.005;
+
+// Keeps fast flicks from carrying the cursors and rope through the level. Everything on the rope
+// is held below the speed at which it could skip a whole wall in one substep, and each cursor's
+// path over the step is swept against the walls, pulling it back out to the surface of the first
+// one it hit.
+pub struct TunnelingPlugin;
+
+impl Plugin for TunnelingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            record_sweep_starts
+                .after(apply_cursor_force)
+                .before(PhysicsSet::Prepare),
+        )
+        .add_systems(
+            FixedUpdate,
+            (sweep_cursors, limit_rope_speed)
+                .chain()
+                .after(PhysicsSet::StepSimulation)
+                .before(PhysicsSet::Sync),
+        );
+    }
+}
+
+/// Where a cursor was at the start of the physics step.
+#[derive(Component, Debug)]
+struct SweepStart(Vec2);
+
+fn record_sweep_starts(
+    mut commands: Commands,
+    mut cursors: Query<(Entity, &Position, Option<&mut SweepStart>), With<CursorSlot>>,
+) {
+    for (entity, position, start) in cursors.iter_mut() {
+        match start {
+            Some(mut start) => start.0 = position.0,
+            // New cursors aren't swept until their second step.
+            None => {
+                commands.entity(entity).insert(SweepStart(position.0));
+            }
+        }
+    }
+}
+
+fn sweep_cursors(
+    spatial_query: SpatialQuery,
+    walls: Query<(&Collider, &Position, &Rotation), Without<CursorSlot>>,
+    mut cursors: Query<
+        (
+            &Collider,
+            &Rotation,
+            &RigidBody,
+            &SweepStart,
+            &mut Position,
+            &mut LinearVelocity,
+        ),
+        With<CursorSlot>,
+    >,
+) {
+    let filter = SpatialQueryFilter::new().with_masks([Layer::Level, Layer::PlayerBlocker]);
+    for (collider, rotation, body, start, mut position, mut velocity) in cursors.iter_mut() {
+        // A cursor which starts the step inside a wall is pushed back out first, and the rest of
+        // its move with it. Only a kinematic cursor can end up there, since nothing else stops it.
+        let push = push_out(&spatial_query, &walls, collider, rotation, start.0, &filter);
+        let start = start.0 + push;
+        position.0 += push;
+
+        // Every move is swept, however short: a kinematic cursor creeping into a wall would
+        // otherwise sink into it a little each step.
+        let travel = position.0 - start;
+        let distance = travel.length();
+        if distance == 0.0 {
+            continue;
+        }
+        let direction = travel / distance;
+        // Walls the cursor starts out touching are ignored, so pressing against one isn't a hit.
+        let Some(hit) = spatial_query.cast_shape(
+            collider,
+            start,
+            rotation.as_radians(),
+            direction,
+            distance,
+            true,
+            filter.clone(),
+        ) else {
+            continue;
+        };
+        // The hit's normal is the cursor's, in the cursor's own space; the wall faces the other way.
+        let normal = -rotation.rotate(hit.normal1);
+        let contact = start + direction * hit.time_of_impact;
+        let depth = (contact - position.0).dot(normal);
+        // The solver keeps a dynamic cursor out of walls it presses against or slides along, so
+        // only going deep enough to have skipped through one counts. Kinematic cursors have no such
+        // help, so any overlap does.
+        let min_depth = match body {
+            RigidBody::Kinematic => 0.0,
+            _ => MIN_TUNNEL_DEPTH,
+        };
+        if depth <= min_depth {
+            continue;
+        }
+        // Back out to the wall's surface, keeping any motion along it so cursors slide rather than
+        // stick.
+        position.0 += normal * (depth + SKIN);
+        // Whatever was carrying it into the wall is spent on the wall.
+        let into_wall = velocity.0.dot(normal).min(0.0);
+        velocity.0 -= normal * into_wall;
+    }
+}
+
+// How far a cursor at `position` has to move to get clear of any walls it's overlapping.
+fn push_out(
+    spatial_query: &SpatialQuery,
+    walls: &Query<(&Collider, &Position, &Rotation), Without<CursorSlot>>,
+    collider: &Collider,
+    rotation: &Rotation,
+    position: Vec2,
+    filter: &SpatialQueryFilter,
+) -> Vec2 {
+    let mut push = Vec2::ZERO;
+    let overlapping = spatial_query.shape_intersections(
+        collider,
+        position,
+        rotation.as_radians(),
+        filter.clone(),
+    );
+    for entity in overlapping {
+        let Ok((wall_collider, wall_position, wall_rotation)) = walls.get(entity) else {
+            continue;
+        };
+        let contact = parry::query::contact(
+            &parry::math::Isometry::new((position + push).into(), rotation.as_radians()),
+            collider.get_shape().as_ref(),
+            &parry::math::Isometry::new(wall_position.0.into(), wall_rotation.as_radians()),
+            wall_collider.get_shape().as_ref(),
+            0.0,
+        );
+        // A negative distance is how deep the shapes overlap, along the normal from the cursor
+        // into the wall.
+        if let Ok(Some(contact)) = contact {
+            if contact.dist < 0.0 {
+                let normal: Vec2 = contact.normal1.into();
+                push -= normal * (SKIN - contact.dist);
+            }
+        }
+    }
+    push
+}
+
+fn limit_rope_speed(
+    substeps: Res<SubstepCount>,
+    time: Res<FixedTime>,
+    mut bodies: Query<&mut LinearVelocity, Or<(With<CursorSlot>, With<RopeSegment>)>>,
+) {
+    let substep_seconds = time.period.as_secs_f32() / substeps.0 as f32;
+    let max_speed = MAX_SUBSTEP_TRAVEL / substep_seconds;
+    for mut velocity in bodies.iter_mut() {
+        // Only written when too fast, so change detection stays quiet.
+        if velocity.0.length() > max_speed {
+            velocity.0 = velocity.0.clamp_length_max(max_speed);
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_xpbd_2d::prelude::*;
+use rand::seq::SliceRandom;
+
+use super::{
+    gameplay::{Bin, BinAssignment, InBin, Shape, ShapeValue},
+    levels::CurrentLevel,
+    locale::Localizer,
+    palette::Palette,
+    player::{AttachState, Cursor, CursorSlot},
+    spawn_level::{Layer, RopeSegment, SpawnState, PLAY_REGION},
+    text_style::TextStyler,
+    tuning::TuningMode,
+    AppState, DespawnOnExitInit, TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+const TARGET_RADIUS: f32 = 0.35;
+// Each cursor's target sits this far above where the cursor was when the step began.
+const TARGET_RISE: f32 = 2.0;
+const PRACTICE_SHAPE_SIZE: f32 = 0.25;
+// The practice shape counts as caught once it has been touching the rope for this long.
+const CATCH_SECONDS: f32 = 0.5;
+
+// The title screen walks players through the game before letting them start: claim a mouse each,
+// move every cursor onto its target, catch a practice shape with the rope, then drop it in the
+// right bin. Each step waits until it's been done, and the warmup only starts after the last.
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<TutorialStep>()
+            .add_systems(OnEnter(AppState::Init), spawn_tutorial_prompt)
+            .add_systems(
+                Update,
+                (
+                    restart_tutorial,
+                    claim_mice.run_if(in_state(TutorialStep::ClaimMice)),
+                    reach_targets.run_if(in_state(TutorialStep::ReachTargets)),
+                    catch_practice_shape.run_if(in_state(TutorialStep::CatchShape)),
+                    sort_practice_shape.run_if(in_state(TutorialStep::SortShape)),
+                    display_tutorial_prompt,
+                )
+                    .run_if(in_state(AppState::Init))
+                    .run_if(not(resource_exists::<TuningMode>())),
+            )
+            .add_systems(
+                OnEnter(TutorialStep::ClaimMice),
+                (
+                    cleanup_system::<TutorialTarget>,
+                    cleanup_system::<PracticeShape>,
+                ),
+            )
+            .add_systems(OnEnter(TutorialStep::ReachTargets), spawn_targets)
+            .add_systems(
+                OnExit(TutorialStep::ReachTargets),
+                cleanup_system::<TutorialTarget>,
+            )
+            .add_systems(OnEnter(TutorialStep::CatchShape), drop_practice_shape)
+            .add_systems(
+                OnExit(TutorialStep::SortShape),
+                cleanup_system::<PracticeShape>,
+            )
+            .add_systems(OnExit(AppState::Init), reset_tutorial);
+    }
+}
+
+/// How far through the tutorial the players are. Only advances on the title screen, and starts over
+/// each time it's left.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
+pub enum TutorialStep {
+    #[default]
+    ClaimMice,
+    ReachTargets,
+    CatchShape,
+    SortShape,
+}
+
+#[derive(Component)]
+struct TutorialPrompt;
+
+#[derive(Component)]
+struct TutorialTarget {
+    slot: CursorSlot,
+    reached: bool,
+}
+
+#[derive(Component)]
+struct PracticeShape {
+    shape: Shape,
+    // How long it's been touching the rope without a break.
+    held_seconds: f32,
+}
+
+fn spawn_tutorial_prompt(mut commands: Commands, text_styler: TextStyler) {
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 2.0, 1.0).with_scale(Vec3::splat(0.005)),
+            text: Text {
+                sections: vec![TextSection::new(
+                    "",
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: TEXT_COLOR,
+                    },
+                )],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        TutorialPrompt,
+        DespawnOnExitInit,
+        Name::new("TutorialPrompt"),
+    ));
+}
+
+fn reset_tutorial(mut step: ResMut<NextState<TutorialStep>>) {
+    step.set(TutorialStep::ClaimMice);
+}
+
+// A mouse let go of partway through means claiming them all over again.
+fn restart_tutorial(
+    step: Res<State<TutorialStep>>,
+    attach_state: Res<State<AttachState>>,
+    mut next_step: ResMut<NextState<TutorialStep>>,
+) {
+    if step.get() != &TutorialStep::ClaimMice && attach_state.get() == &AttachState::Waiting {
+        next_step.set(TutorialStep::ClaimMice);
+    }
+}
+
+fn claim_mice(
+    spawn_state: Res<State<SpawnState>>,
+    attach_state: Res<State<AttachState>>,
+    mut step: ResMut<NextState<TutorialStep>>,
+) {
+    if spawn_state.get() == &SpawnState::Done && attach_state.get() == &AttachState::Attached {
+        step.set(TutorialStep::ReachTargets);
+    }
+}
+
+fn spawn_targets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    palette: Res<Palette>,
+    cursors: Query<(&CursorSlot, &Transform), With<Cursor>>,
+) {
+    let mesh = meshes.add(
+        shape::Circle {
+            radius: TARGET_RADIUS,
+            ..default()
+        }
+        .into(),
+    );
+    for (slot, transform) in cursors.iter() {
+        let position = transform.translation.truncate() + Vec2::new(0.0, TARGET_RISE);
+        commands.spawn((
+            MaterialMesh2dBundle {
+                transform: Transform::from_translation(position.extend(-0.5)),
+                mesh: mesh.clone().into(),
+                // Each target has its own material, so it can light up when reached.
+                material: materials.add(ColorMaterial::from(palette.slot(*slot).with_a(0.3))),
+                ..default()
+            },
+            TutorialTarget {
+                slot: *slot,
+                reached: false,
+            },
+            DespawnOnExitInit,
+            Name::new(format!("TutorialTarget ({})", slot.label())),
+        ));
+    }
+}
+
+// Targets stay lit once reached, so the cursors don't all have to be on them at once.
+fn reach_targets(
+    mut targets: Query<(&mut TutorialTarget, &Transform, &Handle<ColorMaterial>)>,
+    cursors: Query<(&CursorSlot, &Transform), With<Cursor>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    palette: Res<Palette>,
+    mut step: ResMut<NextState<TutorialStep>>,
+) {
+    for (mut target, target_transform, material) in targets.iter_mut() {
+        if target.reached {
+            continue;
+        }
+        let reached = cursors.iter().any(|(slot, transform)| {
+            *slot == target.slot
+                && transform
+                    .translation
+                    .truncate()
+                    .distance(target_transform.translation.truncate())
+                    < TARGET_RADIUS
+        });
+        if reached {
+            target.reached = true;
+            if let Some(material) = materials.get_mut(material) {
+                material.color = palette.slot(target.slot);
+            }
+        }
+    }
+    if !targets.is_empty() && targets.iter().all(|(target, _, _)| target.reached) {
+        step.set(TutorialStep::CatchShape);
+    }
+}
+
+fn drop_practice_shape(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    palette: Res<Palette>,
+    current_level: CurrentLevel,
+) {
+    spawn_practice_shape(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &palette,
+        &current_level,
+    );
+}
+
+// Dropped in from the level's spawn region, like the shapes in a game. Only squares and circles are
+// used, since their bins are the easiest to reach.
+fn spawn_practice_shape(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    palette: &Palette,
+    current_level: &CurrentLevel,
+) {
+    let shape = *[Shape::Square, Shape::Circle]
+        .choose(&mut rand::thread_rng())
+        .unwrap();
+    let (mesh, collider): (Mesh, Collider) = match shape {
+        Shape::Circle => (
+            shape::Circle {
+                radius: PRACTICE_SHAPE_SIZE / 2.0,
+                ..default()
+            }
+            .into(),
+            Collider::ball(PRACTICE_SHAPE_SIZE / 2.0),
+        ),
+        _ => (
+            shape::Quad {
+                size: Vec2::splat(PRACTICE_SHAPE_SIZE),
+                ..default()
+            }
+            .into(),
+            Collider::cuboid(PRACTICE_SHAPE_SIZE, PRACTICE_SHAPE_SIZE),
+        ),
+    };
+    let position = current_level.get().shape_spawn_region.center();
+    commands.spawn((
+        MaterialMesh2dBundle {
+            transform: Transform::from_translation(position.extend(0.0)),
+            mesh: meshes.add(mesh).into(),
+            material: materials.add(ColorMaterial::from(palette.shape(shape))),
+            ..default()
+        },
+        RigidBody::Dynamic,
+        collider,
+        CollisionLayers::new([Layer::Shapes], [Layer::Rope, Layer::Level, Layer::Shapes]),
+        PracticeShape {
+            shape,
+            held_seconds: 0.0,
+        },
+        DespawnOnExitInit,
+        Name::new(format!("Practice {}", shape)),
+    ));
+}
+
+// Whether the practice shape has fallen out the bottom, or into a bin without being caught first.
+fn practice_shape_lost(transform: &Transform, in_bin: Option<&InBin>) -> bool {
+    transform.translation.y < PLAY_REGION.min.y || in_bin.is_some()
+}
+
+fn catch_practice_shape(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    palette: Res<Palette>,
+    current_level: CurrentLevel,
+    mut practice_shapes: Query<(
+        Entity,
+        &mut PracticeShape,
+        &Transform,
+        Option<&CollidingEntities>,
+        Option<&InBin>,
+    )>,
+    segments: Query<(), With<RopeSegment>>,
+    mut step: ResMut<NextState<TutorialStep>>,
+    time: Res<Time>,
+) {
+    for (entity, mut practice_shape, transform, colliding, in_bin) in practice_shapes.iter_mut() {
+        if practice_shape_lost(transform, in_bin) {
+            println!("Missed the practice shape, dropping another");
+            commands.entity(entity).despawn_recursive();
+            spawn_practice_shape(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                &palette,
+                &current_level,
+            );
+            continue;
+        }
+        let touching_rope = colliding
+            .is_some_and(|colliding| colliding.iter().any(|entity| segments.contains(*entity)));
+        practice_shape.held_seconds = match touching_rope {
+            true => practice_shape.held_seconds + time.delta_seconds(),
+            false => 0.0,
+        };
+        if practice_shape.held_seconds >= CATCH_SECONDS {
+            step.set(TutorialStep::SortShape);
+        }
+    }
+}
+
+fn sort_practice_shape(
+    practice_shapes: Query<(&PracticeShape, &Transform, Option<&InBin>)>,
+    assignment: Res<BinAssignment>,
+    mut step: ResMut<NextState<TutorialStep>>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for (practice_shape, transform, in_bin) in practice_shapes.iter() {
+        match in_bin {
+            Some(&InBin(bin))
+                if assignment.points(bin, practice_shape.shape, ShapeValue(1)) > 0 =>
+            {
+                println!("Tutorial done, starting the warmup");
+                app_state.set(AppState::Warmup);
+            }
+            Some(InBin(bin)) => {
+                println!("Practice shape landed in the {:?} bin, try again", bin);
+                step.set(TutorialStep::CatchShape);
+            }
+            None if transform.translation.y < PLAY_REGION.min.y => {
+                println!("Dropped the practice shape, try again");
+                step.set(TutorialStep::CatchShape);
+            }
+            None => {}
+        }
+    }
+}
+
+fn bin_key(bin: Bin) -> &'static str {
+    match bin {
+        Bin::Left => "bin.left",
+        Bin::Right => "bin.right",
+        Bin::Center => "bin.middle",
+    }
+}
+
+fn shape_key(shape: Shape) -> &'static str {
+    match shape {
+        Shape::Square => "shape.square",
+        Shape::Circle => "shape.circle",
+        Shape::Triangle => "shape.triangle",
+    }
+}
+
+fn display_tutorial_prompt(
+    step: Res<State<TutorialStep>>,
+    assignment: Res<BinAssignment>,
+    localizer: Localizer,
+    practice_shapes: Query<&PracticeShape>,
+    mut prompts: Query<&mut Text, With<TutorialPrompt>>,
+) {
+    let shape = practice_shapes
+        .iter()
+        .next()
+        .map_or(Shape::Square, |practice_shape| practice_shape.shape);
+    let shape_name = localizer.get(shape_key(shape));
+    let value = match step.get() {
+        // The mouse diagrams show which button to click, see `MouseDiagramPlugin`.
+        TutorialStep::ClaimMice => String::new(),
+        TutorialStep::ReachTargets => localizer.get("tutorial.reach_targets"),
+        TutorialStep::CatchShape => localizer.format("tutorial.catch_shape", &[&shape_name]),
+        TutorialStep::SortShape => {
+            let bin = assignment.bin(shape).unwrap_or(Bin::Left);
+            localizer.format(
+                "tutorial.sort_shape",
+                &[&shape_name, &localizer.get(bin_key(bin))],
+            )
+        }
+    };
+    for mut text in prompts.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+
+use super::{
+    locale::Localizer, text_style::TextStyler, AppState, DespawnOnExitGameOver, TEXT_COLOR,
+};
+
+// Each longer rope upgrade adds this much rope between neighbouring cursors, in meters.
+const LONGER_STEP: f32 = 1.0;
+// Each lighter rope upgrade scales the segments' mass by this much.
+const LIGHTER_STEP: f32 = 0.75;
+// Any more and the rope would no longer fit the playfield, or would weigh next to nothing.
+const MAX_LONGER: u32 = 2;
+const MAX_LIGHTER: u32 = 2;
+
+// After each level, the players pick an upgrade to their rope for the rest of the run, from the
+// game over screen. Upgrades last until the game goes back to the title screen.
+pub struct UpgradesPlugin;
+
+impl Plugin for UpgradesPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RopeUpgrades>()
+            .init_resource::<RopeUpgrades>()
+            .add_systems(OnEnter(AppState::Init), reset_upgrades)
+            .add_systems(OnEnter(AppState::GameOver), spawn_upgrade_offer)
+            .add_systems(Update, pick_upgrade.run_if(in_state(AppState::GameOver)));
+    }
+}
+
+/// The upgrades picked so far this run. Applied when the rope is respawned, see `RopeBuild`.
+#[derive(Resource, Reflect, Debug, Default, Clone)]
+#[reflect(Resource)]
+pub struct RopeUpgrades {
+    pub longer: u32,
+    pub lighter: u32,
+    pub mid_net: bool,
+}
+
+impl RopeUpgrades {
+    pub fn extra_length(&self) -> f32 {
+        self.longer as f32 * LONGER_STEP
+    }
+
+    pub fn segment_mass_scale(&self) -> f32 {
+        LIGHTER_STEP.powi(self.lighter as i32)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Upgrade {
+    Longer,
+    Lighter,
+    MidNet,
+}
+
+impl Upgrade {
+    const ALL: [Upgrade; 3] = [Upgrade::Longer, Upgrade::Lighter, Upgrade::MidNet];
+
+    // The key of its name, see `Localizer`.
+    fn label_key(self) -> &'static str {
+        match self {
+            Upgrade::Longer => "upgrade.longer",
+            Upgrade::Lighter => "upgrade.lighter",
+            Upgrade::MidNet => "upgrade.mid_net",
+        }
+    }
+
+    fn is_available(self, upgrades: &RopeUpgrades) -> bool {
+        match self {
+            Upgrade::Longer => upgrades.longer < MAX_LONGER,
+            Upgrade::Lighter => upgrades.lighter < MAX_LIGHTER,
+            Upgrade::MidNet => !upgrades.mid_net,
+        }
+    }
+
+    fn apply(self, upgrades: &mut RopeUpgrades) {
+        match self {
+            Upgrade::Longer => upgrades.longer += 1,
+            Upgrade::Lighter => upgrades.lighter += 1,
+            Upgrade::MidNet => upgrades.mid_net = true,
+        }
+    }
+}
+
+/// The upgrades on offer this game over, of which one can be picked.
+#[derive(Component)]
+struct UpgradeOffer {
+    options: Vec<Upgrade>,
+    picked: Option<Upgrade>,
+}
+
+impl UpgradeOffer {
+    fn describe(&self, localizer: &Localizer) -> String {
+        if let Some(upgrade) = self.picked {
+            return localizer.format("upgrade.picked", &[&localizer.get(upgrade.label_key())]);
+        }
+        let mut lines = vec![localizer.get("upgrade.prompt")];
+        lines.extend(
+            self.options
+                .iter()
+                .enumerate()
+                .map(|(i, upgrade)| format!("{}: {}", i + 1, localizer.get(upgrade.label_key()))),
+        );
+        lines.join("\n")
+    }
+}
+
+fn reset_upgrades(mut upgrades: ResMut<RopeUpgrades>) {
+    // Only written when there's something to undo, so the rope isn't respawned for nothing.
+    if upgrades.longer > 0 || upgrades.lighter > 0 || upgrades.mid_net {
+        *upgrades = RopeUpgrades::default();
+    }
+}
+
+fn spawn_upgrade_offer(
+    mut commands: Commands,
+    upgrades: Res<RopeUpgrades>,
+    text_styler: TextStyler,
+    localizer: Localizer,
+) {
+    let options = Upgrade::ALL
+        .into_iter()
+        .filter(|upgrade| upgrade.is_available(&upgrades))
+        .collect::<Vec<_>>();
+    // Fully upgraded, there's nothing left to offer.
+    if options.is_empty() {
+        return;
+    }
+    let offer = UpgradeOffer {
+        options,
+        picked: None,
+    };
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, -0.6, 1.0).with_scale(Vec3::splat(0.003)),
+            text_anchor: bevy::sprite::Anchor::TopCenter,
+            text: Text {
+                sections: vec![TextSection::new(
+                    offer.describe(&localizer),
+                    TextStyle {
+                        font: text_styler.font(),
+                        font_size: 100.0,
+                        color: TEXT_COLOR,
+                    },
+                )],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        offer,
+        DespawnOnExitGameOver,
+        Name::new("UpgradeOffer"),
+    ));
+}
+
+// Mouse clicks start the next level, so upgrades are picked from the keyboard.
+fn pick_upgrade(
+    keys: Res<Input<KeyCode>>,
+    mut upgrades: ResMut<RopeUpgrades>,
+    mut offers: Query<(&mut UpgradeOffer, &mut Text)>,
+    localizer: Localizer,
+) {
+    const NUMBER_KEYS: [KeyCode; 3] = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3];
+    for (mut offer, mut text) in offers.iter_mut() {
+        if offer.picked.is_some() {
+            continue;
+        }
+        let Some(upgrade) = NUMBER_KEYS
+            .iter()
+            .zip(offer.options.iter())
+            .find(|(key, _)| keys.just_pressed(**key))
+            .map(|(_, upgrade)| *upgrade)
+        else {
+            continue;
+        };
+        println!("Upgrading the rope: {:?}", upgrade);
+        upgrade.apply(&mut upgrades);
+        offer.picked = Some(upgrade);
+        text.sections[0].value = offer.describe(&localizer);
+    }
+}
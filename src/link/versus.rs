@@ -0,0 +1,345 @@
+use bevy::prelude::*;
+
+use super::{
+    cursor_control::CursorControl,
+    game_mode::GameMode,
+    gameplay::{Bin, BinAssignment, InBin, Settled, Shape, ShapeValue},
+    locale::{Localizer, TextKey},
+    palette::Palette,
+    player::{CursorSlot, PlayerCount},
+    spawn_level::{RopeSegment, HEIGHT},
+    text_style::TextStyler,
+    AppState, DespawnOnExitGameOver, TEXT_COLOR,
+};
+use crate::util::cleanup_system;
+
+// The tug meter sits just above the tension meter, along the bottom of the screen.
+const TUG_METER_SIZE: Vec2 = Vec2::new(3.0, 0.12);
+const TUG_METER_Y: f32 = -HEIGHT / 2.0 + 0.45;
+const TUG_MARKER_SIZE: Vec2 = Vec2::new(0.08, 0.3);
+// The marker reaches the end of the meter once the middle of the rope is this far to one side, in
+// meters.
+const TUG_RANGE: f32 = 4.0;
+// Within this much of the middle of the meter, neither player is winning the tug.
+const TUG_DEAD_ZONE: f32 = 0.1;
+// Holding the middle of the rope on your own side for this long wins the tug, worth this many
+// points, and starts the next one.
+const TUG_HOLD_SECONDS: f32 = 3.0;
+const TUG_POINTS: i32 = 1;
+
+// In versus mode each player owns the bin in their color, the left player the left bin and so on,
+// and scores for the right shapes in it. Knocking a shape back out of a rival's bin takes its
+// points away. Cursors are held in force mode, so the players really do pull against each other
+// through the rope: whoever keeps the middle of the rope dragged over to their side for long enough
+// wins the tug and scores for it, and a meter shows which way it's going. Whoever has the most
+// points when the shapes run out wins.
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VersusScore>()
+            .init_resource::<Tug>()
+            .add_systems(
+                OnEnter(AppState::Playing),
+                (
+                    reset_versus_score,
+                    save_cursor_control,
+                    spawn_versus_displays,
+                )
+                    .run_if(resource_equals(GameMode::Versus)),
+            )
+            .add_systems(
+                Update,
+                (
+                    hold_force_control,
+                    pull_tug,
+                    update_versus_score,
+                    display_versus_score,
+                    display_tug_meter,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(resource_equals(GameMode::Versus)),
+            )
+            .add_systems(
+                OnExit(AppState::Playing),
+                (cleanup_system::<TugMeter>, restore_cursor_control),
+            )
+            .add_systems(
+                OnEnter(AppState::GameOver),
+                spawn_versus_winner.run_if(resource_equals(GameMode::Versus)),
+            );
+    }
+}
+
+/// Each player's points, in the order of `Bin::ALL`: the left player owns the left bin, the right
+/// player the right bin, and a third player the center bin.
+#[derive(Resource, Debug, Default)]
+struct VersusScore([i32; 3]);
+
+// The tug-of-war between the left and right players over the middle of the rope.
+#[derive(Resource, Debug, Default)]
+struct Tug {
+    // How far the middle of the rope has been dragged, from -1 all the way left to 1 all the way
+    // right.
+    pull: f32,
+    // Which player is winning the current tug, and for how long they've held it.
+    holder: Option<usize>,
+    held_seconds: f32,
+    // Tugs won by each player, in the same order as `VersusScore`.
+    wins: [i32; 3],
+}
+
+// The cursor control from before the game, to put back once it's over.
+#[derive(Resource)]
+struct SavedCursorControl(CursorControl);
+
+#[derive(Component)]
+struct VersusScoreDisplay;
+
+#[derive(Component)]
+struct TugMeter;
+
+#[derive(Component)]
+struct TugMarker;
+
+// Players past the third have no bin of their own, and just help out.
+fn num_competitors(player_count: &PlayerCount) -> usize {
+    (player_count.0 as usize).clamp(2, Bin::ALL.len())
+}
+
+fn reset_versus_score(mut score: ResMut<VersusScore>, mut tug: ResMut<Tug>) {
+    *score = VersusScore::default();
+    *tug = Tug::default();
+}
+
+fn save_cursor_control(mut commands: Commands, control: Res<CursorControl>) {
+    commands.insert_resource(SavedCursorControl(*control));
+}
+
+fn restore_cursor_control(
+    mut commands: Commands,
+    saved: Option<Res<SavedCursorControl>>,
+    mut control: ResMut<CursorControl>,
+) {
+    if let Some(saved) = saved {
+        control.set_if_neq(saved.0);
+        commands.remove_resource::<SavedCursorControl>();
+    }
+}
+
+fn spawn_versus_displays(
+    mut commands: Commands,
+    player_count: Res<PlayerCount>,
+    palette: Res<Palette>,
+    text_styler: TextStyler,
+) {
+    let sections = (0..num_competitors(&player_count))
+        .map(|player| {
+            TextSection::new(
+                "",
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color: palette.slot(CursorSlot(player as u8)),
+                },
+            )
+        })
+        .collect();
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, HEIGHT / 2.0 - 1.8, 1.0)
+                .with_scale(Vec3::splat(0.004)),
+            text: Text {
+                sections,
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        VersusScoreDisplay,
+        DespawnOnExitGameOver,
+        Name::new("VersusScoreDisplay"),
+    ));
+
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: TEXT_COLOR.with_a(0.2),
+                    custom_size: Some(TUG_METER_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, TUG_METER_Y, 1.0),
+                ..default()
+            },
+            TugMeter,
+            Name::new("TugMeter"),
+        ))
+        .with_children(|meter| {
+            meter.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: TEXT_COLOR,
+                        custom_size: Some(TUG_MARKER_SIZE),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                    ..default()
+                },
+                TugMarker,
+            ));
+            meter.spawn((
+                Text2dBundle {
+                    transform: Transform::from_xyz(-TUG_METER_SIZE.x / 2.0 - 0.1, 0.0, 0.1)
+                        .with_scale(Vec3::splat(0.002)),
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: text_styler.font(),
+                            font_size: 100.0,
+                            color: TEXT_COLOR,
+                        },
+                    )
+                    .with_alignment(TextAlignment::Right),
+                    text_anchor: bevy::sprite::Anchor::CenterRight,
+                    ..default()
+                },
+                TextKey("versus.tug"),
+            ));
+        });
+}
+
+// Switching to position mode would let a player overpower the rope outright.
+fn hold_force_control(mut control: ResMut<CursorControl>) {
+    control.set_if_neq(CursorControl::Force);
+}
+
+// Follows the middle of the rope. Only the left and right players tug, since the rope runs
+// between them.
+fn pull_tug(mut tug: ResMut<Tug>, segments: Query<(&RopeSegment, &Transform)>, time: Res<Time>) {
+    let Some(middle) = segments
+        .iter()
+        .min_by(|(a, _), (b, _)| (a.along - 0.5).abs().total_cmp(&(b.along - 0.5).abs()))
+        .map(|(_, transform)| transform.translation.x)
+    else {
+        return;
+    };
+    tug.pull = (middle / TUG_RANGE).clamp(-1.0, 1.0);
+    let holder = match tug.pull {
+        pull if pull < -TUG_DEAD_ZONE => Some(0),
+        pull if pull > TUG_DEAD_ZONE => Some(1),
+        _ => None,
+    };
+    if holder != tug.holder {
+        tug.holder = holder;
+        tug.held_seconds = 0.0;
+        return;
+    }
+    let Some(holder) = holder else {
+        return;
+    };
+    tug.held_seconds += time.delta_seconds();
+    if tug.held_seconds >= TUG_HOLD_SECONDS {
+        tug.wins[holder] += TUG_POINTS;
+        tug.held_seconds = 0.0;
+    }
+}
+
+// Recounted every frame, like the main score, so points leave a bin along with their shapes. Only
+// the right shapes count; a wrong one in a rival's bin just takes up room. Won tugs are kept.
+fn update_versus_score(
+    mut score: ResMut<VersusScore>,
+    tug: Res<Tug>,
+    assignment: Res<BinAssignment>,
+    shapes: Query<(&Shape, &ShapeValue, &InBin), With<Settled>>,
+) {
+    score.0 = tug.wins;
+    for (shape, value, InBin(bin)) in shapes.iter() {
+        let points = assignment.points(*bin, *shape, *value);
+        let Some(owner) = Bin::ALL.iter().position(|owned| owned == bin) else {
+            continue;
+        };
+        if points > 0 {
+            score.0[owner] += points;
+        }
+    }
+}
+
+fn display_versus_score(
+    score: Res<VersusScore>,
+    localizer: Localizer,
+    mut displays: Query<&mut Text, With<VersusScoreDisplay>>,
+) {
+    for mut text in displays.iter_mut() {
+        let last = text.sections.len().saturating_sub(1);
+        for (player, section) in text.sections.iter_mut().enumerate() {
+            section.value = localizer.format(
+                "versus.player_score",
+                &[&(player + 1).to_string(), &score.0[player].to_string()],
+            );
+            if player < last {
+                section.value.push_str("    ");
+            }
+        }
+    }
+}
+
+// Takes on the color of whichever player is winning the tug, and grows taller the closer they are
+// to winning it.
+fn display_tug_meter(
+    tug: Res<Tug>,
+    palette: Res<Palette>,
+    mut markers: Query<(&mut Transform, &mut Sprite), With<TugMarker>>,
+) {
+    let progress = (tug.held_seconds / TUG_HOLD_SECONDS).min(1.0);
+    for (mut transform, mut sprite) in markers.iter_mut() {
+        transform.translation.x = tug.pull * TUG_METER_SIZE.x / 2.0;
+        transform.scale.y = 1.0 + progress;
+        sprite.color = match tug.holder {
+            Some(0) => palette.left,
+            Some(_) => palette.right,
+            None => TEXT_COLOR,
+        };
+    }
+}
+
+fn spawn_versus_winner(
+    mut commands: Commands,
+    score: Res<VersusScore>,
+    player_count: Res<PlayerCount>,
+    palette: Res<Palette>,
+    text_styler: TextStyler,
+    localizer: Localizer,
+) {
+    let scores = &score.0[..num_competitors(&player_count)];
+    let best = scores.iter().copied().max().unwrap_or(0);
+    let leaders = (0..scores.len())
+        .filter(|player| scores[*player] == best)
+        .collect::<Vec<_>>();
+    let (message, color) = match leaders.as_slice() {
+        [winner] => (
+            localizer.format("versus.winner", &[&(winner + 1).to_string()]),
+            palette.slot(CursorSlot(*winner as u8)),
+        ),
+        _ => (localizer.get("versus.tie"), TEXT_COLOR),
+    };
+    println!("{}", message);
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 0.3, 1.0).with_scale(Vec3::splat(0.005)),
+            text: Text::from_section(
+                message,
+                TextStyle {
+                    font: text_styler.font(),
+                    font_size: 100.0,
+                    color,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        DespawnOnExitGameOver,
+        Name::new("VersusWinner"),
+    ));
+}
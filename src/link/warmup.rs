@@ -0,0 +1,162 @@
+use bevy::{
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+use bevy_xpbd_2d::prelude::*;
+
+use super::{
+    locale::Localizer,
+    player::Cursor,
+    spawn_level::{Layer, PLAY_REGION},
+    stalls::StallState,
+    text_style::TextStyler,
+    AppState, TEXT_COLOR,
+};
+use crate::{
+    mischief::{MischiefEvent, MischiefEventData},
+    util::cleanup_system,
+};
+
+const WARMUP_SECONDS: f32 = 15.0;
+const BALL_RADIUS: f32 = 0.15;
+// Balls are fired upward from the cursor at this speed, in meters per second.
+const BALL_SPEED: f32 = 5.0;
+
+// Before the first match, players get a short warmup where clicking fires a harmless ball from
+// their cursor to bat around with the rope.
+pub struct WarmupPlugin;
+
+impl Plugin for WarmupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Warmup), start_warmup)
+            .add_systems(
+                Update,
+                (
+                    fire_balls,
+                    despawn_balls,
+                    count_down.run_if(in_state(StallState::Running)),
+                )
+                    .run_if(in_state(AppState::Warmup)),
+            )
+            .add_systems(
+                OnExit(AppState::Warmup),
+                cleanup_system::<DespawnOnExitWarmup>,
+            );
+    }
+}
+
+#[derive(Component)]
+struct DespawnOnExitWarmup;
+
+#[derive(Component)]
+struct WarmupBall;
+
+#[derive(Component)]
+struct WarmupCountdown;
+
+#[derive(Resource)]
+struct Warmup {
+    timer: Timer,
+    ball_mesh: Mesh2dHandle,
+    ball_material: Handle<ColorMaterial>,
+}
+
+fn start_warmup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    text_styler: TextStyler,
+) {
+    commands.insert_resource(Warmup {
+        timer: Timer::from_seconds(WARMUP_SECONDS, TimerMode::Once),
+        ball_mesh: meshes
+            .add(
+                shape::Circle {
+                    radius: BALL_RADIUS,
+                    ..default()
+                }
+                .into(),
+            )
+            .into(),
+        ball_material: materials.add(ColorMaterial::from(TEXT_COLOR)),
+    });
+
+    let text_style = TextStyle {
+        font: text_styler.font(),
+        font_size: 100.0,
+        color: TEXT_COLOR,
+    };
+    commands.spawn((
+        Text2dBundle {
+            transform: Transform::from_xyz(0.0, 3.0, 1.0).with_scale(Vec3::splat(0.005)),
+            text: Text {
+                sections: vec![TextSection::new("", text_style)],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+            },
+            ..default()
+        },
+        WarmupCountdown,
+        DespawnOnExitWarmup,
+        Name::new("WarmupCountdown"),
+    ));
+}
+
+fn count_down(
+    mut warmup: ResMut<Warmup>,
+    mut countdowns: Query<&mut Text, With<WarmupCountdown>>,
+    mut app_state: ResMut<NextState<AppState>>,
+    localizer: Localizer,
+    time: Res<Time>,
+) {
+    if warmup.timer.tick(time.delta()).just_finished() {
+        app_state.set(AppState::ModeSelect);
+    }
+
+    let seconds_left = warmup.timer.remaining_secs().ceil();
+    for mut text in countdowns.iter_mut() {
+        text.sections[0].value = localizer.format("warmup.countdown", &[&seconds_left.to_string()]);
+    }
+}
+
+fn fire_balls(
+    mut commands: Commands,
+    mut mouse_events: EventReader<MischiefEvent>,
+    cursors: Query<(&Cursor, &Transform)>,
+    warmup: Res<Warmup>,
+) {
+    for event in mouse_events.iter() {
+        let MischiefEventData::Button { pressed: true, .. } = event.event_data else {
+            continue;
+        };
+        for (cursor, transform) in cursors.iter() {
+            if cursor.0 != Some(event.device) {
+                continue;
+            }
+            let position = transform.translation.truncate() + Vec2::new(0.0, 0.4);
+            commands.spawn((
+                MaterialMesh2dBundle {
+                    transform: Transform::from_xyz(position.x, position.y, 0.0),
+                    mesh: warmup.ball_mesh.clone(),
+                    material: warmup.ball_material.clone(),
+                    ..default()
+                },
+                RigidBody::Dynamic,
+                LinearVelocity(Vec2::new(0.0, BALL_SPEED)),
+                Collider::ball(BALL_RADIUS),
+                CollisionLayers::new([Layer::Shapes], [Layer::Rope, Layer::Level, Layer::Shapes]),
+                WarmupBall,
+                DespawnOnExitWarmup,
+                Name::new("WarmupBall"),
+            ));
+        }
+    }
+}
+
+fn despawn_balls(mut commands: Commands, balls: Query<(Entity, &Transform), With<WarmupBall>>) {
+    for (entity, transform) in balls.iter() {
+        if !PLAY_REGION.contains(transform.translation.truncate()) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -174,6 +174,11 @@ fn bindgen_test_layout_ManyMouseDriver() {
 extern "C" {
     pub fn ManyMouse_Init() -> ::std::os::raw::c_int;
 }
+extern "C" {
+    pub fn ManyMouse_InitWithDriver(
+        driver_name: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
 extern "C" {
     pub fn ManyMouse_DriverName() -> *const ::std::os::raw::c_char;
 }
@@ -0,0 +1,266 @@
+use std::fs;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::MischiefDevices;
+
+// Settings are saved by device name, since device ids only last until the game exits. Identical
+// mice share a name, and so share their settings too.
+const DEVICE_SETTINGS_PATH: &str = "device_settings.txt";
+
+// Calibrated devices are scaled to move like a mouse with this resolution.
+const REFERENCE_COUNTS_PER_INCH: f32 = 800.0;
+// Response curves pivot around this speed, in counts per second: motion this fast is the same
+// under every curve, and accelerating curves slow down anything slower and speed up anything faster.
+const REFERENCE_SPEED: f32 = 1000.0;
+// The exponent `ResponseCurve::Accelerated` uses.
+const ACCELERATED_EXPONENT: f32 = 1.5;
+
+/// Per-device input tweaks, keyed by device id. Every connected device gets an entry, with the
+/// settings last saved for a device of its name or else the defaults, so they can be edited from
+/// the inspector. Changes are saved to disk as they're made.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct MischiefDeviceSettings {
+    pub devices: HashMap<u32, DeviceSettings>,
+    // Everything in the settings file, by device name, including devices not connected this time.
+    #[reflect(ignore)]
+    saved: HashMap<String, DeviceSettings>,
+}
+
+impl MischiefDeviceSettings {
+    pub fn get(&self, device: u32) -> DeviceSettings {
+        self.devices.get(&device).cloned().unwrap_or_default()
+    }
+
+    pub fn load() -> Self {
+        let mut settings = Self::default();
+        let Ok(contents) = fs::read_to_string(DEVICE_SETTINGS_PATH) else {
+            return settings;
+        };
+        // Each device's settings follow its name in brackets, one `key=value` per line.
+        let mut device = None;
+        for line in contents.lines() {
+            if let Some(name) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                device = Some(settings.saved.entry(name.to_owned()).or_default());
+                continue;
+            }
+            let applied = device.as_mut().is_some_and(|device| {
+                line.split_once('=')
+                    .is_some_and(|(key, value)| device.set(key, value))
+            });
+            if !applied {
+                println!("Skipping malformed device settings line: {}", line);
+            }
+        }
+        settings
+    }
+
+    fn save(&mut self, devices: &MischiefDevices) {
+        for device in devices.iter() {
+            if let Some(device_settings) = self.devices.get(&device.id) {
+                self.saved
+                    .insert(device.name.clone(), device_settings.clone());
+            }
+        }
+
+        let mut names = self.saved.keys().collect::<Vec<_>>();
+        names.sort();
+        let mut contents = String::new();
+        for name in names {
+            contents += &format!("[{}]\n", name);
+            for line in self.saved[name].lines() {
+                contents += &format!("{}\n", line);
+            }
+        }
+        if let Err(error) = fs::write(DEVICE_SETTINGS_PATH, contents) {
+            println!("Failed to save device settings: {}", error);
+        }
+    }
+}
+
+#[derive(Reflect, Debug, Clone)]
+pub struct DeviceSettings {
+    pub sensitivity: f32,
+    pub invert_x: bool,
+    pub invert_y: bool,
+    pub swap_axes: bool,
+    /// Time constant of a low-pass filter on relative motion, in seconds. Evens out jittery
+    /// sensors and shaky hands at the cost of some lag; 0 turns it off.
+    pub smoothing_seconds: f32,
+    /// The device's resolution, measured on the calibration screen or entered by hand, so that
+    /// high and low DPI mice cover the same distance for the same hand movement. 0 means unknown,
+    /// which leaves motion unscaled.
+    pub counts_per_inch: f32,
+    pub response_curve: ResponseCurve,
+    /// Motion slower than this, in counts per second, is ignored, so a resting mouse with a noisy
+    /// sensor doesn't drift. 0 turns it off.
+    pub dead_zone: f32,
+}
+
+/// How the speed of the hand maps to the speed of the cursor.
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq)]
+pub enum ResponseCurve {
+    /// The cursor moves in proportion to the hand.
+    #[default]
+    Linear,
+    /// Fast motion carries the cursor further, for crossing the screen without lifting the mouse.
+    Accelerated,
+    /// Speed is raised to this power. Above 1 accelerates, below 1 decelerates.
+    Exponent(f32),
+}
+
+impl ResponseCurve {
+    fn exponent(self) -> f32 {
+        match self {
+            Self::Linear => 1.0,
+            Self::Accelerated => ACCELERATED_EXPONENT,
+            Self::Exponent(exponent) => exponent,
+        }
+    }
+
+    // Saved as its name, or as the bare exponent for `Exponent`.
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "linear" => Some(Self::Linear),
+            "accelerated" => Some(Self::Accelerated),
+            _ => value.parse().ok().map(Self::Exponent),
+        }
+    }
+
+    fn saved(self) -> String {
+        match self {
+            Self::Linear => "linear".to_string(),
+            Self::Accelerated => "accelerated".to_string(),
+            Self::Exponent(exponent) => exponent.to_string(),
+        }
+    }
+}
+
+impl Default for DeviceSettings {
+    fn default() -> Self {
+        Self {
+            sensitivity: 1.0,
+            invert_x: false,
+            invert_y: false,
+            swap_axes: false,
+            smoothing_seconds: 0.0,
+            counts_per_inch: 0.0,
+            response_curve: ResponseCurve::Linear,
+            dead_zone: 0.0,
+        }
+    }
+}
+
+impl DeviceSettings {
+    /// Applies these settings to a relative motion, in the device's own coordinates.
+    pub fn apply(&self, motion: Vec2) -> Vec2 {
+        let mut motion = match self.swap_axes {
+            true => Vec2::new(motion.y, motion.x),
+            false => motion,
+        };
+        if self.invert_x {
+            motion.x = -motion.x;
+        }
+        if self.invert_y {
+            motion.y = -motion.y;
+        }
+        motion * self.sensitivity * self.resolution_scale()
+    }
+
+    /// Applies the dead zone and response curve to a velocity, in counts per second, after
+    /// `apply`. Works on speed rather than per-frame motion so the feel doesn't change with the
+    /// frame rate.
+    pub fn respond(&self, velocity: Vec2) -> Vec2 {
+        let speed = velocity.length();
+        if speed <= self.dead_zone || speed == 0.0 {
+            return Vec2::ZERO;
+        }
+        let exponent = self.response_curve.exponent();
+        if exponent == 1.0 {
+            return velocity;
+        }
+        velocity * (speed / REFERENCE_SPEED).powf(exponent - 1.0)
+    }
+
+    fn resolution_scale(&self) -> f32 {
+        match self.counts_per_inch > 0.0 {
+            true => REFERENCE_COUNTS_PER_INCH / self.counts_per_inch,
+            false => 1.0,
+        }
+    }
+
+    // Sets one of the settings from its saved form, and says whether it was understood.
+    fn set(&mut self, key: &str, value: &str) -> bool {
+        let number = || value.parse::<f32>().ok();
+        let flag = || value.parse::<bool>().ok();
+        let applied = match key {
+            "sensitivity" => number().map(|sensitivity| self.sensitivity = sensitivity),
+            "invert_x" => flag().map(|on| self.invert_x = on),
+            "invert_y" => flag().map(|on| self.invert_y = on),
+            "swap_axes" => flag().map(|on| self.swap_axes = on),
+            "smoothing_seconds" => number().map(|seconds| self.smoothing_seconds = seconds),
+            "counts_per_inch" => number().map(|cpi| self.counts_per_inch = cpi),
+            "response_curve" => {
+                ResponseCurve::parse(value).map(|curve| self.response_curve = curve)
+            }
+            "dead_zone" => number().map(|dead_zone| self.dead_zone = dead_zone),
+            _ => None,
+        };
+        applied.is_some()
+    }
+
+    fn lines(&self) -> Vec<String> {
+        vec![
+            format!("sensitivity={}", self.sensitivity),
+            format!("invert_x={}", self.invert_x),
+            format!("invert_y={}", self.invert_y),
+            format!("swap_axes={}", self.swap_axes),
+            format!("smoothing_seconds={}", self.smoothing_seconds),
+            format!("counts_per_inch={}", self.counts_per_inch),
+            format!("response_curve={}", self.response_curve.saved()),
+            format!("dead_zone={}", self.dead_zone),
+        ]
+    }
+
+    /// Runs one step of the motion filter: an exponential moving average from `previous` towards
+    /// `velocity`, independent of frame rate.
+    pub fn smooth(&self, previous: Vec2, velocity: Vec2, delta_seconds: f32) -> Vec2 {
+        if self.smoothing_seconds <= 0.0 {
+            return velocity;
+        }
+        let alpha = 1.0 - (-delta_seconds / self.smoothing_seconds).exp();
+        previous.lerp(velocity, alpha)
+    }
+}
+
+pub fn register_device_settings(
+    devices: Res<MischiefDevices>,
+    mut settings: ResMut<MischiefDeviceSettings>,
+) {
+    if !devices.is_changed() {
+        return;
+    }
+    for device in devices.iter() {
+        // Check first to avoid marking the resource as changed every frame.
+        if !settings.devices.contains_key(&device.id) {
+            let device_settings = settings
+                .saved
+                .get(&device.name)
+                .cloned()
+                .unwrap_or_default();
+            settings.devices.insert(device.id, device_settings);
+        }
+    }
+}
+
+pub fn save_device_settings(
+    devices: Res<MischiefDevices>,
+    mut settings: ResMut<MischiefDeviceSettings>,
+) {
+    // Saving mustn't mark the settings as changed, or they'd be saved again every frame.
+    settings.bypass_change_detection().save(&devices);
+}
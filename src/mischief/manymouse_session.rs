@@ -3,17 +3,98 @@ mod bindings {
     include!("bindings.rs");
 }
 
-use std::{error::Error, ffi::CStr};
+use std::{error::Error, ffi::CStr, str::FromStr};
 
 pub use self::bindings::ManyMouseEvent;
 
+/// Which ManyMouse driver to use. On Linux, XInput2 and evdev behave differently with
+/// cursor-locked windows, so it's useful to be able to pick one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ManyMouseBackend {
+    /// Try each driver built for this platform in order, and use the first that works.
+    #[default]
+    Auto,
+    XInput2,
+    Evdev,
+    WmInput,
+    HidManager,
+    HidUtilities,
+}
+
+impl ManyMouseBackend {
+    fn driver_name(&self) -> Option<&'static CStr> {
+        let name: &'static [u8] = match self {
+            ManyMouseBackend::Auto => return None,
+            ManyMouseBackend::XInput2 => b"X11 XInput2 extension\0",
+            ManyMouseBackend::Evdev => b"Linux /dev/input/event* interface\0",
+            ManyMouseBackend::WmInput => b"Windows XP and later WM_INPUT interface\0",
+            ManyMouseBackend::HidManager => b"Mac OS X 10.5+ HID Manager\0",
+            ManyMouseBackend::HidUtilities => b"Mac OS X Legacy HID Utilities\0",
+        };
+        CStr::from_bytes_with_nul(name).ok()
+    }
+}
+
+impl FromStr for ManyMouseBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ManyMouseBackend::Auto),
+            "xinput2" => Ok(ManyMouseBackend::XInput2),
+            "evdev" => Ok(ManyMouseBackend::Evdev),
+            "wminput" => Ok(ManyMouseBackend::WmInput),
+            "hidmanager" => Ok(ManyMouseBackend::HidManager),
+            "hidutilities" => Ok(ManyMouseBackend::HidUtilities),
+            _ => Err(format!("Unknown ManyMouse backend '{}', expected one of auto, xinput2, evdev, wminput, hidmanager or hidutilities", s)),
+        }
+    }
+}
+
 pub struct ManyMouseSession {
-    pub devices: Vec<InputDevice>
+    pub devices: Vec<InputDevice>,
+    backend: ManyMouseBackend,
 }
 
 impl ManyMouseSession {
-    pub fn init() -> Result<Self, Box<dyn Error>> {
-        let num_devices: u32 = ManyMouseSession::call_init()?;
+    /// Initializes ManyMouse using only the given backend. Fails if that backend isn't built for
+    /// this platform or can't start.
+    pub fn init_with_backend(backend: ManyMouseBackend) -> Result<Self, Box<dyn Error>> {
+        let num_devices: u32 = ManyMouseSession::call_init(backend)?;
+        let devices = ManyMouseSession::enumerate(num_devices)?;
+
+        Ok(ManyMouseSession{devices, backend})
+    }
+
+    /// The name of the driver ManyMouse is using, e.g. "X11 XInput2 extension".
+    pub fn driver_name(&self) -> Option<String> {
+        let ptr = unsafe {
+            bindings::ManyMouse_DriverName()
+        };
+        if ptr.is_null() {
+            return None;
+        }
+        Some(unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned())
+    }
+
+    /// Shuts ManyMouse down and initializes it again, picking up any devices connected or
+    /// disconnected since the last init. ManyMouse renumbers devices when it restarts, so ids in
+    /// `devices` may change. Events queued before the call are discarded.
+    ///
+    /// If re-initializing fails, the session is left with no devices and will not report any
+    /// events; calling `reenumerate` again may recover it.
+    pub fn reenumerate(&mut self) -> Result<(), Box<dyn Error>> {
+        // ManyMouse refuses to init while already initialized, so it must be shut down first.
+        self.devices.clear();
+        unsafe {
+            bindings::ManyMouse_Quit()
+        };
+        let num_devices: u32 = ManyMouseSession::call_init(self.backend)?;
+        self.devices = ManyMouseSession::enumerate(num_devices)?;
+        Ok(())
+    }
+
+    fn enumerate(num_devices: u32) -> Result<Vec<InputDevice>, Box<dyn Error>> {
         let mut devices = Vec::new();
 
         for id in 0..num_devices {
@@ -24,12 +105,13 @@ impl ManyMouseSession {
                 }
                 CStr::from_ptr(ptr)
             };
-            devices.push(InputDevice{id, name: name.to_string_lossy().into_owned()});
+            devices.push(InputDevice{name: name.to_string_lossy().into_owned()});
         }
 
-        Ok(ManyMouseSession{devices})
+        Ok(devices)
     }
 
+    /// Polls the next queued event, if any.
     pub fn poll_event(&self) -> Result<Option<ManyMouseEvent>, Box<dyn Error>> {
         let mut event = ManyMouseEvent::default();
         let poll_response: i32 = unsafe {
@@ -49,13 +131,16 @@ impl ManyMouseSession {
         Ok(Some(event))
     }
 
-    fn call_init() -> Result<u32, Box<dyn Error>> {
+    fn call_init(backend: ManyMouseBackend) -> Result<u32, Box<dyn Error>> {
         let init_response: i32 = unsafe {
-            bindings::ManyMouse_Init()
+            match backend.driver_name() {
+                Some(name) => bindings::ManyMouse_InitWithDriver(name.as_ptr()),
+                None => bindings::ManyMouse_Init(),
+            }
         };
     
         if init_response == -1 {
-            return Err("Error initializing ManyMouse".into());
+            return Err(format!("Error initializing ManyMouse with backend {:?}", backend).into());
         }
         Ok(init_response as u32)
     }
@@ -70,11 +155,74 @@ impl Drop for ManyMouseSession {
     }
 }
 
+/// A device as ManyMouse lists it; its position in `ManyMouseSession::devices` is its index.
 pub struct InputDevice {
-    pub id:u32,
     pub name:String,
 }
 
+/// What a device has shown it can do. ManyMouse has no way to ask a device about its axes or
+/// buttons up front, so these are learned from its events and fill in as the device is used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeviceCapabilities {
+    /// Whether the device has reported relative motion, like a mouse.
+    pub relative: bool,
+    /// The range of each absolute axis, x then y, that the device has reported, if any.
+    pub absolute_range: [Option<(i32, i32)>; 2],
+    /// One more than the highest button the device has reported.
+    pub buttons: u32,
+}
+
+impl DeviceCapabilities {
+    pub fn observe(&mut self, event: &ManyMouseEvent) {
+        match event.type_ {
+            bindings::ManyMouseEventType_MANYMOUSE_EVENT_ABSMOTION => {
+                let Some(range) = self.absolute_range.get_mut(event.item as usize) else {
+                    return;
+                };
+                // Some backends leave the range empty, in which case the values seen so far
+                // are the best guess at it.
+                let (min, max) = match event.minval < event.maxval {
+                    true => (event.minval, event.maxval),
+                    false => (event.value, event.value),
+                };
+                *range = Some(match *range {
+                    Some((old_min, old_max)) => (old_min.min(min), old_max.max(max)),
+                    None => (min, max),
+                });
+            }
+            bindings::ManyMouseEventType_MANYMOUSE_EVENT_RELMOTION => self.relative = true,
+            bindings::ManyMouseEventType_MANYMOUSE_EVENT_BUTTON => {
+                self.buttons = self.buttons.max(event.item + 1);
+            }
+            _ => {}
+        }
+    }
+
+    /// Maps a value on absolute axis `axis` (0 for x, 1 for y) to 0..1 across the axis's range,
+    /// or to the middle if the range isn't known yet.
+    pub fn normalize(&self, axis: u32, value: i32) -> f32 {
+        match self.absolute_range.get(axis as usize).copied().flatten() {
+            Some((min, max)) if max > min => {
+                ((value - min) as f32 / (max - min) as f32).clamp(0.0, 1.0)
+            }
+            _ => 0.5,
+        }
+    }
+
+    /// Whether the device can move a cursor as far as is known: it either moves relatively, or has
+    /// an absolute position that spans some range. Devices that haven't moved yet get the benefit
+    /// of the doubt.
+    pub fn can_point(&self) -> bool {
+        let absolute = self.absolute_range.iter().any(Option::is_some);
+        let spans = self
+            .absolute_range
+            .iter()
+            .flatten()
+            .all(|(min, max)| max > min);
+        self.relative || !absolute || spans
+    }
+}
+
 impl Default for ManyMouseEvent {
     fn default() -> Self {
         return ManyMouseEvent {
@@ -1,56 +1,380 @@
-use bevy::prelude::*;
+//! Reads several mice at once through ManyMouse, as Bevy events and resources. Nothing in here
+//! knows about the game: add `MischiefPlugin`, read `MischiefEvent`s or the per-frame
+//! `MouseMotionAccumulator`, fake input with the `MischiefEventInjector`, look devices up in
+//! `MischiefDevices`, and record which player each device belongs to in `MischiefAssignments`.
+//! Systems that touch the `MischiefSession` itself should run if `session_ready`, since it's
+//! missing when ManyMouse fails to start.
 
-use std::error::Error;
+use bevy::{prelude::*, utils::HashMap};
+
+use std::{error::Error, time::Instant};
 
 #[allow(warnings)]
 mod bindings {
     include!("bindings.rs");
 }
+pub mod device_settings;
 pub mod manymouse_session;
-use manymouse_session::{ManyMouseEvent, ManyMouseSession};
+mod poll_thread;
+pub mod touch;
+use device_settings::{register_device_settings, save_device_settings};
+pub use device_settings::MischiefDeviceSettings;
+pub use manymouse_session::DeviceCapabilities;
+pub use manymouse_session::ManyMouseBackend;
+use manymouse_session::ManyMouseEvent;
+use poll_thread::{PollMessage, PollThread};
+use touch::{translate_touches, TouchFingers};
 
-pub struct MischiefPlugin;
+pub struct MischiefPlugin {
+    /// Which ManyMouse driver to use.
+    pub backend: ManyMouseBackend,
+    /// How often to restart ManyMouse looking for newly connected mice, while hot-plug scanning is
+    /// enabled.
+    pub hotplug_scan_seconds: f32,
+    /// Whether touchscreen fingers are reported as devices too, see `touch`.
+    pub touch: bool,
+}
+
+impl Default for MischiefPlugin {
+    fn default() -> Self {
+        Self {
+            backend: ManyMouseBackend::Auto,
+            hotplug_scan_seconds: 2.0,
+            touch: true,
+        }
+    }
+}
 
 impl Plugin for MischiefPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_non_send_resource::<MischiefSession>(MischiefSession::new().unwrap())
-            .add_event::<MischiefEvent>()
-            .add_systems(Update, poll_events);
+        let mut devices = MischiefDevices::default();
+        match MischiefSession::new(self.backend, &mut devices) {
+            Ok(session) => {
+                app.insert_non_send_resource::<MischiefSession>(session);
+            }
+            Err(error) => {
+                println!("Failed to initialize ManyMouse: {}", error);
+                app.insert_resource(MischiefInitError(error.to_string()));
+            }
+        }
+        app.add_event::<MischiefEvent>()
+            .insert_resource(devices)
+            .init_resource::<MischiefAssignments>()
+            .insert_resource(MischiefBackend(self.backend))
+            .insert_resource(MischiefHotplug {
+                enabled: true,
+                timer: Timer::from_seconds(self.hotplug_scan_seconds, TimerMode::Repeating),
+            })
+            .add_event::<MischiefRescan>()
+            .add_event::<MischiefRetryInit>()
+            .init_resource::<MouseMotionAccumulator>()
+            .init_resource::<MischiefEventInjector>()
+            .register_type::<MischiefDeviceSettings>()
+            .insert_resource(MischiefDeviceSettings::load())
+            .add_systems(
+                Update,
+                (
+                    inject_events,
+                    poll_events.run_if(session_ready),
+                    accumulate_motion,
+                    (scan_for_devices, rescan_on_request).run_if(session_ready),
+                    register_device_settings,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Last,
+                save_device_settings.run_if(resource_changed::<MischiefDeviceSettings>()),
+            )
+            .add_systems(
+                Update,
+                retry_init.run_if(resource_exists::<MischiefInitError>()),
+            );
+
+        if self.touch {
+            // Touch works without ManyMouse, and goes through the injector.
+            app.init_resource::<TouchFingers>()
+                .add_systems(Update, translate_touches.before(inject_events));
+        }
+
+        #[cfg(target_os = "windows")]
+        app.add_systems(PostStartup, claim_raw_input.run_if(session_ready));
+    }
+}
+
+// Windows only delivers raw mouse input (WM_INPUT) to the most recent window registered for it, and
+// winit registers its own windows too. Restarting ManyMouse once the game window exists makes the
+// poll thread's hidden window the most recent registration.
+#[cfg(target_os = "windows")]
+fn claim_raw_input(session: NonSend<MischiefSession>) {
+    session.rescan();
+}
+
+/// Present while ManyMouse has failed to initialize, with a description of what went wrong. Send
+/// `MischiefRetryInit` to try again; on success this is removed and `MischiefSession` is inserted.
+#[derive(Resource, Debug)]
+pub struct MischiefInitError(pub String);
+
+#[derive(Event, Debug, Default)]
+pub struct MischiefRetryInit;
+
+/// Run condition for systems that need the `MischiefSession`, which doesn't exist if ManyMouse
+/// failed to initialize.
+pub fn session_ready(session: Option<NonSend<MischiefSession>>) -> bool {
+    session.is_some()
+}
+
+// The driver the plugin was configured with, for retries.
+#[derive(Resource)]
+struct MischiefBackend(ManyMouseBackend);
+
+// Exclusive, since the session is a non-send resource and can only be inserted through the world.
+fn retry_init(world: &mut World) {
+    let requested = world
+        .resource_mut::<Events<MischiefRetryInit>>()
+        .drain()
+        .count()
+        > 0;
+    if !requested {
+        return;
+    }
+
+    let backend = world.resource::<MischiefBackend>().0;
+    let session = world.resource_scope(|_, mut devices: Mut<MischiefDevices>| {
+        MischiefSession::new(backend, &mut devices)
+    });
+    match session {
+        Ok(session) => {
+            world.insert_non_send_resource(session);
+            world.remove_resource::<MischiefInitError>();
+        }
+        Err(error) => {
+            println!("Failed to initialize ManyMouse: {}", error);
+            world.insert_resource(MischiefInitError(error.to_string()));
+        }
     }
 }
 
+/// The running ManyMouse session. Only exists while ManyMouse is initialized, see `session_ready`.
 #[derive(Resource)]
 pub struct MischiefSession {
-    pub session: ManyMouseSession,
+    poll_thread: PollThread,
+    // Stable device id for each ManyMouse device index. ManyMouse renumbers devices whenever it
+    // restarts, so raw events are translated through this table.
+    device_ids: Vec<u32>,
+}
+
+/// Every ManyMouse device seen so far, connected or not, indexed by its stable device id. Ids stay
+/// the same across rescans and retries, so they're safe to keep around.
+#[derive(Resource, Default, Debug)]
+pub struct MischiefDevices(Vec<MischiefDevice>);
+
+impl MischiefDevices {
+    pub fn get(&self, device: u32) -> Option<&MischiefDevice> {
+        self.0.get(device as usize)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MischiefDevice> {
+        self.0.iter()
+    }
+
+    pub fn connected(&self) -> impl Iterator<Item = &MischiefDevice> {
+        self.0.iter().filter(|device| device.connected)
+    }
+
+    /// What `device` has shown it can do, or `None` if it isn't a ManyMouse device.
+    pub fn capabilities(&self, device: u32) -> Option<DeviceCapabilities> {
+        self.get(device).map(|device| device.capabilities)
+    }
+
+    // ManyMouse doesn't expose any persistent device identifier, so devices are matched up by name,
+    // and identical mice by where ManyMouse lists them: a device listed at the same index as before
+    // keeps its id ahead of any other device with the same name. Returns the stable id for each
+    // name, in order.
+    fn match_names(&mut self, names: &[String]) -> (Vec<u32>, DeviceChanges) {
+        let mut changes = DeviceChanges::default();
+        let mut matched = vec![false; self.0.len()];
+        let mut existing = vec![None; names.len()];
+        for same_index in [true, false] {
+            for (index, (found, name)) in existing.iter_mut().zip(names.iter()).enumerate() {
+                if found.is_some() {
+                    continue;
+                }
+                *found = (0..self.0.len()).find(|&i| {
+                    !matched[i]
+                        && &self.0[i].name == name
+                        && (!same_index || self.0[i].index == index as u32)
+                });
+                if let Some(i) = *found {
+                    matched[i] = true;
+                }
+            }
+        }
+        let mut ids = Vec::new();
+
+        for (index, (name, existing)) in names.iter().zip(existing).enumerate() {
+            let id = match existing {
+                Some(i) => {
+                    self.0[i].index = index as u32;
+                    if !self.0[i].connected {
+                        self.0[i].connected = true;
+                        changes.connected.push(self.0[i].id);
+                    }
+                    self.0[i].id
+                }
+                None => {
+                    let id = self.0.len() as u32;
+                    println!("Mouse connected: {}", name);
+                    self.0.push(MischiefDevice {
+                        id,
+                        name: name.clone(),
+                        connected: true,
+                        capabilities: DeviceCapabilities::default(),
+                        index: index as u32,
+                    });
+                    matched.push(true);
+                    changes.connected.push(id);
+                    id
+                }
+            };
+            ids.push(id);
+        }
+
+        for (device, matched) in self.0.iter_mut().zip(matched) {
+            if !matched && device.connected {
+                device.connected = false;
+                changes.disconnected.push(device.id);
+            }
+        }
+
+        (ids, changes)
+    }
+}
+
+/// Which player each device belongs to, by player index. Mischief itself never assigns devices;
+/// that's up to the app, which keeps this up to date so anything can look assignments up.
+#[derive(Resource, Default, Debug)]
+pub struct MischiefAssignments(HashMap<u8, u32>);
+
+impl MischiefAssignments {
+    pub fn player(&self, device: u32) -> Option<u8> {
+        self.0
+            .iter()
+            .find(|(_, &assigned)| assigned == device)
+            .map(|(&player, _)| player)
+    }
+
+    pub fn assign(&mut self, player: u8, device: u32) {
+        self.0.insert(player, device);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MischiefDevice {
+    pub id: u32,
+    pub name: String,
+    pub connected: bool,
+    /// Learned from the device's events. Kept across reconnects, since it's the same device.
+    pub capabilities: DeviceCapabilities,
+    // Where ManyMouse listed the device when it was last connected, to tell identical mice apart.
+    index: u32,
+}
+
+#[derive(Default)]
+pub struct DeviceChanges {
+    pub connected: Vec<u32>,
+    pub disconnected: Vec<u32>,
 }
 
 impl MischiefSession {
-    pub fn new() -> Result<Self, Box<dyn Error>> {
+    /// Starts ManyMouse, adding the devices it finds to `devices`.
+    pub fn new(
+        backend: ManyMouseBackend,
+        devices: &mut MischiefDevices,
+    ) -> Result<Self, Box<dyn Error>> {
         println!("Initializing ManyMouse");
-        let session = ManyMouseSession::init()?;
-        println!("Found {} mice", session.devices.len());
-        Ok(Self { session })
+        let (poll_thread, names) = PollThread::spawn(backend)?;
+        let (device_ids, _) = devices.match_names(&names);
+        Ok(Self {
+            poll_thread,
+            device_ids,
+        })
     }
+
+    /// Asks the poll thread to restart ManyMouse to pick up hot-plugged mice. The resulting
+    /// connects and disconnects are reported by `poll_events` once the restart finishes, and
+    /// devices that are still connected keep their ids.
+    pub fn rescan(&self) {
+        self.poll_thread.request_rescan();
+    }
+
+    fn device_id(&self, index: u32) -> Option<u32> {
+        self.device_ids.get(index as usize).copied()
+    }
+}
+
+/// Controls periodic rescanning for hot-plugged mice. Rescanning restarts ManyMouse, so it should
+/// only be enabled while a brief input hitch is acceptable.
+#[derive(Resource)]
+pub struct MischiefHotplug {
+    pub enabled: bool,
+    timer: Timer,
 }
 
 #[derive(Event, Debug)]
 pub struct MischiefEvent {
     pub device: u32,
     pub event_data: MischiefEventData,
+    /// When the event was polled from ManyMouse. Events arrive in batches once per frame, but
+    /// these are monotonic and as precise as the poll thread, so their spacing reflects when the
+    /// mouse actually reported.
+    pub timestamp: Instant,
 }
 
 #[derive(Debug)]
 pub enum MischiefEventData {
-    AbsMotion,
-    RelMotion { x: i32, y: i32 },
-    Button { button: u32, pressed: bool },
-    Scroll,
+    /// Absolute position along one axis, normalized to 0..1 across the device's range (see
+    /// `DeviceCapabilities::normalize`). As with relative motion, each event only carries one axis.
+    AbsMotion {
+        x: Option<f32>,
+        y: Option<f32>,
+    },
+    RelMotion {
+        x: i32,
+        y: i32,
+    },
+    Button {
+        button: u32,
+        pressed: bool,
+    },
+    Scroll {
+        axis: ScrollAxis,
+        delta: i32,
+    },
+    Connect,
     Disconnect,
 }
 
-fn parse_event(event: ManyMouseEvent) -> MischiefEvent {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollAxis {
+    Vertical,
+    Horizontal,
+}
+
+fn parse_event(
+    event: ManyMouseEvent,
+    capabilities: &DeviceCapabilities,
+    timestamp: Instant,
+) -> MischiefEvent {
     let event_data = match event.type_ {
-        bindings::ManyMouseEventType_MANYMOUSE_EVENT_ABSMOTION => MischiefEventData::AbsMotion,
+        bindings::ManyMouseEventType_MANYMOUSE_EVENT_ABSMOTION => {
+            let x = event.item == 0;
+            let value = capabilities.normalize(event.item, event.value);
+            MischiefEventData::AbsMotion {
+                x: if x { Some(value) } else { None },
+                y: if !x { Some(value) } else { None },
+            }
+        }
         bindings::ManyMouseEventType_MANYMOUSE_EVENT_RELMOTION => {
             let x = event.item == 0;
             MischiefEventData::RelMotion {
@@ -65,7 +389,14 @@ fn parse_event(event: ManyMouseEvent) -> MischiefEvent {
                 pressed: event.value == 1,
             }
         }
-        bindings::ManyMouseEventType_MANYMOUSE_EVENT_SCROLL => MischiefEventData::Scroll,
+        bindings::ManyMouseEventType_MANYMOUSE_EVENT_SCROLL => MischiefEventData::Scroll {
+            // ManyMouse reports the vertical wheel as item 0 and the horizontal wheel as item 1.
+            axis: match event.item {
+                0 => ScrollAxis::Vertical,
+                _ => ScrollAxis::Horizontal,
+            },
+            delta: event.value,
+        },
         bindings::ManyMouseEventType_MANYMOUSE_EVENT_DISCONNECT => MischiefEventData::Disconnect,
         _ => {
             panic!("Unknown event type");
@@ -74,12 +405,158 @@ fn parse_event(event: ManyMouseEvent) -> MischiefEvent {
     MischiefEvent {
         device: event.device,
         event_data,
+        timestamp,
     }
 }
 
-pub fn poll_events(session: NonSend<MischiefSession>, mut events: EventWriter<MischiefEvent>) {
+pub fn poll_events(
+    mut session: NonSendMut<MischiefSession>,
+    mut devices: ResMut<MischiefDevices>,
+    mut events: EventWriter<MischiefEvent>,
+) {
+    let _span = info_span!("mischief_poll").entered();
     // println!("Polling events");
-    while let Some(event) = session.session.poll_event().unwrap() {
-        events.send(parse_event(event));
+    while let Some(message) = session.poll_thread.try_recv() {
+        match message {
+            PollMessage::Event(event, timestamp) => {
+                let Some(id) = session.device_id(event.device) else {
+                    continue;
+                };
+                let device = &mut devices.0[id as usize];
+                device.capabilities.observe(&event);
+                let mut event = parse_event(event, &device.capabilities, timestamp);
+                event.device = id;
+                if let MischiefEventData::Disconnect = event.event_data {
+                    device.connected = false;
+                }
+                events.send(event);
+            }
+            PollMessage::Devices(names) => {
+                let (device_ids, changes) = devices.match_names(&names);
+                session.device_ids = device_ids;
+                send_device_changes(changes, Instant::now(), &mut events);
+            }
+            PollMessage::Error(error) => println!("{}", error),
+        }
+    }
+}
+
+// Stand-in device ids for input that doesn't come from ManyMouse. Real device ids are small
+// indices, so these count down from `u32::MAX`, and every stand-in takes its id from this table so
+// no two ever share one.
+
+/// The cursor driven by the keyboard.
+pub const KEYBOARD_DEVICE: u32 = u32::MAX;
+/// The cursor driven by a gamepad.
+pub const GAMEPAD_DEVICE: u32 = u32::MAX - 1;
+/// Fingers on a touchscreen, one per simultaneous touch.
+pub const TOUCH_DEVICES: [u32; 2] = [u32::MAX - 3, u32::MAX - 2];
+/// The cursor that mirrors the other end of the rope in practice mode.
+pub const MIRROR_DEVICE: u32 = u32::MAX - 4;
+
+/// Queues made-up events, for tutorials, bots and tests. They're sent as `MischiefEvent`s just
+/// before ManyMouse's own each frame, so they go through exactly the same systems as real input.
+/// Use device ids that no ManyMouse device will have, like the stand-in ids above.
+#[derive(Resource, Default, Debug)]
+pub struct MischiefEventInjector(Vec<MischiefEvent>);
+
+impl MischiefEventInjector {
+    /// Queues an event from `device`, stamped with the current time.
+    pub fn send(&mut self, device: u32, event_data: MischiefEventData) {
+        self.0.push(MischiefEvent {
+            device,
+            event_data,
+            timestamp: Instant::now(),
+        });
+    }
+}
+
+pub fn inject_events(
+    mut injector: ResMut<MischiefEventInjector>,
+    mut events: EventWriter<MischiefEvent>,
+) {
+    if injector.0.is_empty() {
+        return;
+    }
+    events.send_batch(injector.0.drain(..));
+}
+
+/// Relative motion of each device summed over the current frame, in raw counts. Systems ordered
+/// after `accumulate_motion` can read it instead of re-reading every `MischiefEvent`.
+#[derive(Resource, Default, Debug)]
+pub struct MouseMotionAccumulator {
+    motion: HashMap<u32, IVec2>,
+}
+
+impl MouseMotionAccumulator {
+    /// This frame's motion of `device`, zero if it didn't move.
+    pub fn get(&self, device: u32) -> IVec2 {
+        self.motion.get(&device).copied().unwrap_or_default()
+    }
+
+    /// Every device which moved this frame, with its motion.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, IVec2)> + '_ {
+        self.motion
+            .iter()
+            .map(|(&device, &motion)| (device, motion))
+    }
+}
+
+pub fn accumulate_motion(
+    mut accumulator: ResMut<MouseMotionAccumulator>,
+    mut events: EventReader<MischiefEvent>,
+) {
+    accumulator.motion.clear();
+    for event in events.iter() {
+        if let MischiefEventData::RelMotion { x, y } = event.event_data {
+            *accumulator.motion.entry(event.device).or_default() += IVec2::new(x, y);
+        }
+    }
+}
+
+fn scan_for_devices(
+    session: NonSend<MischiefSession>,
+    mut hotplug: ResMut<MischiefHotplug>,
+    time: Res<Time>,
+) {
+    if !hotplug.enabled || !hotplug.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    session.rescan();
+}
+
+/// Send to restart ManyMouse and re-enumerate devices, e.g. after a mouse is unplugged.
+#[derive(Event, Debug, Default)]
+pub struct MischiefRescan;
+
+fn rescan_on_request(session: NonSend<MischiefSession>, mut requests: EventReader<MischiefRescan>) {
+    if requests.iter().count() == 0 {
+        return;
+    }
+
+    session.rescan();
+}
+
+// Connects and disconnects are only noticed once a rescan's results arrive, so they're stamped
+// with the time they're processed.
+fn send_device_changes(
+    changes: DeviceChanges,
+    timestamp: Instant,
+    events: &mut EventWriter<MischiefEvent>,
+) {
+    for device in changes.connected {
+        events.send(MischiefEvent {
+            device,
+            event_data: MischiefEventData::Connect,
+            timestamp,
+        });
+    }
+    for device in changes.disconnected {
+        events.send(MischiefEvent {
+            device,
+            event_data: MischiefEventData::Disconnect,
+            timestamp,
+        });
     }
 }
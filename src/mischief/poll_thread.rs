@@ -0,0 +1,144 @@
+use std::{
+    error::Error,
+    thread,
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::*;
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
+
+use super::manymouse_session::{ManyMouseBackend, ManyMouseEvent, ManyMouseSession};
+
+// How long the poll thread sleeps when ManyMouse has nothing queued. Short enough that a 1000Hz
+// mouse never has more than a report or two waiting.
+const IDLE_SLEEP: Duration = Duration::from_micros(500);
+
+pub enum PollCommand {
+    Rescan,
+}
+
+pub enum PollMessage {
+    /// ManyMouse (re)started with devices with these names. Events sent after this refer to
+    /// devices by their index in this list.
+    Devices(Vec<String>),
+    /// An event, with when it was read from ManyMouse.
+    Event(ManyMouseEvent, Instant),
+    Error(String),
+}
+
+/// Polls ManyMouse on a background thread, so events are drained at the mice's own report rate
+/// rather than once per frame. ManyMouse is initialized, polled and shut down on that thread, and
+/// all other access goes through channels. On Windows this is required, not just tidy: the
+/// WM_INPUT backend's hidden window only receives messages on the thread that created it.
+pub struct PollThread {
+    commands: Sender<PollCommand>,
+    messages: Receiver<PollMessage>,
+}
+
+impl PollThread {
+    /// Starts the thread and waits for ManyMouse to initialize, returning the names of the
+    /// devices it found.
+    pub fn spawn(backend: ManyMouseBackend) -> Result<(Self, Vec<String>), Box<dyn Error>> {
+        let (command_sender, command_receiver) = crossbeam_channel::unbounded();
+        let (message_sender, message_receiver) = crossbeam_channel::unbounded();
+
+        thread::Builder::new()
+            .name("mischief poll".to_owned())
+            .spawn(move || run(backend, command_receiver, message_sender))?;
+
+        match message_receiver.recv()? {
+            PollMessage::Devices(names) => Ok((
+                Self {
+                    commands: command_sender,
+                    messages: message_receiver,
+                },
+                names,
+            )),
+            PollMessage::Error(error) => Err(error.into()),
+            PollMessage::Event(..) => Err("ManyMouse sent an event before its devices".into()),
+        }
+    }
+
+    pub fn request_rescan(&self) {
+        // The thread only exits once this side is dropped, so sending can't fail.
+        let _ = self.commands.send(PollCommand::Rescan);
+    }
+
+    pub fn try_recv(&self) -> Option<PollMessage> {
+        self.messages.try_recv().ok()
+    }
+}
+
+fn run(backend: ManyMouseBackend, commands: Receiver<PollCommand>, messages: Sender<PollMessage>) {
+    let mut session = match ManyMouseSession::init_with_backend(backend) {
+        Ok(session) => session,
+        Err(error) => {
+            let _ = messages.send(PollMessage::Error(error.to_string()));
+            return;
+        }
+    };
+    println!(
+        "Found {} mice using {}",
+        session.devices.len(),
+        session.driver_name().unwrap_or_default()
+    );
+    let _ = messages.send(PollMessage::Devices(device_names(&session)));
+
+    // After a polling error, wait for a rescan to restart ManyMouse rather than spinning on it.
+    let mut failed = false;
+    loop {
+        let command = match failed {
+            true => commands.recv().map_err(|_| TryRecvError::Disconnected),
+            false => commands.try_recv(),
+        };
+        match command {
+            Ok(PollCommand::Rescan) => {
+                let _span = info_span!("mischief_rescan").entered();
+                let message = match session.reenumerate() {
+                    Ok(()) => PollMessage::Devices(device_names(&session)),
+                    Err(error) => PollMessage::Error(format!("Failed to rescan mice: {}", error)),
+                };
+                failed = matches!(message, PollMessage::Error(_));
+                let _ = messages.send(message);
+            }
+            // The session was dropped, so nobody is listening anymore.
+            Err(TryRecvError::Disconnected) => return,
+            Err(TryRecvError::Empty) => {}
+        }
+        if failed {
+            continue;
+        }
+
+        let mut polled_any = false;
+        loop {
+            match session.poll_event() {
+                Ok(Some(event)) => {
+                    polled_any = true;
+                    if messages
+                        .send(PollMessage::Event(event, Instant::now()))
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+                Ok(None) => break,
+                Err(error) => {
+                    let _ = messages.send(PollMessage::Error(error.to_string()));
+                    failed = true;
+                    break;
+                }
+            }
+        }
+        if !polled_any {
+            thread::sleep(IDLE_SLEEP);
+        }
+    }
+}
+
+fn device_names(session: &ManyMouseSession) -> Vec<String> {
+    session
+        .devices
+        .iter()
+        .map(|device| device.name.clone())
+        .collect()
+}
@@ -0,0 +1,74 @@
+use bevy::{input::touch::TouchPhase, prelude::*, utils::HashMap, window::PrimaryWindow};
+
+use super::{MischiefEventData, MischiefEventInjector, TOUCH_DEVICES};
+
+// Fingers currently down, by Bevy touch id, with the entry of TOUCH_DEVICES each one drives and
+// the button it pressed.
+#[derive(Resource, Default)]
+pub struct TouchFingers(HashMap<u64, (u32, u32)>);
+
+/// Turns touches into `MischiefEvent`s, so two fingers can stand in for two mice. A finger going
+/// down presses the left button if it's on the left half of the window and the right button
+/// otherwise, so it claims the matching cursor, and then drives that cursor like an absolute
+/// pointing device. A finger's device id is reused by the next finger once it's lifted.
+pub fn translate_touches(
+    mut fingers: ResMut<TouchFingers>,
+    mut touches: EventReader<TouchInput>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut injector: ResMut<MischiefEventInjector>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let size = Vec2::new(window.width(), window.height());
+
+    for touch in touches.iter() {
+        // Window positions have y pointing down, like ManyMouse's absolute axes.
+        let position = (touch.position / size).clamp(Vec2::ZERO, Vec2::ONE);
+        let (device, button) = match touch.phase {
+            TouchPhase::Started => {
+                let free = TOUCH_DEVICES
+                    .into_iter()
+                    .find(|device| !fingers.0.values().any(|(used, _)| used == device));
+                // Extra fingers beyond the two cursors are ignored.
+                let Some(device) = free else {
+                    continue;
+                };
+                let button = match position.x < 0.5 {
+                    true => 0,
+                    false => 1,
+                };
+                fingers.0.insert(touch.id, (device, button));
+                (device, button)
+            }
+            _ => match fingers.0.get(&touch.id) {
+                Some(&finger) => finger,
+                None => continue,
+            },
+        };
+
+        let mut send = |event_data| injector.send(device, event_data);
+        send(MischiefEventData::AbsMotion {
+            x: Some(position.x),
+            y: None,
+        });
+        send(MischiefEventData::AbsMotion {
+            x: None,
+            y: Some(position.y),
+        });
+        match touch.phase {
+            TouchPhase::Started => send(MischiefEventData::Button {
+                button,
+                pressed: true,
+            }),
+            TouchPhase::Ended | TouchPhase::Canceled => {
+                fingers.0.remove(&touch.id);
+                send(MischiefEventData::Button {
+                    button,
+                    pressed: false,
+                });
+            }
+            TouchPhase::Moved => {}
+        }
+    }
+}